@@ -4,7 +4,7 @@ fn main() {
     let cron = CronExpressionBuilder::new()
         .at_time("08:00")
         .and_then(|s| s.on_week_days(vec![1, 2, 3, 4, 5]))
-        .and_then(|s| Ok(s.compile()));
+        .and_then(|s| s.try_compile());
 
     match cron {
         Ok(cron_string) => println!("Generated cron: {}", cron_string),
@@ -0,0 +1,123 @@
+//! Compile-time validated cron expressions for natural-cron.
+use natural_cron::{CronSchedule, CronValidators};
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{LitStr, parse_macro_input};
+
+/// Parses a five- or six-field cron expression at compile time and expands to
+/// a `natural_cron::CronSchedule` built without any runtime parsing, so it can
+/// initialise a `const`.
+///
+/// ```
+/// use natural_cron::CronSchedule;
+/// use natural_cron_macros::cron;
+///
+/// const OFFICE_HOURS: CronSchedule = cron!("*/15 9-17 * * MON-FRI");
+/// assert_eq!(OFFICE_HOURS.to_string(), "0,15,30,45 9-17 * * 1-5");
+/// ```
+///
+/// Invalid expressions fail the build, pointing at the offending field:
+///
+/// ```compile_fail
+/// let schedule = natural_cron_macros::cron!("0 24 * * *");
+/// ```
+///
+/// So do expressions that can never fire:
+///
+/// ```compile_fail
+/// let schedule = natural_cron_macros::cron!("0 0 31 2 *");
+/// ```
+#[proc_macro]
+pub fn cron(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    match expand(&literal) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(literal: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let expression = literal.value();
+    let schedule = CronSchedule::parse(&expression)
+        .map_err(|message| syn::Error::new(field_span(literal, &expression), message))?;
+    CronValidators::validate_satisfiable(&expression)
+        .map_err(|message| syn::Error::new(literal.span(), message))?;
+
+    let seconds = bits(&schedule.seconds());
+    let minutes = bits(&schedule.minutes());
+    let hours = bits(&schedule.hours()) as u32;
+    let days_of_month = bits(&schedule.days_of_month()) as u32;
+    let months = bits(&schedule.months()) as u16;
+    let days_of_week = bits(&schedule.days_of_week()) as u8;
+    let day_of_month_restricted = schedule.is_day_of_month_restricted();
+    let day_of_week_restricted = schedule.is_day_of_week_restricted();
+    let has_seconds = schedule.has_seconds();
+
+    Ok(quote! {
+        ::natural_cron::CronSchedule::from_parts(
+            #seconds,
+            #minutes,
+            #hours,
+            #days_of_month,
+            #months,
+            #days_of_week,
+            #day_of_month_restricted,
+            #day_of_week_restricted,
+            #has_seconds,
+        )
+    })
+}
+
+fn bits(values: &[u32]) -> u64 {
+    values.iter().fold(0, |acc, v| acc | (1 << v))
+}
+
+/// Finds the span of the field that makes the expression invalid. Narrowing a
+/// span inside a string literal needs a nightly compiler; on stable the whole
+/// literal is highlighted and the message names the field.
+fn field_span(literal: &LitStr, expression: &str) -> Span {
+    let fields = field_offsets(expression);
+    if fields.len() != 5 && fields.len() != 6 {
+        return literal.span();
+    }
+
+    for (index, (offset, field)) in fields.iter().enumerate() {
+        // Check the field alone, with every other field left as a wildcard
+        let probe: Vec<&str> = (0..fields.len())
+            .map(|i| if i == index { *field } else { "*" })
+            .collect();
+        if CronSchedule::parse(&probe.join(" ")).is_err() {
+            // The literal's source text starts with its opening quote
+            let source = literal.token().to_string();
+            let quote = source.find('"').map_or(0, |q| q + 1);
+            if source[quote..].starts_with(expression) {
+                let start = quote + offset;
+                if let Some(span) = literal.token().subspan(start..start + field.len()) {
+                    return span;
+                }
+            }
+            return literal.span();
+        }
+    }
+    literal.span()
+}
+
+fn field_offsets(expression: &str) -> Vec<(usize, &str)> {
+    let mut fields = Vec::new();
+    let mut start = None;
+    for (i, c) in expression.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                fields.push((s, &expression[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        fields.push((s, &expression[s..]));
+    }
+    fields
+}
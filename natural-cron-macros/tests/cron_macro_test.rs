@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use natural_cron::CronSchedule;
+    use natural_cron_macros::cron;
+
+    const WEEKDAY_MORNINGS: CronSchedule = cron!("30 8 * * MON-FRI");
+
+    #[test]
+    fn expands_to_a_const_schedule() {
+        assert_eq!(
+            WEEKDAY_MORNINGS,
+            CronSchedule::parse("30 8 * * 1-5").unwrap()
+        );
+    }
+
+    #[test]
+    fn matches_runtime_parsing() {
+        for (expanded, expression) in [
+            (cron!("*/15 9-17 * * *"), "*/15 9-17 * * *"),
+            (cron!("0 0 1,15 * 1"), "0 0 1,15 * 1"),
+            (cron!("*/10 * * * * *"), "*/10 * * * * *"),
+            (cron!("0 12 * JAN,JUL SUN"), "0 12 * JAN,JUL SUN"),
+        ] {
+            assert_eq!(
+                expanded,
+                CronSchedule::parse(expression).unwrap(),
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn evaluates_without_parsing() {
+        let after = NaiveDate::from_ymd_opt(2025, 3, 8)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        assert_eq!(
+            WEEKDAY_MORNINGS.next_after(&after).unwrap().to_string(),
+            "2025-03-10 08:30:00"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Schedules that run every other week.
+//!
+//! Five-field cron has no notion of weeks, so "every other Monday at 10:00"
+//! cannot be compiled. The builder records which weeks to keep with
+//! [`CronExpressionBuilder::on_odd_weeks`], [`CronExpressionBuilder::on_even_weeks`]
+//! or [`CronExpressionBuilder::every_other_week`], and an
+//! [`AlternateWeekSchedule`] evaluates it:
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use natural_cron::CronExpressionBuilder;
+//! use natural_cron::alternate_weeks::AlternateWeekSchedule;
+//!
+//! // Sprint reviews every other Monday, starting on 2025-01-06
+//! let builder = CronExpressionBuilder::new()
+//!     .at_time("10:00")
+//!     .unwrap()
+//!     .on_week_days(vec![1])
+//!     .unwrap()
+//!     .every_other_week(NaiveDate::from_ymd_opt(2025, 1, 6).unwrap())
+//!     .unwrap();
+//! assert!(builder.requires_runtime_filter());
+//! assert!(builder.clone().try_compile().is_err());
+//!
+//! let schedule = AlternateWeekSchedule::from_builder(&builder).unwrap();
+//! let after = NaiveDate::from_ymd_opt(2025, 1, 7).unwrap().and_hms_opt(0, 0, 0).unwrap();
+//! assert_eq!(
+//!     schedule.next_after(&after),
+//!     NaiveDate::from_ymd_opt(2025, 1, 20).unwrap().and_hms_opt(10, 0, 0)
+//! );
+//! ```
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+
+use crate::blackout::BlackoutSchedule;
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::{CronSchedule, Schedule};
+use crate::runtime_days::{RuntimeDays, missing_runtime_days};
+
+/// Which weeks, Monday to Sunday, a schedule runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlternateWeeks {
+    /// Odd ISO week numbers. Years with 53 weeks end in an odd week, so
+    /// weeks 53 and 1 both run.
+    Odd,
+    /// Even ISO week numbers
+    Even,
+    /// The week of the anchor date and every second week before and after it
+    From(NaiveDate),
+}
+
+impl AlternateWeeks {
+    /// Checks if the date falls in one of the weeks
+    pub fn matches(&self, date: &NaiveDate) -> bool {
+        match self {
+            AlternateWeeks::Odd => !date.iso_week().week().is_multiple_of(2),
+            AlternateWeeks::Even => date.iso_week().week().is_multiple_of(2),
+            AlternateWeeks::From(anchor) => {
+                (monday_of(date) - monday_of(anchor)).num_weeks() % 2 == 0
+            }
+        }
+    }
+}
+
+impl fmt::Display for AlternateWeeks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlternateWeeks::Odd => f.write_str("odd ISO weeks"),
+            AlternateWeeks::Even => f.write_str("even ISO weeks"),
+            AlternateWeeks::From(anchor) => write!(f, "every other week from {}", anchor),
+        }
+    }
+}
+
+fn monday_of(date: &NaiveDate) -> NaiveDate {
+    *date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+/// A schedule that runs only in every other week
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlternateWeekSchedule {
+    schedule: BlackoutSchedule,
+    weeks: AlternateWeeks,
+    /// A business day or days before month end also set on the builder
+    also: RuntimeDays,
+}
+
+impl AlternateWeekSchedule {
+    /// Runs at the times of the schedule in the given weeks
+    pub fn new(schedule: CronSchedule, weeks: AlternateWeeks) -> Self {
+        Self {
+            schedule: BlackoutSchedule::new(schedule, Vec::new()),
+            weeks,
+            also: RuntimeDays::default(),
+        }
+    }
+
+    /// Takes the alternate weeks, blackouts and any other runtime days set
+    /// on a builder
+    pub fn from_builder(builder: &CronExpressionBuilder) -> Result<Self, String> {
+        let Some(weeks) = builder.alternate_weeks() else {
+            return Err(missing_runtime_days("alternate weeks"));
+        };
+        Ok(Self {
+            schedule: BlackoutSchedule::from_builder(builder)?,
+            weeks,
+            also: RuntimeDays {
+                alternate_weeks: None,
+                ..RuntimeDays::from_builder(builder)
+            },
+        })
+    }
+
+    pub fn weeks(&self) -> AlternateWeeks {
+        self.weeks
+    }
+
+    /// Returns the first run strictly after the given time
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        self.schedule.next_on_dates(after, |date| {
+            self.weeks.matches(date) && self.also.matches(date)
+        })
+    }
+
+    /// Returns up to `count` runs strictly after the given time
+    pub fn upcoming(&self, after: &NaiveDateTime, count: usize) -> Vec<NaiveDateTime> {
+        Schedule::upcoming(self, after, count)
+    }
+}
+
+impl Schedule for AlternateWeekSchedule {
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        AlternateWeekSchedule::next_after(self, after)
+    }
+
+    fn resolution(&self) -> Duration {
+        self.schedule.resolution()
+    }
+}
@@ -0,0 +1,425 @@
+//! Command-line interface to natural-cron.
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use chrono::{DateTime, Datelike, Local, TimeZone, Utc};
+use chrono_tz::Tz;
+use natural_cron::CronExpressionBuilder;
+use natural_cron::CronSchedule;
+use natural_cron::cron_explainer::CronExplainer;
+use natural_cron::cron_lint::CronLinter;
+use natural_cron::interfaces::CronTimeUnit;
+use natural_cron::quartz::QuartzConverter;
+
+const USAGE: &str = "\
+Usage: natural-cron [--json] <command> [arguments]
+
+Commands:
+  build [flags]                      Build an expression; flags apply in the order given:
+                                       --at HH:MM
+                                       --minutes LIST      --hours LIST
+                                       --weekdays LIST     --days-of-month LIST
+                                       --months LIST
+                                       --every minute|hour|day|month|week
+                                       --every-x N minute|hour|day-of-month|month|day-of-week
+  validate <expr>                    Check an expression
+  explain <expr>                     Describe an expression in plain English
+  lint <expr> [--tz ZONE]            Warn about likely mistakes in an expression
+  next <expr> [-n COUNT] [--tz ZONE] List the next occurrences (default 5, at most 10000,
+                                     local time)
+  convert <expr> --to quartz|cron    Convert between cron and Quartz syntax
+
+Options:
+  --json                             Print results as JSON
+
+Exit codes:
+  0  success
+  1  invalid expression or input
+  2  invalid command-line usage
+  3  internal error";
+
+const DEFAULT_COUNT: usize = 5;
+const MAX_COUNT: usize = 10_000;
+
+enum CliError {
+    /// The expression or a flag value is not valid
+    Invalid(String),
+    /// The command line itself is malformed
+    Usage(String),
+    /// Something went wrong that is not the caller's fault
+    Internal(String),
+}
+
+impl CliError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Invalid(_) => 1,
+            CliError::Usage(_) => 2,
+            CliError::Internal(_) => 3,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            CliError::Invalid(_) => "invalid_input",
+            CliError::Usage(_) => "usage",
+            CliError::Internal(_) => "internal",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            CliError::Invalid(m) | CliError::Usage(m) | CliError::Internal(m) => m,
+        }
+    }
+}
+
+/// The result of a command, printed as text or as a JSON object
+struct Output {
+    text: String,
+    json: Vec<(&'static str, Json)>,
+}
+
+enum Json {
+    String(String),
+    Bool(bool),
+    Array(Vec<String>),
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let json = args.iter().any(|a| a == "--json");
+    let args: Vec<String> = args.into_iter().filter(|a| a != "--json").collect();
+
+    let result = std::panic::catch_unwind(|| run(&args))
+        .unwrap_or_else(|_| Err(CliError::Internal("unexpected panic".to_string())))
+        .and_then(|output| print_output(&output, json));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            if json {
+                eprintln!(
+                    "{{\"error\":{},\"kind\":{}}}",
+                    json_string(error.message()),
+                    json_string(error.kind())
+                );
+            } else {
+                eprintln!("error: {}", error.message());
+                if let CliError::Usage(_) = error {
+                    eprintln!("\n{}", USAGE);
+                }
+            }
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<Output, CliError> {
+    let (command, rest) = args
+        .split_first()
+        .ok_or_else(|| CliError::Usage("missing command".to_string()))?;
+    match command.as_str() {
+        "build" => build(rest),
+        "validate" => validate(rest),
+        "explain" => explain(rest),
+        "lint" => lint(rest),
+        "next" => next(rest),
+        "convert" => convert(rest),
+        "help" | "--help" | "-h" => Ok(Output {
+            text: USAGE.to_string(),
+            json: vec![("usage", Json::String(USAGE.to_string()))],
+        }),
+        other => Err(CliError::Usage(format!("unknown command '{}'", other))),
+    }
+}
+
+fn build(args: &[String]) -> Result<Output, CliError> {
+    let mut builder = CronExpressionBuilder::new();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| CliError::Usage(format!("{} needs a value", flag)))
+        };
+        builder = match flag.as_str() {
+            "--at" => builder.at_time(value()?),
+            "--minutes" => builder.at_minutes(parse_list(value()?)?),
+            "--hours" => builder.at_hours(parse_list(value()?)?),
+            "--weekdays" => builder.on_week_days(parse_list(value()?)?),
+            "--days-of-month" => builder.on_days_of_month(parse_list(value()?)?),
+            "--months" => builder.during_months(parse_list(value()?)?),
+            "--every" => builder.every(value()?),
+            "--every-x" => {
+                let interval = parse_number(value()?)?;
+                let unit = parse_unit(value()?)?;
+                builder.every_x(interval, unit)
+            }
+            other => return Err(CliError::Usage(format!("unknown build flag '{}'", other))),
+        }
+        .map_err(CliError::Invalid)?;
+    }
+
+    let expression = builder.try_compile().map_err(CliError::Invalid)?;
+    Ok(Output {
+        text: expression.clone(),
+        json: vec![("expression", Json::String(expression))],
+    })
+}
+
+fn validate(args: &[String]) -> Result<Output, CliError> {
+    let expression = single_expression(args)?;
+    CronSchedule::parse(expression).map_err(CliError::Invalid)?;
+    Ok(Output {
+        text: format!("valid: {}", expression),
+        json: vec![
+            ("expression", Json::String(expression.to_string())),
+            ("valid", Json::Bool(true)),
+        ],
+    })
+}
+
+fn explain(args: &[String]) -> Result<Output, CliError> {
+    let expression = single_expression(args)?;
+    let description = CronExplainer::explain(expression).map_err(CliError::Invalid)?;
+    Ok(Output {
+        text: description.clone(),
+        json: vec![
+            ("expression", Json::String(expression.to_string())),
+            ("description", Json::String(description)),
+        ],
+    })
+}
+
+fn lint(args: &[String]) -> Result<Output, CliError> {
+    let mut expression = None;
+    let mut zone = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tz" => zone = Some(parse_zone(args.next())?),
+            _ if expression.is_none() => expression = Some(arg.as_str()),
+            other => return Err(CliError::Usage(format!("unexpected argument '{}'", other))),
+        }
+    }
+    let expression = expression.ok_or_else(|| CliError::Usage("missing expression".to_string()))?;
+    let warnings = match zone {
+        Some(zone) => CronLinter::lint_in_zone(expression, &zone, Utc::now().year()),
+        None => CronLinter::lint(expression),
+    }
+    .map_err(CliError::Invalid)?;
+    let warnings: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+
+    Ok(Output {
+        text: if warnings.is_empty() {
+            "no warnings".to_string()
+        } else {
+            warnings
+                .iter()
+                .map(|w| format!("warning: {}", w))
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+        json: vec![
+            ("expression", Json::String(expression.to_string())),
+            ("warnings", Json::Array(warnings)),
+        ],
+    })
+}
+
+fn next(args: &[String]) -> Result<Output, CliError> {
+    let mut expression = None;
+    let mut count = DEFAULT_COUNT;
+    let mut zone = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--count" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| CliError::Usage(format!("{} needs a value", arg)))?;
+                count = value
+                    .parse()
+                    .map_err(|_| CliError::Usage(format!("invalid count '{}'", value)))?;
+                if count > MAX_COUNT {
+                    return Err(CliError::Usage(format!(
+                        "count '{}' is above the limit of {}",
+                        value, MAX_COUNT
+                    )));
+                }
+            }
+            "--tz" => zone = Some(parse_zone(args.next())?),
+            _ if expression.is_none() => expression = Some(arg.as_str()),
+            other => return Err(CliError::Usage(format!("unexpected argument '{}'", other))),
+        }
+    }
+    let expression = expression.ok_or_else(|| CliError::Usage("missing expression".to_string()))?;
+    let schedule = CronSchedule::parse(expression).map_err(CliError::Invalid)?;
+
+    let (zone_name, occurrences) = match zone {
+        Some(zone) => (
+            zone.name().to_string(),
+            upcoming(&schedule, Utc::now().with_timezone(&zone), count),
+        ),
+        None => (
+            "local".to_string(),
+            upcoming(&schedule, Local::now(), count),
+        ),
+    };
+    Ok(Output {
+        text: occurrences.join("\n"),
+        json: vec![
+            ("expression", Json::String(expression.to_string())),
+            ("timezone", Json::String(zone_name)),
+            ("occurrences", Json::Array(occurrences)),
+        ],
+    })
+}
+
+fn upcoming<Z: TimeZone>(schedule: &CronSchedule, now: DateTime<Z>, count: usize) -> Vec<String>
+where
+    Z::Offset: std::fmt::Display,
+{
+    let mut occurrences = Vec::new();
+    let mut cursor = now;
+    while occurrences.len() < count {
+        match schedule.next_after_in(&cursor) {
+            Some(next) => {
+                occurrences.push(next.format("%Y-%m-%dT%H:%M:%S%:z").to_string());
+                cursor = next;
+            }
+            None => break,
+        }
+    }
+    occurrences
+}
+
+fn convert(args: &[String]) -> Result<Output, CliError> {
+    let mut expression = None;
+    let mut target = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--to" => {
+                target = Some(
+                    args.next()
+                        .ok_or_else(|| CliError::Usage("--to needs a value".to_string()))?
+                        .as_str(),
+                )
+            }
+            _ if expression.is_none() => expression = Some(arg.as_str()),
+            other => return Err(CliError::Usage(format!("unexpected argument '{}'", other))),
+        }
+    }
+    let expression = expression.ok_or_else(|| CliError::Usage("missing expression".to_string()))?;
+    let converted = match target {
+        Some("quartz") => QuartzConverter::to_quartz(expression),
+        Some("cron") => QuartzConverter::from_quartz(expression),
+        Some(other) => {
+            return Err(CliError::Usage(format!(
+                "unknown conversion target '{}'",
+                other
+            )));
+        }
+        None => return Err(CliError::Usage("missing --to".to_string())),
+    }
+    .map_err(CliError::Invalid)?;
+
+    Ok(Output {
+        text: converted.clone(),
+        json: vec![
+            ("expression", Json::String(expression.to_string())),
+            ("to", Json::String(target.unwrap_or_default().to_string())),
+            ("result", Json::String(converted)),
+        ],
+    })
+}
+
+fn single_expression(args: &[String]) -> Result<&str, CliError> {
+    match args {
+        [expression] => Ok(expression),
+        [] => Err(CliError::Usage("missing expression".to_string())),
+        _ => Err(CliError::Usage(
+            "expected a single expression; quote it to keep the fields together".to_string(),
+        )),
+    }
+}
+
+fn parse_zone(value: Option<&String>) -> Result<Tz, CliError> {
+    let value = value.ok_or_else(|| CliError::Usage("--tz needs a value".to_string()))?;
+    value
+        .parse()
+        .map_err(|_| CliError::Invalid(format!("unknown time zone '{}'", value)))
+}
+
+fn parse_number(value: &str) -> Result<i32, CliError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| CliError::Invalid(format!("invalid number '{}'", value)))
+}
+
+fn parse_list(value: &str) -> Result<Vec<i32>, CliError> {
+    value.split(',').map(parse_number).collect()
+}
+
+fn parse_unit(value: &str) -> Result<CronTimeUnit, CliError> {
+    match value {
+        "minute" => Ok(CronTimeUnit::Minute),
+        "hour" => Ok(CronTimeUnit::Hour),
+        "day-of-month" => Ok(CronTimeUnit::DayOfMonth),
+        "month" => Ok(CronTimeUnit::Month),
+        "day-of-week" => Ok(CronTimeUnit::DayOfWeek),
+        other => Err(CliError::Invalid(format!("invalid time unit '{}'", other))),
+    }
+}
+
+fn print_output(output: &Output, json: bool) -> Result<(), CliError> {
+    let text = if json {
+        let fields: Vec<String> = output
+            .json
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    Json::String(s) => json_string(s),
+                    Json::Bool(b) => b.to_string(),
+                    Json::Array(items) => format!(
+                        "[{}]",
+                        items
+                            .iter()
+                            .map(|i| json_string(i))
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    ),
+                };
+                format!("{}:{}", json_string(key), value)
+            })
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    } else {
+        output.text.clone()
+    };
+
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{}", text)
+        .and_then(|_| stdout.flush())
+        .map_err(|e| CliError::Internal(format!("failed to write output: {}", e)))
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
@@ -0,0 +1,225 @@
+//! Blackout windows: times a schedule must not run.
+//!
+//! Blackouts are added to a builder with
+//! [`CronExpressionBuilder::except`](crate::CronExpressionBuilder::except).
+//! When the schedule without the blackout is still a plain cron expression,
+//! `try_compile()` emits it. Otherwise the blackout needs a runtime filter, which
+//! [`BlackoutSchedule`] provides:
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use natural_cron::CronExpressionBuilder;
+//! use natural_cron::blackout::{Blackout, BlackoutSchedule};
+//! use natural_cron::interfaces::CronTimeUnit;
+//!
+//! // Every 5 minutes except during the Sunday 02:00-04:00 maintenance window
+//! let builder = CronExpressionBuilder::new()
+//!     .every_x(5, CronTimeUnit::Minute)
+//!     .unwrap()
+//!     .except(Blackout::schedule("* 2-3 * * 0").unwrap())
+//!     .unwrap();
+//! assert!(builder.requires_runtime_filter());
+//!
+//! let schedule = BlackoutSchedule::from_builder(&builder).unwrap();
+//! // 2025-03-09 is a Sunday
+//! let after = NaiveDate::from_ymd_opt(2025, 3, 9).unwrap().and_hms_opt(1, 58, 0).unwrap();
+//! assert_eq!(
+//!     schedule.next_after(&after),
+//!     NaiveDate::from_ymd_opt(2025, 3, 9).unwrap().and_hms_opt(4, 0, 0)
+//! );
+//! ```
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::composite::CompositeSchedule;
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::{CronSchedule, SEARCH_LIMIT_YEARS, Schedule};
+use crate::time_of_day::TimeOfDay;
+
+/// A span of time during which a schedule does not run
+#[derive(Debug, Clone, PartialEq)]
+pub enum Blackout {
+    /// Every time matching another schedule
+    Schedule(CronSchedule),
+    /// The same time of day every day, from `start` up to but excluding
+    /// `end`; a window whose end is before its start runs past midnight
+    TimeWindow { start: NaiveTime, end: NaiveTime },
+    /// Whole days from `start` through `end`
+    DateRange { start: NaiveDate, end: NaiveDate },
+}
+
+impl Blackout {
+    /// Blacks out every time matching a five- or six-field cron expression
+    pub fn schedule(expression: &str) -> Result<Self, String> {
+        Ok(Blackout::Schedule(CronSchedule::parse(expression)?))
+    }
+
+    /// Blacks out a daily window between two times without seconds, such as
+    /// `HH:MM` or `10pm`, end excluded
+    pub fn time_window(start: &str, end: &str) -> Result<Self, String> {
+        let time = |time: &str| -> Result<NaiveTime, String> {
+            match time.parse::<TimeOfDay>()? {
+                parsed if parsed.second() != 0 => Err(format!("Invalid time format: {}", time)),
+                parsed => Ok(parsed.into()),
+            }
+        };
+        let (start, end) = (time(start)?, time(end)?);
+        if start == end {
+            return Err(format!(
+                "Invalid time window: {}-{}. Start and end must differ.",
+                start.format("%H:%M"),
+                end.format("%H:%M")
+            ));
+        }
+        Ok(Blackout::TimeWindow { start, end })
+    }
+
+    /// Blacks out whole days from `start` through `end`
+    pub fn date_range(start: NaiveDate, end: NaiveDate) -> Result<Self, String> {
+        if start > end {
+            return Err(format!(
+                "Invalid date range: {} to {}. The start is after the end.",
+                start, end
+            ));
+        }
+        Ok(Blackout::DateRange { start, end })
+    }
+
+    /// Checks if the time falls inside the blackout
+    pub fn contains(&self, time: &NaiveDateTime) -> bool {
+        match self {
+            Blackout::Schedule(schedule) => schedule.matches(time),
+            Blackout::TimeWindow { start, end } => in_window(&time.time(), start, end),
+            Blackout::DateRange { start, end } => (*start..=*end).contains(&time.date()),
+        }
+    }
+
+    /// A time from which the next run of a schedule is no longer held up by
+    /// this blackout, for a time inside it
+    fn resume_after(&self, time: &NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            Blackout::Schedule(_) => Some(*time),
+            Blackout::TimeWindow { end, .. } => {
+                let date = if time.time() < *end {
+                    time.date()
+                } else {
+                    time.date().succ_opt()?
+                };
+                Some(date.and_time(*end) - Duration::seconds(1))
+            }
+            Blackout::DateRange { end, .. } => end.and_hms_opt(23, 59, 59),
+        }
+    }
+}
+
+impl fmt::Display for Blackout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Blackout::Schedule(schedule) => write!(f, "{}", schedule),
+            Blackout::TimeWindow { start, end } => {
+                write!(f, "{}-{}", start.format("%H:%M"), end.format("%H:%M"))
+            }
+            Blackout::DateRange { start, end } => write!(f, "{} to {}", start, end),
+        }
+    }
+}
+
+pub(crate) fn in_window(time: &NaiveTime, start: &NaiveTime, end: &NaiveTime) -> bool {
+    if start < end {
+        start <= time && time < end
+    } else {
+        time >= start || time < end
+    }
+}
+
+/// A schedule evaluated together with its blackouts at runtime, for
+/// blackouts a single cron expression cannot express
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlackoutSchedule {
+    schedule: CompositeSchedule,
+    blackouts: Vec<Blackout>,
+}
+
+impl BlackoutSchedule {
+    /// Takes a cron schedule, or a [`CompositeSchedule`] of several lines
+    pub fn new(schedule: impl Into<CompositeSchedule>, blackouts: Vec<Blackout>) -> Self {
+        Self {
+            schedule: schedule.into(),
+            blackouts,
+        }
+    }
+
+    /// Takes the builder's schedule, one part per line of times, and its
+    /// blackouts
+    pub fn from_builder(builder: &CronExpressionBuilder) -> Result<Self, String> {
+        Ok(Self::new(
+            builder.unfiltered_schedules()?,
+            builder.blackouts().to_vec(),
+        ))
+    }
+
+    /// The schedule before blackouts are applied
+    pub fn schedule(&self) -> &CompositeSchedule {
+        &self.schedule
+    }
+
+    pub fn blackouts(&self) -> &[Blackout] {
+        &self.blackouts
+    }
+
+    /// Checks if the time matches the schedule and is outside every blackout
+    pub fn matches(&self, time: &NaiveDateTime) -> bool {
+        self.schedule.matches(time) && !self.blackouts.iter().any(|b| b.contains(time))
+    }
+
+    /// Returns the first run strictly after the given time that is outside
+    /// every blackout
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        let limit = after.year() + SEARCH_LIMIT_YEARS;
+        let mut cursor = *after;
+        while cursor.year() <= limit {
+            let next = self.schedule.next_after(&cursor)?;
+            match self.blackouts.iter().find(|b| b.contains(&next)) {
+                None => return Some(next),
+                Some(blackout) => cursor = blackout.resume_after(&next)?.max(next),
+            }
+        }
+        None
+    }
+
+    /// Returns the first run strictly after the given time that is outside
+    /// every blackout and on a date `keep` accepts
+    pub(crate) fn next_on_dates(
+        &self,
+        after: &NaiveDateTime,
+        keep: impl Fn(&NaiveDate) -> bool,
+    ) -> Option<NaiveDateTime> {
+        let limit = after.year() + SEARCH_LIMIT_YEARS;
+        let mut cursor = *after;
+        while cursor.year() <= limit {
+            let next = self.next_after(&cursor)?;
+            if keep(&next.date()) {
+                return Some(next);
+            }
+            // No other run that day can match either
+            cursor = next.date().and_hms_opt(23, 59, 59)?;
+        }
+        None
+    }
+
+    /// Returns up to `count` runs strictly after the given time
+    pub fn upcoming(&self, after: &NaiveDateTime, count: usize) -> Vec<NaiveDateTime> {
+        Schedule::upcoming(self, after, count)
+    }
+}
+
+impl Schedule for BlackoutSchedule {
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        BlackoutSchedule::next_after(self, after)
+    }
+
+    fn resolution(&self) -> Duration {
+        self.schedule.resolution()
+    }
+}
@@ -0,0 +1,395 @@
+//! A thread-based scheduler for code that does not run inside an async runtime.
+//!
+//! Jobs are plain closures registered with a [`CronExpressionBuilder`] schedule.
+//! A dispatcher thread wakes up when the next job is due and hands it to a
+//! fixed-size pool of worker threads.
+//!
+//! ```rust
+//! use natural_cron::{BlockingScheduler, CronExpressionBuilder};
+//!
+//! fn main() -> Result<(), String> {
+//!     let handle = BlockingScheduler::new()
+//!         .with_workers(2)?
+//!         .add_job("report", CronExpressionBuilder::new().at_time("08:00")?, || {
+//!             println!("building report");
+//!         })?
+//!         .start()?;
+//!     handle.shutdown();
+//!     Ok(())
+//! }
+//! ```
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+
+use crate::clock::{Clock, SystemClock};
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::{CronSchedule, Schedule};
+use crate::job_runner::{JobMetrics, JobRunner, OverlapPolicy};
+use crate::job_store::{JobState, JobStore};
+use crate::misfire::MisfirePolicy;
+
+const DEFAULT_WORKERS: usize = 4;
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Per-job settings for how a job reacts to missed and overlapping runs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JobOptions {
+    misfire_policy: MisfirePolicy,
+    overlap_policy: OverlapPolicy,
+}
+
+impl JobOptions {
+    /// Creates the default options: missed runs fire once and runs may overlap
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what happens to occurrences missed while the dispatcher was not running
+    pub fn with_misfire_policy(mut self, policy: MisfirePolicy) -> Self {
+        self.misfire_policy = policy;
+        self
+    }
+
+    /// Sets what happens when the job is due while a previous run is still going
+    pub fn with_overlap_policy(mut self, policy: OverlapPolicy) -> Self {
+        self.overlap_policy = policy;
+        self
+    }
+}
+
+struct ScheduledJob {
+    name: String,
+    schedule: Box<dyn Schedule>,
+    options: JobOptions,
+    runner: Arc<JobRunner>,
+    last_checked: NaiveDateTime,
+    next_run: Option<NaiveDateTime>,
+    /// Kept in every saved state; see [`JobState::first_seen`]
+    first_seen: Option<NaiveDateTime>,
+}
+
+/// Collects jobs and scheduler settings before the threads are started
+pub struct BlockingScheduler {
+    clock: Arc<dyn Clock>,
+    store: Option<Arc<dyn JobStore>>,
+    workers: usize,
+    poll_interval: Duration,
+    jobs: Vec<ScheduledJob>,
+}
+
+impl Default for BlockingScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockingScheduler {
+    /// Creates a scheduler using the system clock and four workers
+    pub fn new() -> Self {
+        Self {
+            clock: Arc::new(SystemClock),
+            store: None,
+            workers: DEFAULT_WORKERS,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Uses the given clock instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Persists run times in the given store and, on start, treats occurrences
+    /// missed since the stored last run as misfires
+    pub fn with_store(mut self, store: Arc<dyn JobStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Sets the number of worker threads that run jobs
+    pub fn with_workers(mut self, workers: usize) -> Result<Self, String> {
+        if workers == 0 {
+            return Err("Invalid worker count: 0. At least one worker is required.".to_string());
+        }
+        self.workers = workers;
+        Ok(self)
+    }
+
+    /// Sets the longest time the dispatcher sleeps before re-reading the clock
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Registers a job to run whenever the schedule fires
+    pub fn add_job<F>(
+        self,
+        name: &str,
+        schedule: CronExpressionBuilder,
+        job: F,
+    ) -> Result<Self, String>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.add_job_with_options(name, schedule, JobOptions::default(), job)
+    }
+
+    /// Registers a job with custom options
+    pub fn add_job_with_options<F>(
+        self,
+        name: &str,
+        schedule: CronExpressionBuilder,
+        options: JobOptions,
+        job: F,
+    ) -> Result<Self, String>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        schedule.validate()?;
+        let schedule = CronSchedule::from_builder(&schedule)?;
+        self.add_job_with_schedule(name, schedule, options, job)
+    }
+
+    /// Registers a job on any schedule, such as an
+    /// [`IntervalSchedule`](crate::interval::IntervalSchedule) or a schedule
+    /// with blackouts
+    pub fn add_job_with_schedule<S, F>(
+        mut self,
+        name: &str,
+        schedule: S,
+        options: JobOptions,
+        job: F,
+    ) -> Result<Self, String>
+    where
+        S: Schedule + 'static,
+        F: Fn() + Send + Sync + 'static,
+    {
+        if self.jobs.iter().any(|j| j.name == name) {
+            return Err(format!("Duplicate job name: {}", name));
+        }
+        self.jobs.push(ScheduledJob {
+            name: name.to_string(),
+            schedule: Box::new(schedule),
+            options,
+            runner: Arc::new(JobRunner::new(Arc::new(job), options.overlap_policy)?),
+            last_checked: NaiveDateTime::default(),
+            next_run: None,
+            first_seen: None,
+        });
+        Ok(self)
+    }
+
+    /// Spawns the dispatcher and worker threads
+    pub fn start(mut self) -> Result<SchedulerHandle, String> {
+        let now = self.clock.now();
+        for job in &mut self.jobs {
+            let stored = match &self.store {
+                Some(store) => store.load(&job.name)?.unwrap_or_default(),
+                None => JobState::default(),
+            };
+            job.first_seen = stored.first_seen;
+            job.last_checked = stored.last_run.or(stored.first_seen).unwrap_or(now);
+            job.next_run = job.schedule.next_after(&job.last_checked);
+            if let (Some(store), None, None) = (&self.store, stored.last_run, stored.first_seen) {
+                // Record the first start so a crash before the first run still
+                // counts the occurrences that follow as missed
+                job.first_seen = Some(now);
+                let state = JobState {
+                    last_run: None,
+                    next_run: job.next_run,
+                    first_seen: job.first_seen,
+                };
+                store.save(&job.name, &state)?;
+            }
+        }
+
+        let (sender, receiver) = mpsc::channel::<Arc<JobRunner>>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let mut workers = Vec::with_capacity(self.workers);
+        for index in 0..self.workers {
+            let receiver = Arc::clone(&receiver);
+            let worker = thread::Builder::new()
+                .name(format!("natural-cron-worker-{}", index))
+                .spawn(move || worker_loop(receiver))
+                .map_err(|e| format!("Failed to spawn worker thread: {}", e))?;
+            workers.push(worker);
+        }
+
+        let shared = Arc::new(Shared {
+            clock: self.clock,
+            store: self.store,
+            jobs: Mutex::new(self.jobs),
+            sender: Mutex::new(Some(sender)),
+            stopped: Mutex::new(false),
+            wake: Condvar::new(),
+        });
+
+        let dispatcher_shared = Arc::clone(&shared);
+        let poll_interval = self.poll_interval;
+        let dispatcher = thread::Builder::new()
+            .name("natural-cron-dispatcher".to_string())
+            .spawn(move || dispatcher_loop(dispatcher_shared, poll_interval))
+            .map_err(|e| format!("Failed to spawn dispatcher thread: {}", e))?;
+
+        Ok(SchedulerHandle {
+            shared,
+            dispatcher: Some(dispatcher),
+            workers,
+        })
+    }
+}
+
+struct Shared {
+    clock: Arc<dyn Clock>,
+    store: Option<Arc<dyn JobStore>>,
+    jobs: Mutex<Vec<ScheduledJob>>,
+    sender: Mutex<Option<Sender<Arc<JobRunner>>>>,
+    stopped: Mutex<bool>,
+    wake: Condvar,
+}
+
+impl Shared {
+    /// Hands every due run to the worker pool and returns how many were sent
+    fn run_pending(&self) -> usize {
+        let now = self.clock.now();
+        let mut dispatched = 0;
+        let sender = self.sender.lock().unwrap();
+        let Some(sender) = sender.as_ref() else {
+            return 0;
+        };
+        for job in self.jobs.lock().unwrap().iter_mut() {
+            if job.next_run.is_some_and(|next| next <= now) {
+                let outcome = job.options.misfire_policy.resolve(
+                    job.schedule.as_ref(),
+                    &job.last_checked,
+                    &now,
+                );
+                job.runner.record_misfired(outcome.skipped);
+                for _ in &outcome.fire {
+                    if job.runner.admit() && sender.send(Arc::clone(&job.runner)).is_ok() {
+                        dispatched += 1;
+                    }
+                }
+                job.last_checked = now;
+                job.next_run = outcome.next_run;
+                // Saved while the jobs are locked, so the dispatcher and
+                // `SchedulerHandle::run_pending` save in the order they ran
+                if let Some(store) = &self.store {
+                    let state = JobState {
+                        last_run: Some(now),
+                        next_run: job.next_run,
+                        first_seen: job.first_seen,
+                    };
+                    // The dispatcher has nobody to report to; a failed save only
+                    // means the occurrence may be treated as missed after a restart
+                    let _ = store.save(&job.name, &state);
+                }
+            }
+        }
+        dispatched
+    }
+
+    /// How long the dispatcher may sleep before the earliest job is due
+    fn time_until_next_run(&self, poll_interval: Duration) -> Duration {
+        let now = self.clock.now();
+        self.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|job| job.next_run)
+            .min()
+            .and_then(|next| (next - now).to_std().ok())
+            .map_or(poll_interval, |wait| wait.min(poll_interval))
+    }
+}
+
+fn dispatcher_loop(shared: Arc<Shared>, poll_interval: Duration) {
+    loop {
+        shared.run_pending();
+        let wait = shared.time_until_next_run(poll_interval);
+        let stopped = shared.stopped.lock().unwrap();
+        if *stopped {
+            return;
+        }
+        let (stopped, _) = shared.wake.wait_timeout(stopped, wait).unwrap();
+        if *stopped {
+            return;
+        }
+    }
+}
+
+fn worker_loop(receiver: Arc<Mutex<Receiver<Arc<JobRunner>>>>) {
+    loop {
+        let runner = receiver.lock().unwrap().recv();
+        match runner {
+            Ok(runner) => runner.run(),
+            Err(_) => return,
+        }
+    }
+}
+
+/// A running scheduler; dropping it shuts the scheduler down gracefully
+pub struct SchedulerHandle {
+    shared: Arc<Shared>,
+    dispatcher: Option<JoinHandle<()>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl SchedulerHandle {
+    /// Immediately dispatches every job that is due according to the clock
+    pub fn run_pending(&self) -> usize {
+        self.shared.run_pending()
+    }
+
+    /// Returns the next time the named job will run
+    pub fn next_run(&self, name: &str) -> Option<NaiveDateTime> {
+        self.shared
+            .jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|job| job.name == name)
+            .and_then(|job| job.next_run)
+    }
+
+    /// Returns the run counters of the named job
+    pub fn metrics(&self, name: &str) -> Option<JobMetrics> {
+        self.shared
+            .jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|job| job.name == name)
+            .map(|job| job.runner.metrics())
+    }
+
+    /// Stops dispatching, lets already queued jobs finish and joins all threads
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        *self.shared.stopped.lock().unwrap() = true;
+        self.shared.wake.notify_all();
+        if let Some(dispatcher) = self.dispatcher.take() {
+            let _ = dispatcher.join();
+        }
+        // Closing the channel lets the workers drain the queue and exit
+        self.shared.sender.lock().unwrap().take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for SchedulerHandle {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
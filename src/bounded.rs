@@ -0,0 +1,200 @@
+//! Schedules with a start, an end or a limited number of runs, and schedules
+//! that run only once.
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use natural_cron::CronExpressionBuilder;
+//! use natural_cron::bounded::BoundedSchedule;
+//!
+//! // Every day at 09:00, five times, starting on 2026-11-01
+//! let start = NaiveDate::from_ymd_opt(2026, 11, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+//! let builder = CronExpressionBuilder::new().at_time("09:00").unwrap();
+//! let schedule = BoundedSchedule::from_builder(&builder)
+//!     .and_then(|s| s.starting(start))
+//!     .and_then(|s| s.with_max_runs(5))
+//!     .unwrap();
+//!
+//! let runs = schedule.upcoming(&start, 10);
+//! assert_eq!(runs.len(), 5);
+//! assert_eq!(runs.last(), NaiveDate::from_ymd_opt(2026, 11, 5).unwrap().and_hms_opt(9, 0, 0).as_ref());
+//! ```
+use chrono::{Duration, NaiveDateTime};
+
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::{CronSchedule, Schedule};
+
+/// The most runs [`BoundedSchedule::with_max_runs`] counts ahead
+pub const MAX_RUN_LIMIT: usize = 100_000;
+
+/// Another schedule limited to runs from `start` through `end`, and to its
+/// first `max_runs` runs from the start
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundedSchedule<S = CronSchedule> {
+    schedule: S,
+    start: Option<NaiveDateTime>,
+    end: Option<NaiveDateTime>,
+    max_runs: Option<usize>,
+    /// The last run allowed by the run limit, counted once when it is set
+    last_counted_run: Option<NaiveDateTime>,
+}
+
+impl BoundedSchedule<CronSchedule> {
+    /// Bounds the schedule of a builder
+    pub fn from_builder(builder: &CronExpressionBuilder) -> Result<Self, String> {
+        Ok(Self::new(CronSchedule::from_builder(builder)?))
+    }
+}
+
+impl<S: Schedule> BoundedSchedule<S> {
+    /// Starts without bounds
+    pub fn new(schedule: S) -> Self {
+        Self {
+            schedule,
+            start: None,
+            end: None,
+            max_runs: None,
+            last_counted_run: None,
+        }
+    }
+
+    /// Runs no earlier than `start`; a run at `start` itself counts
+    pub fn starting(mut self, start: NaiveDateTime) -> Result<Self, String> {
+        check_bounds(Some(start), self.end)?;
+        self.start = Some(start);
+        if let Some(runs) = self.max_runs {
+            self.last_counted_run = Some(self.count_runs(start, runs)?);
+        }
+        Ok(self)
+    }
+
+    /// Runs no later than `end`; a run at `end` itself counts
+    pub fn until(mut self, end: NaiveDateTime) -> Result<Self, String> {
+        check_bounds(self.start, Some(end))?;
+        self.end = Some(end);
+        Ok(self)
+    }
+
+    /// Stops after the first `runs` runs from the start, which has to be set.
+    /// The runs are counted here, so at most [`MAX_RUN_LIMIT`] of them, and
+    /// the schedule has to run that often.
+    pub fn with_max_runs(mut self, runs: usize) -> Result<Self, String> {
+        if runs == 0 {
+            return Err("Invalid run limit: 0. At least one run is required.".to_string());
+        }
+        if runs > MAX_RUN_LIMIT {
+            return Err(format!(
+                "Invalid run limit: {}. At most {} runs can be counted; use an end instead.",
+                runs, MAX_RUN_LIMIT
+            ));
+        }
+        let Some(start) = self.start else {
+            return Err(
+                "Invalid run limit: runs are counted from the start, so set one first.".to_string(),
+            );
+        };
+        self.last_counted_run = Some(self.count_runs(start, runs)?);
+        self.max_runs = Some(runs);
+        Ok(self)
+    }
+
+    /// The schedule without its bounds
+    pub fn schedule(&self) -> &S {
+        &self.schedule
+    }
+
+    pub fn start(&self) -> Option<NaiveDateTime> {
+        self.start
+    }
+
+    pub fn end(&self) -> Option<NaiveDateTime> {
+        self.end
+    }
+
+    pub fn max_runs(&self) -> Option<usize> {
+        self.max_runs
+    }
+
+    /// Returns the first run strictly after the given time that is inside
+    /// the bounds
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        let cursor = match self.start {
+            Some(start) if *after < start => start - Duration::nanoseconds(1),
+            _ => *after,
+        };
+        let next = self.schedule.next_after(&cursor)?;
+        let last = match (self.end, self.last_counted_run) {
+            (Some(end), Some(last)) => Some(end.min(last)),
+            (end, last) => end.or(last),
+        };
+        match last {
+            Some(last) if next > last => None,
+            _ => Some(next),
+        }
+    }
+
+    /// Returns up to `count` runs strictly after the given time
+    pub fn upcoming(&self, after: &NaiveDateTime, count: usize) -> Vec<NaiveDateTime> {
+        Schedule::upcoming(self, after, count)
+    }
+
+    /// Finds the last of the first `runs` runs from `start`
+    fn count_runs(&self, start: NaiveDateTime, runs: usize) -> Result<NaiveDateTime, String> {
+        let mut cursor = start - Duration::nanoseconds(1);
+        for counted in 0..runs {
+            cursor = self.schedule.next_after(&cursor).ok_or_else(|| {
+                format!(
+                    "Invalid run limit: {}. The schedule ends after {} of them.",
+                    runs, counted
+                )
+            })?;
+        }
+        Ok(cursor)
+    }
+}
+
+impl<S: Schedule> Schedule for BoundedSchedule<S> {
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        BoundedSchedule::next_after(self, after)
+    }
+
+    fn resolution(&self) -> Duration {
+        self.schedule.resolution()
+    }
+}
+
+fn check_bounds(start: Option<NaiveDateTime>, end: Option<NaiveDateTime>) -> Result<(), String> {
+    match (start, end) {
+        (Some(start), Some(end)) if end < start => Err(format!(
+            "Invalid bounds: the end {} is before the start {}.",
+            end, start
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Runs once, at a fixed time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OneShotSchedule {
+    at: NaiveDateTime,
+}
+
+impl OneShotSchedule {
+    pub fn new(at: NaiveDateTime) -> Self {
+        Self { at }
+    }
+
+    pub fn at(&self) -> NaiveDateTime {
+        self.at
+    }
+
+    /// Returns the run if it is strictly after the given time
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        (*after < self.at).then_some(self.at)
+    }
+}
+
+impl Schedule for OneShotSchedule {
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        OneShotSchedule::next_after(self, after)
+    }
+}
@@ -0,0 +1,584 @@
+//! Holiday calendars for keeping schedules off non-business days.
+//!
+//! Cron cannot express "not on exchange holidays". A [`Calendar`] lists the
+//! holidays and weekend days, and a [`CalendarSchedule`] evaluates a cron
+//! schedule against it, skipping runs on those days or moving them to the
+//! previous or next business day.
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use natural_cron::CronExpressionBuilder;
+//! use natural_cron::calendar::{Calendar, CalendarSchedule, HolidayShift};
+//!
+//! let calendar = Calendar::parse(
+//!     "fixed dec 25      # Christmas Day\n\
+//!      last mon may      # Memorial Day\n\
+//!      easter -2         # Good Friday\n",
+//! )
+//! .unwrap();
+//! let builder = CronExpressionBuilder::new().at_time("18:00").unwrap();
+//! let schedule = CalendarSchedule::from_builder(&builder, calendar, HolidayShift::Skip).unwrap();
+//!
+//! // 2025-04-18 is Good Friday, and the weekend follows
+//! let after = NaiveDate::from_ymd_opt(2025, 4, 17).unwrap().and_hms_opt(19, 0, 0).unwrap();
+//! assert_eq!(
+//!     schedule.next_after(&after),
+//!     NaiveDate::from_ymd_opt(2025, 4, 21).unwrap().and_hms_opt(18, 0, 0)
+//! );
+//! ```
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+
+use crate::blackout::BlackoutSchedule;
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::{CronSchedule, DAY_NAMES, MONTH_NAMES, SEARCH_LIMIT_YEARS, Schedule};
+use crate::runtime_days::{RuntimeDays, missing_runtime_days};
+
+/// Saturday and Sunday, as a bitset of weekdays numbered from Sunday
+const SATURDAY_AND_SUNDAY: u8 = 1 << 6 | 1;
+
+/// Longest run of consecutive non-business days looked through when shifting
+const MAX_CLOSED_DAYS: i64 = 366;
+
+/// A rule that marks one day a year, or a single date, as a holiday
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HolidayRule {
+    /// The same month and day every year
+    Fixed { month: u32, day: u32 },
+    /// The nth weekday (0 = Sunday) of a month; `n` of -1 means the last one
+    NthWeekday { n: i32, weekday: u32, month: u32 },
+    /// A number of days after Easter Sunday; negative for days before it
+    Easter { offset: i64 },
+    /// A single date
+    Date(NaiveDate),
+}
+
+impl HolidayRule {
+    /// Checks if the rule falls on the given date
+    pub fn matches(&self, date: &NaiveDate) -> bool {
+        match *self {
+            HolidayRule::Fixed { month, day } => date.month() == month && date.day() == day,
+            HolidayRule::NthWeekday { n, weekday, month } => {
+                date.month() == month
+                    && date.weekday().num_days_from_sunday() == weekday
+                    && if n < 0 {
+                        (*date + Duration::days(7)).month() != month
+                    } else {
+                        (date.day() as i32 - 1) / 7 + 1 == n
+                    }
+            }
+            // The offset may move the holiday into the previous or next year
+            HolidayRule::Easter { offset } => (date.year() - 1..=date.year() + 1)
+                .filter_map(easter_sunday)
+                .any(|easter| easter + Duration::days(offset) == *date),
+            HolidayRule::Date(holiday) => holiday == *date,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match *self {
+            HolidayRule::Fixed { month, day } => {
+                // 2000 is a leap year, so February 29 is accepted
+                if NaiveDate::from_ymd_opt(2000, month, day).is_none() {
+                    return Err(format!("Invalid holiday date: month {} day {}", month, day));
+                }
+            }
+            HolidayRule::NthWeekday { n, weekday, month } => {
+                if !(1..=5).contains(&n) && n != -1 {
+                    return Err(format!(
+                        "Invalid weekday occurrence: {}. Value should be between 1 and 5, or -1 for the last one.",
+                        n
+                    ));
+                }
+                if weekday > 6 {
+                    return Err(format!(
+                        "Invalid weekday: {}. Value should be between 0 and 6.",
+                        weekday
+                    ));
+                }
+                if !(1..=12).contains(&month) {
+                    return Err(format!(
+                        "Invalid month: {}. Value should be between 1 and 12.",
+                        month
+                    ));
+                }
+            }
+            HolidayRule::Easter { offset } => {
+                if offset.abs() > 365 {
+                    return Err(format!(
+                        "Invalid Easter offset: {}. Value should be between -365 and 365.",
+                        offset
+                    ));
+                }
+            }
+            HolidayRule::Date(_) => {}
+        }
+        Ok(())
+    }
+}
+
+/// Writes the rule as a line of [`Calendar::parse`]
+impl fmt::Display for HolidayRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let month = |month: u32| MONTH_NAMES[month as usize - 1].to_ascii_lowercase();
+        let weekday = |day: u32| DAY_NAMES[day as usize].to_ascii_lowercase();
+        match *self {
+            HolidayRule::Fixed { month: m, day } => write!(f, "fixed {} {}", month(m), day),
+            HolidayRule::NthWeekday {
+                n: -1,
+                weekday: d,
+                month: m,
+            } => {
+                write!(f, "last {} {}", weekday(d), month(m))
+            }
+            HolidayRule::NthWeekday {
+                n,
+                weekday: d,
+                month: m,
+            } => {
+                write!(f, "nth {} {} {}", n, weekday(d), month(m))
+            }
+            HolidayRule::Easter { offset } => write!(f, "easter {}", offset),
+            HolidayRule::Date(date) => write!(f, "date {}", date),
+        }
+    }
+}
+
+/// Holidays and weekend days on which scheduled jobs should not run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calendar {
+    rules: Vec<HolidayRule>,
+    /// Weekdays as a bitset, bit 0 for Sunday
+    weekend: u8,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            weekend: SATURDAY_AND_SUNDAY,
+        }
+    }
+}
+
+impl Calendar {
+    /// A calendar without holidays and with a Saturday and Sunday weekend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a holiday rule
+    pub fn with_rule(mut self, rule: HolidayRule) -> Result<Self, String> {
+        rule.validate()?;
+        self.rules.push(rule);
+        Ok(self)
+    }
+
+    /// Replaces the weekend days (0 = Sunday); an empty list means every
+    /// day that is not a holiday is a business day
+    pub fn with_weekend(mut self, days: Vec<u32>) -> Result<Self, String> {
+        self.weekend = 0;
+        for day in days {
+            self.weekend |= 1 << weekday(day)?;
+        }
+        Ok(self)
+    }
+
+    /// Reads a calendar from a file in the format accepted by [`Calendar::parse`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read calendar {}: {}", path.display(), e))?;
+        Self::parse(&content)
+    }
+
+    /// Parses a calendar with one rule per line; `#` starts a comment:
+    ///
+    /// ```text
+    /// weekend sat sun    # the default; `weekend none` for no weekend
+    /// fixed jan 1        # month and day
+    /// nth 3 mon jan      # third Monday of January
+    /// last mon may       # last Monday of May
+    /// easter -2          # days after Easter Sunday
+    /// date 2025-01-09    # a single date
+    /// ```
+    ///
+    /// Months and weekdays are names or numbers (0 = Sunday).
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut calendar = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            calendar = calendar
+                .parse_line(line)
+                .map_err(|e| format!("Invalid calendar line {}: {}", index + 1, e))?;
+        }
+        Ok(calendar)
+    }
+
+    fn parse_line(self, line: &str) -> Result<Self, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words[..] {
+            ["weekend", "none"] => self.with_weekend(Vec::new()),
+            ["weekend", ref days @ ..] => {
+                let days = days
+                    .iter()
+                    .map(|day| parse_named(day, &DAY_NAMES, 0, "weekday"))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.with_weekend(days)
+            }
+            ["fixed", month, day] => self.with_rule(HolidayRule::Fixed {
+                month: parse_named(month, &MONTH_NAMES, 1, "month")?,
+                day: day
+                    .parse()
+                    .map_err(|_| format!("Invalid day of month: {}", day))?,
+            }),
+            ["nth", n, weekday, month] => self.with_rule(HolidayRule::NthWeekday {
+                n: n.parse()
+                    .map_err(|_| format!("Invalid weekday occurrence: {}", n))?,
+                weekday: parse_named(weekday, &DAY_NAMES, 0, "weekday")?,
+                month: parse_named(month, &MONTH_NAMES, 1, "month")?,
+            }),
+            ["last", weekday, month] => self.with_rule(HolidayRule::NthWeekday {
+                n: -1,
+                weekday: parse_named(weekday, &DAY_NAMES, 0, "weekday")?,
+                month: parse_named(month, &MONTH_NAMES, 1, "month")?,
+            }),
+            ["easter", offset] => self.with_rule(HolidayRule::Easter {
+                offset: offset
+                    .trim_start_matches('+')
+                    .parse()
+                    .map_err(|_| format!("Invalid Easter offset: {}", offset))?,
+            }),
+            ["date", date] => self.with_rule(HolidayRule::Date(
+                NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map_err(|_| format!("Invalid date: {}", date))?,
+            )),
+            _ => Err(format!("Unknown rule: '{}'", line)),
+        }
+    }
+
+    /// Checks if any holiday rule falls on the date
+    pub fn is_holiday(&self, date: &NaiveDate) -> bool {
+        self.rules.iter().any(|rule| rule.matches(date))
+    }
+
+    /// Checks if the date is neither a weekend day nor a holiday
+    pub fn is_business_day(&self, date: &NaiveDate) -> bool {
+        self.weekend & (1 << date.weekday().num_days_from_sunday()) == 0 && !self.is_holiday(date)
+    }
+
+    /// The first business day after the date
+    pub fn next_business_day(&self, date: &NaiveDate) -> Option<NaiveDate> {
+        (1..=MAX_CLOSED_DAYS)
+            .map(|days| *date + Duration::days(days))
+            .find(|day| self.is_business_day(day))
+    }
+
+    /// The last business day before the date
+    pub fn previous_business_day(&self, date: &NaiveDate) -> Option<NaiveDate> {
+        (1..=MAX_CLOSED_DAYS)
+            .map(|days| *date - Duration::days(days))
+            .find(|day| self.is_business_day(day))
+    }
+
+    /// The business days of a month, in order
+    pub fn business_days_of_month(&self, year: i32, month: u32) -> Vec<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, month, 1)
+            .into_iter()
+            .flat_map(|first| first.iter_days())
+            .take_while(|date| date.month() == month)
+            .filter(|date| self.is_business_day(date))
+            .collect()
+    }
+
+    /// Whether business days are exactly Monday to Friday: no holidays and
+    /// a Saturday and Sunday weekend
+    pub fn is_weekdays_only(&self) -> bool {
+        self.rules.is_empty() && self.weekend == SATURDAY_AND_SUNDAY
+    }
+}
+
+/// Writes the calendar in the format read by [`Calendar::parse`], one rule
+/// per line; the weekend only when it is not Saturday and Sunday
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        if self.weekend != SATURDAY_AND_SUNDAY {
+            let days: Vec<String> = (0..7)
+                .filter(|day| self.weekend & (1 << day) != 0)
+                .map(|day| DAY_NAMES[day].to_ascii_lowercase())
+                .collect();
+            if days.is_empty() {
+                lines.push("weekend none".to_string());
+            } else {
+                lines.push(format!("weekend {}", days.join(" ")));
+            }
+        }
+        lines.extend(self.rules.iter().map(HolidayRule::to_string));
+        f.write_str(&lines.join("\n"))
+    }
+}
+
+/// A business day counted from the start or the end of the month
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusinessDay {
+    /// The nth business day, counting from 1
+    Nth(u32),
+    /// The last business day
+    Last,
+}
+
+impl BusinessDay {
+    /// Checks if the date is this business day of its month
+    pub fn matches(&self, date: &NaiveDate, calendar: &Calendar) -> bool {
+        let days = calendar.business_days_of_month(date.year(), date.month());
+        let day = match self {
+            BusinessDay::Nth(n) => n.checked_sub(1).and_then(|i| days.get(i as usize)),
+            BusinessDay::Last => days.last(),
+        };
+        day == Some(date)
+    }
+}
+
+impl fmt::Display for BusinessDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BusinessDay::Nth(n) => {
+                let suffix = match (n % 10, n % 100) {
+                    (1, 11) | (2, 12) | (3, 13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                write!(f, "{}{} business day of the month", n, suffix)
+            }
+            BusinessDay::Last => f.write_str("last business day of the month"),
+        }
+    }
+}
+
+/// What happens to a run that falls on a non-business day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HolidayShift {
+    /// The run is dropped
+    #[default]
+    Skip,
+    /// The run happens at the same time on the previous business day
+    Previous,
+    /// The run happens at the same time on the next business day
+    Next,
+}
+
+/// A cron schedule that only runs on the business days of a calendar
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarSchedule {
+    schedule: CronSchedule,
+    calendar: Calendar,
+    shift: HolidayShift,
+}
+
+impl CalendarSchedule {
+    pub fn new(schedule: CronSchedule, calendar: Calendar, shift: HolidayShift) -> Self {
+        Self {
+            schedule,
+            calendar,
+            shift,
+        }
+    }
+
+    /// Attaches a calendar to the schedule a builder describes
+    pub fn from_builder(
+        builder: &CronExpressionBuilder,
+        calendar: Calendar,
+        shift: HolidayShift,
+    ) -> Result<Self, String> {
+        Ok(Self::new(
+            CronSchedule::from_builder(builder)?,
+            calendar,
+            shift,
+        ))
+    }
+
+    pub fn schedule(&self) -> &CronSchedule {
+        &self.schedule
+    }
+
+    pub fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    /// Returns the first run strictly after the given time. Runs shifted onto
+    /// a day the schedule already runs on at the same time happen only once.
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        let daily = self.schedule.every_day();
+        let mut date = after.date();
+        let limit = date.year() + SEARCH_LIMIT_YEARS;
+
+        while date.year() <= limit {
+            if self.calendar.is_business_day(&date) && self.runs_on(&date) {
+                let start = if date == after.date() {
+                    *after
+                } else {
+                    date.pred_opt()?.and_hms_opt(23, 59, 59)?
+                };
+                if let Some(next) = daily.next_after(&start).filter(|n| n.date() == date) {
+                    return Some(next);
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    /// Returns up to `count` runs strictly after the given time
+    pub fn upcoming(&self, after: &NaiveDateTime, count: usize) -> Vec<NaiveDateTime> {
+        Schedule::upcoming(self, after, count)
+    }
+
+    /// Checks if the business day gets runs, either its own or ones shifted
+    /// from the non-business days next to it
+    fn runs_on(&self, date: &NaiveDate) -> bool {
+        if self.schedule.matches_date(date) {
+            return true;
+        }
+        let step = match self.shift {
+            HolidayShift::Skip => return false,
+            // Runs move forward onto this day from the closed days before it
+            HolidayShift::Next => -1,
+            HolidayShift::Previous => 1,
+        };
+        (1..=MAX_CLOSED_DAYS)
+            .map(|days| *date + Duration::days(days * step))
+            .take_while(|day| !self.calendar.is_business_day(day))
+            .any(|day| self.schedule.matches_date(&day))
+    }
+}
+
+impl Schedule for CalendarSchedule {
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        CalendarSchedule::next_after(self, after)
+    }
+
+    fn resolution(&self) -> Duration {
+        self.schedule.resolution()
+    }
+}
+
+/// A schedule that runs only on one business day of each month, such as the
+/// third or the last. Months without that many business days are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusinessDaySchedule {
+    schedule: BlackoutSchedule,
+    day: BusinessDay,
+    calendar: Calendar,
+    /// Days before month end or alternate weeks also set on the builder
+    also: RuntimeDays,
+}
+
+impl BusinessDaySchedule {
+    /// Runs at the times of the schedule on the given business day
+    pub fn new(schedule: CronSchedule, day: BusinessDay, calendar: Calendar) -> Self {
+        Self {
+            schedule: BlackoutSchedule::new(schedule, Vec::new()),
+            day,
+            calendar,
+            also: RuntimeDays::default(),
+        }
+    }
+
+    /// Takes the business day, holidays, blackouts and any other runtime
+    /// days set on a builder
+    pub fn from_builder(builder: &CronExpressionBuilder) -> Result<Self, String> {
+        let Some(day) = builder.business_day() else {
+            return Err(missing_runtime_days("business day"));
+        };
+        Ok(Self {
+            schedule: BlackoutSchedule::from_builder(builder)?,
+            day,
+            calendar: builder.calendar().clone(),
+            also: RuntimeDays {
+                business_day: None,
+                ..RuntimeDays::from_builder(builder)
+            },
+        })
+    }
+
+    pub fn day(&self) -> BusinessDay {
+        self.day
+    }
+
+    pub fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    /// Returns the first run strictly after the given time
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        self.schedule.next_on_dates(after, |date| {
+            self.day.matches(date, &self.calendar) && self.also.matches(date)
+        })
+    }
+
+    /// Returns up to `count` runs strictly after the given time
+    pub fn upcoming(&self, after: &NaiveDateTime, count: usize) -> Vec<NaiveDateTime> {
+        Schedule::upcoming(self, after, count)
+    }
+}
+
+impl Schedule for BusinessDaySchedule {
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        BusinessDaySchedule::next_after(self, after)
+    }
+
+    fn resolution(&self) -> Duration {
+        self.schedule.resolution()
+    }
+}
+
+/// Easter Sunday in the Gregorian calendar (anonymous Gregorian algorithm)
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+fn weekday(day: u32) -> Result<u32, String> {
+    match day {
+        0..=6 => Ok(day),
+        7 => Ok(0),
+        _ => Err(format!(
+            "Invalid weekday: {}. Value should be between 0 and 6.",
+            day
+        )),
+    }
+}
+
+/// Parses a number or a three-letter name such as `mon` or `jan`
+fn parse_named(value: &str, names: &[&str], first: u32, kind: &str) -> Result<u32, String> {
+    if let Ok(number) = value.parse::<u32>() {
+        return Ok(number);
+    }
+    names
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(value))
+        .map(|index| index as u32 + first)
+        .ok_or_else(|| format!("Invalid {} name: {}", kind, value))
+}
@@ -0,0 +1,54 @@
+//! Time sources used by the schedulers.
+//!
+//! Schedulers never read the system time directly; they ask a [`Clock`].
+//! Swapping in a [`ManualClock`] makes scheduling fully deterministic in tests.
+use std::sync::Mutex;
+
+use chrono::{Duration, Local, NaiveDateTime};
+
+/// A source of the current local wall-clock time
+pub trait Clock: Send + Sync {
+    /// Returns the current time
+    fn now(&self) -> NaiveDateTime;
+}
+
+/// Reads the time from the operating system
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
+
+/// A clock that only moves when told to
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<NaiveDateTime>,
+}
+
+impl ManualClock {
+    /// Creates a clock frozen at the given time
+    pub fn new(start: NaiveDateTime) -> Self {
+        Self {
+            now: Mutex::new(start),
+        }
+    }
+
+    /// Moves the clock to the given time
+    pub fn set(&self, time: NaiveDateTime) {
+        *self.now.lock().unwrap() = time;
+    }
+
+    /// Moves the clock forward by the given duration
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> NaiveDateTime {
+        *self.now.lock().unwrap()
+    }
+}
@@ -0,0 +1,104 @@
+//! Schedules made of several cron expressions, running at the union of
+//! their times.
+//!
+//! "08:00 and 17:30" shares neither its minute nor its hour, so no single
+//! five-field expression covers it.
+//! [`CronExpressionBuilder::at_times`] splits such times into as few lines as
+//! possible, and a [`CompositeSchedule`] evaluates them together:
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use natural_cron::CronExpressionBuilder;
+//! use natural_cron::composite::CompositeSchedule;
+//!
+//! let builder = CronExpressionBuilder::new().at_times(vec!["08:00", "17:30"]).unwrap();
+//! assert_eq!(
+//!     builder.clone().compile_lines(),
+//!     Ok(vec!["0 8 * * *".to_string(), "30 17 * * *".to_string()])
+//! );
+//!
+//! let schedule = CompositeSchedule::from_builder(&builder).unwrap();
+//! let after = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap().and_hms_opt(9, 0, 0).unwrap();
+//! assert_eq!(
+//!     schedule.next_after(&after),
+//!     NaiveDate::from_ymd_opt(2025, 3, 10).unwrap().and_hms_opt(17, 30, 0)
+//! );
+//! ```
+use chrono::{Duration, NaiveDateTime};
+
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::{CronSchedule, Schedule};
+
+/// The union of several cron schedules
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositeSchedule {
+    schedules: Vec<CronSchedule>,
+}
+
+impl CompositeSchedule {
+    /// Runs whenever any of the schedules does
+    pub fn new(schedules: Vec<CronSchedule>) -> Result<Self, String> {
+        if schedules.is_empty() {
+            return Err(
+                "Invalid composite schedule: at least one schedule is required.".to_string(),
+            );
+        }
+        Ok(Self { schedules })
+    }
+
+    /// Takes one schedule per cron line of the builder
+    pub fn from_builder(builder: &CronExpressionBuilder) -> Result<Self, String> {
+        let schedules = builder
+            .lines()
+            .iter()
+            .map(CronSchedule::from_builder)
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(schedules)
+    }
+
+    pub fn schedules(&self) -> &[CronSchedule] {
+        &self.schedules
+    }
+
+    /// Checks if any of the schedules matches the time
+    pub fn matches(&self, time: &NaiveDateTime) -> bool {
+        self.schedules.iter().any(|schedule| schedule.matches(time))
+    }
+
+    /// Returns the earliest run of any schedule strictly after the given time
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        self.schedules
+            .iter()
+            .filter_map(|schedule| schedule.next_after(after))
+            .min()
+    }
+
+    /// Returns up to `count` runs strictly after the given time; a time
+    /// matched by several schedules runs once
+    pub fn upcoming(&self, after: &NaiveDateTime, count: usize) -> Vec<NaiveDateTime> {
+        Schedule::upcoming(self, after, count)
+    }
+}
+
+/// A single line
+impl From<CronSchedule> for CompositeSchedule {
+    fn from(schedule: CronSchedule) -> Self {
+        Self {
+            schedules: vec![schedule],
+        }
+    }
+}
+
+impl Schedule for CompositeSchedule {
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        CompositeSchedule::next_after(self, after)
+    }
+
+    fn resolution(&self) -> Duration {
+        self.schedules
+            .iter()
+            .map(CronSchedule::resolution)
+            .min()
+            .unwrap_or_else(|| Duration::minutes(1))
+    }
+}
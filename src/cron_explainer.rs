@@ -0,0 +1,190 @@
+//! Human-readable descriptions of cron expressions.
+use crate::cron_schedule::CronSchedule;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const DAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+pub struct CronExplainer;
+
+impl CronExplainer {
+    /// Describes a five- or six-field cron expression in plain English,
+    /// e.g. `30 8 * * 1-5` becomes "At 08:30 on every day-of-week from Monday through Friday."
+    pub fn explain(expression: &str) -> Result<String, String> {
+        CronSchedule::parse(expression)?;
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let (seconds, fields) = if fields.len() == 6 {
+            (Some(fields[0]), &fields[1..])
+        } else {
+            (None, &fields[..])
+        };
+        let (minute, hour, day_of_month, month, day_of_week) =
+            (fields[0], fields[1], fields[2], fields[3], fields[4]);
+
+        let mut description = match (single_number(minute), single_number(hour)) {
+            (Some(m), Some(h)) => format!("at {:02}:{:02}", h, m),
+            _ => {
+                let mut time = format!("at {}", describe_field(minute, "minute", &plain));
+                if hour != "*" {
+                    time.push_str(&format!(" past {}", describe_field(hour, "hour", &plain)));
+                }
+                time
+            }
+        };
+        if let Some(seconds) = seconds.filter(|s| *s != "0") {
+            description = format!(
+                "{}, {}",
+                describe_field(seconds, "second", &plain),
+                description
+            );
+        }
+
+        let day_of_month_set = !is_wildcard(day_of_month);
+        if day_of_month_set {
+            description.push_str(&format!(
+                " on {}",
+                describe_field(day_of_month, "day-of-month", &plain)
+            ));
+        }
+        if !is_wildcard(day_of_week) {
+            // Both day fields set means either one matching is enough
+            let joiner = if day_of_month_set { " or on" } else { " on" };
+            description.push_str(&format!(
+                "{} {}",
+                joiner,
+                describe_field(day_of_week, "day-of-week", &day_name)
+            ));
+        }
+        if !is_wildcard(month) {
+            description.push_str(&format!(
+                " in {}",
+                describe_field(month, "month", &month_name)
+            ));
+        }
+
+        let mut chars = description.chars();
+        Ok(match chars.next() {
+            Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+            None => description,
+        })
+    }
+}
+
+fn is_wildcard(field: &str) -> bool {
+    field == "*" || field == "?"
+}
+
+fn single_number(field: &str) -> Option<u32> {
+    field.parse().ok()
+}
+
+/// Names a value of a numeric field; `None` means it is not a known value
+type Namer = dyn Fn(&str) -> Option<String>;
+
+fn plain(value: &str) -> Option<String> {
+    value.parse::<u32>().ok().map(|v| v.to_string())
+}
+
+fn month_name(value: &str) -> Option<String> {
+    lookup(value, &MONTH_NAMES, 1)
+}
+
+fn day_name(value: &str) -> Option<String> {
+    // 7 is an alias for Sunday
+    if value == "7" {
+        return Some(DAY_NAMES[0].to_string());
+    }
+    lookup(value, &DAY_NAMES, 0)
+}
+
+fn lookup(value: &str, names: &[&str], first: usize) -> Option<String> {
+    if let Ok(number) = value.parse::<usize>() {
+        return number
+            .checked_sub(first)
+            .and_then(|index| names.get(index))
+            .map(|name| name.to_string());
+    }
+    names
+        .iter()
+        .find(|name| name[..3].eq_ignore_ascii_case(value))
+        .map(|name| name.to_string())
+}
+
+fn describe_field(field: &str, unit: &str, namer: &Namer) -> String {
+    if is_wildcard(field) {
+        return format!("every {}", unit);
+    }
+    let items: Vec<String> = field
+        .split(',')
+        .map(|item| describe_item(item, unit, namer))
+        .collect();
+    // Plain numbers need the unit once: "minute 0 and 30"
+    let first = field.split(',').next().unwrap_or_default();
+    if first.parse::<u32>().is_ok() && namer(first) == plain(first) {
+        format!("{} {}", unit, join(&items))
+    } else {
+        join(&items)
+    }
+}
+
+fn describe_item(item: &str, unit: &str, namer: &Namer) -> String {
+    let name = |value: &str| namer(value).unwrap_or_else(|| value.to_string());
+
+    let (range, step) = match item.split_once('/') {
+        Some((range, step)) => (range, Some(step)),
+        None => (item, None),
+    };
+    let every = match step {
+        Some(step) => format!("every {} {}", ordinal(step), unit),
+        None => format!("every {}", unit),
+    };
+
+    if is_wildcard(range) {
+        every
+    } else if let Some((start, end)) = range.split_once('-') {
+        format!("{} from {} through {}", every, name(start), name(end))
+    } else if step.is_some() {
+        format!("{} from {}", every, name(range))
+    } else {
+        name(range)
+    }
+}
+
+fn ordinal(value: &str) -> String {
+    let suffix = match value.parse::<u32>() {
+        Ok(n) if (11..=13).contains(&(n % 100)) => "th",
+        Ok(n) if n % 10 == 1 => "st",
+        Ok(n) if n % 10 == 2 => "nd",
+        Ok(n) if n % 10 == 3 => "rd",
+        _ => "th",
+    };
+    format!("{}{}", value, suffix)
+}
+
+fn join(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
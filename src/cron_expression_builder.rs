@@ -1,49 +1,295 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::fmt::Display;
 
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::alternate_weeks::AlternateWeeks;
+use crate::blackout::{Blackout, in_window};
+use crate::calendar::{BusinessDay, Calendar, HolidayRule};
+use crate::composite::CompositeSchedule;
+use crate::cron_schedule::CronSchedule;
 use crate::cron_utils::CronUtils;
 use crate::cron_validator::CronValidators;
-use crate::interfaces::{CronTimeUnit, ScheduleValue};
+use crate::interfaces::CronTimeUnit;
+use crate::month_end;
+use crate::time_of_day::TimeOfDay;
+
+/// One field of the expression: the values it selects as a bitset, and how
+/// to write them back out
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    bits: u64,
+    text: FieldText,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FieldText {
+    /// `*`
+    Wildcard,
+    /// `*/n`
+    Step(i32),
+    /// Comma-separated values, with runs of three or more written as a range
+    List,
+    /// Kept as written, from an existing expression
+    Raw(Box<str>),
+}
+
+impl Field {
+    fn wildcard(min: u32, max: u32) -> Self {
+        Self {
+            bits: range_bits(min, max, 1),
+            text: FieldText::Wildcard,
+        }
+    }
+
+    fn step(interval: i32, min: u32, max: u32) -> Self {
+        // A zero step selects nothing; compile still writes it out as given
+        let bits = match u32::try_from(interval) {
+            Ok(step) if step > 0 => range_bits(min, max, step),
+            _ => 0,
+        };
+        Self {
+            bits,
+            text: FieldText::Step(interval),
+        }
+    }
+
+    fn list(values: &[i32]) -> Self {
+        Self::from_bits(values.iter().fold(0, |bits, v| bits | (1 << v)))
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        Self {
+            bits,
+            text: FieldText::List,
+        }
+    }
+
+    fn is_wildcard(&self) -> bool {
+        match &self.text {
+            FieldText::Wildcard => true,
+            FieldText::Raw(text) => &**text == "*" || &**text == "?",
+            _ => false,
+        }
+    }
+
+    fn format(&self) -> String {
+        match &self.text {
+            FieldText::Wildcard => "*".to_string(),
+            FieldText::Step(interval) => format!("*/{}", interval),
+            FieldText::List => {
+                let values: Vec<i32> = (0..64).filter(|v| self.bits & (1 << v) != 0).collect();
+                CronUtils::format_cron_part(&values)
+            }
+            FieldText::Raw(text) => text.to_string(),
+        }
+    }
+}
+
+fn range_bits(min: u32, max: u32, step: u32) -> u64 {
+    (min..=max)
+        .step_by(step as usize)
+        .fold(0, |bits, v| bits | (1 << v))
+}
+
+fn format_or_wildcard(field: &Option<Field>) -> String {
+    field.as_ref().map_or("*".to_string(), Field::format)
+}
+
+fn bits_or_all(field: &Option<Field>, min: u32, max: u32) -> u64 {
+    field
+        .as_ref()
+        .map_or_else(|| range_bits(min, max, 1), |f| f.bits)
+}
+
+fn is_restricted(field: &Option<Field>) -> bool {
+    field.as_ref().is_some_and(|f| !f.is_wildcard())
+}
+
+/// Splits times of day into the fewest minute-by-hour products, each one a
+/// cron line, as `(minutes, hours)` bitsets ordered by their earliest time
+pub(crate) fn group_times(times: &[(u32, u32)]) -> Vec<(u64, u64)> {
+    // Times sharing a minute go on one line, and so do minutes sharing
+    // their hours; grouping by hour first sometimes needs fewer lines
+    let merge = |by_first: BTreeMap<u32, u64>| {
+        let mut groups: Vec<(u64, u64)> = Vec::new();
+        for (first, others) in by_first {
+            match groups.iter_mut().find(|(_, bits)| *bits == others) {
+                Some((firsts, _)) => *firsts |= 1 << first,
+                None => groups.push((1 << first, others)),
+            }
+        }
+        groups
+    };
+    let mut by_minute = BTreeMap::new();
+    let mut by_hour = BTreeMap::new();
+    for (hour, minute) in times {
+        *by_minute.entry(*minute).or_insert(0) |= 1 << hour;
+        *by_hour.entry(*hour).or_insert(0) |= 1 << minute;
+    }
+    let by_minute = merge(by_minute);
+    let by_hour: Vec<(u64, u64)> = merge(by_hour)
+        .into_iter()
+        .map(|(hours, minutes)| (minutes, hours))
+        .collect();
+    let mut groups = if by_hour.len() < by_minute.len() {
+        by_hour
+    } else {
+        by_minute
+    };
+    groups.sort_by_key(|(minutes, hours)| (hours.trailing_zeros(), minutes.trailing_zeros()));
+    groups
+}
+
+#[cfg(feature = "serde")]
+fn bit_values(bits: u64) -> Vec<u32> {
+    (0..64).filter(|v| bits & (1 << v) != 0).collect()
+}
+
+fn to_bits(values: &[u32]) -> u64 {
+    values.iter().fold(0, |bits, v| bits | (1 << v))
+}
+
+/// Why the builder cannot be folded into a single expression
+enum FoldError<'a> {
+    /// The times of day need one line per group
+    MultipleTimes(usize),
+    /// Cron cannot count business days
+    BusinessDay(BusinessDay),
+    /// Cron cannot count back from the end of the month
+    MonthEnd(u32),
+    /// Cron has no notion of weeks
+    AlternateWeeks(AlternateWeeks),
+    /// Removing it leaves times no single cron expression describes
+    RuntimeFilter(&'a Blackout),
+    /// It covers every run of the schedule
+    Everything(&'a Blackout),
+}
+
+impl FoldError<'_> {
+    /// Whether the schedule still runs, only not as a single expression
+    fn needs_runtime_filter(&self) -> bool {
+        !matches!(self, FoldError::Everything(_) | FoldError::MultipleTimes(_))
+    }
+
+    fn message(&self) -> String {
+        match self {
+            FoldError::MultipleTimes(lines) => format!(
+                "Cannot express these times in a single cron expression; they need {} lines. Use compile_lines() or CompositeSchedule.",
+                lines
+            ),
+            FoldError::BusinessDay(day) => format!(
+                "Cannot express the {} in a cron expression; it requires a runtime filter such as BusinessDaySchedule.",
+                day
+            ),
+            FoldError::MonthEnd(days) => format!(
+                "Cannot express {} in a cron expression; it requires a runtime filter such as MonthEndSchedule.",
+                month_end::describe(*days)
+            ),
+            FoldError::AlternateWeeks(weeks) => format!(
+                "Cannot express {} in a cron expression; it requires a runtime filter such as AlternateWeekSchedule.",
+                weeks
+            ),
+            FoldError::RuntimeFilter(blackout) => format!(
+                "Cannot express blackout '{}' in a cron expression; it requires a runtime filter such as BlackoutSchedule.",
+                blackout
+            ),
+            FoldError::Everything(blackout) => format!(
+                "Invalid blackout: '{}' excludes every run of the schedule.",
+                blackout
+            ),
+        }
+    }
+}
 
-#[derive(Debug, Default, Clone)]
+/// The outcome of folding one blackout
+enum Fold {
+    Folded,
+    RuntimeFilter,
+    Everything,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct CronExpressionBuilder {
-    schedule: HashMap<String, ScheduleValue>,
+    /// Only set once seconds are enabled, for a six-field expression
+    second: Option<Field>,
+    minute: Option<Field>,
+    hour: Option<Field>,
+    day_of_month: Option<Field>,
+    month: Option<Field>,
+    day_of_week: Option<Field>,
+    blackouts: Vec<Blackout>,
+    business_day: Option<BusinessDay>,
+    days_before_month_end: Option<u32>,
+    alternate_weeks: Option<AlternateWeeks>,
+    calendar: Calendar,
+    /// Minutes and hours of each line, when the times set by `at_times`
+    /// need more than one
+    time_groups: Vec<(Field, Field)>,
 }
 
 impl CronExpressionBuilder {
     /// Creates a new instance of the builder
     pub fn new() -> Self {
-        Self {
-            schedule: HashMap::new(),
+        Self::default()
+    }
+
+    /// Creates a builder from an existing five-field cron expression, or a
+    /// six-field one with a leading seconds field
+    pub fn from_expression(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 && fields.len() != 6 {
+            return Err(format!(
+                "Invalid cron expression: '{}'. Expected 5 or 6 fields.",
+                expression
+            ));
         }
+        let schedule = CronSchedule::parse(expression)?;
+        // The minute is the first field without seconds
+        let first = fields.len() - 5;
+        let raw = |index: usize, values: Vec<u32>| {
+            Some(Field {
+                bits: values.iter().fold(0, |bits, v| bits | (1 << v)),
+                text: FieldText::Raw(fields[index].into()),
+            })
+        };
+
+        Ok(Self {
+            second: schedule
+                .has_seconds()
+                .then(|| raw(0, schedule.seconds()))
+                .flatten(),
+            minute: raw(first, schedule.minutes()),
+            hour: raw(first + 1, schedule.hours()),
+            day_of_month: raw(first + 2, schedule.days_of_month()),
+            month: raw(first + 3, schedule.months()),
+            day_of_week: raw(first + 4, schedule.days_of_week()),
+            blackouts: Vec::new(),
+            business_day: None,
+            days_before_month_end: None,
+            alternate_weeks: None,
+            calendar: Calendar::default(),
+            time_groups: Vec::new(),
+        })
     }
 
-    /// Ensure default values for all cron parts
-    fn ensure_default_values(&mut self) {
-        CronUtils::set_default(
-            &mut self.schedule,
-            "minute",
-            ScheduleValue::String("*".to_string()),
-        );
-        CronUtils::set_default(
-            &mut self.schedule,
-            "hour",
-            ScheduleValue::String("*".to_string()),
-        );
-        CronUtils::set_default(
-            &mut self.schedule,
-            "dayOfMonth",
-            ScheduleValue::String("*".to_string()),
-        );
-        CronUtils::set_default(
-            &mut self.schedule,
-            "month",
-            ScheduleValue::String("*".to_string()),
-        );
-        CronUtils::set_default(
-            &mut self.schedule,
-            "dayOfWeek",
-            ScheduleValue::String("*".to_string()),
-        );
+    /// Adds a seconds field, compiling to six-field expressions that run at
+    /// second 0 until a time with seconds is set
+    pub fn with_seconds(mut self) -> Self {
+        if self.second.is_none() {
+            self.second = Some(Field::list(&[0]));
+        }
+        self
+    }
+
+    /// At specific seconds, adding a seconds field as
+    /// [`CronExpressionBuilder::with_seconds`] does
+    pub fn at_seconds(mut self, seconds: Vec<i32>) -> Result<Self, String> {
+        for second in &seconds {
+            CronValidators::validate_second(*second)?;
+        }
+        self.second = Some(Field::list(&seconds));
+        Ok(self)
     }
 
     /// At specific minutes
@@ -52,9 +298,8 @@ impl CronExpressionBuilder {
             // ? — it propagates the error automatically if any validation fails.
             CronValidators::validate_minute(*minute)?;
         }
-        let formatted = CronUtils::format_cron_part(&minutes);
-        self.schedule
-            .insert("minute".to_string(), ScheduleValue::String(formatted));
+        self.merge_time_groups();
+        self.minute = Some(Field::list(&minutes));
         Ok(self)
     }
 
@@ -63,85 +308,144 @@ impl CronExpressionBuilder {
         for hour in &hours {
             CronValidators::validate_hour(*hour)?;
         }
-        let formatted = CronUtils::format_cron_part(&hours);
-        self.schedule
-            .insert("hour".to_string(), ScheduleValue::String(formatted));
+        self.merge_time_groups();
+        self.hour = Some(Field::list(&hours));
 
-        if !self.schedule.contains_key("minute") {
-            self.schedule
-                .insert("minute".to_string(), ScheduleValue::String("0".to_string()));
+        if self.minute.is_none() {
+            self.minute = Some(Field::list(&[0]));
         }
         Ok(self)
     }
 
-    pub fn at_time(mut self, time: &str) -> Result<Self, String> {
-        CronValidators::validate_time(time)?;
+    /// At a time of day: a [`TimeOfDay`] or text such as `"08:30"`,
+    /// `"8:30pm"` or `"noon"`. Seconds, as in `"08:30:15"`, need
+    /// [`CronExpressionBuilder::with_seconds`].
+    pub fn at_time<T>(mut self, time: T) -> Result<Self, String>
+    where
+        T: TryInto<TimeOfDay>,
+        T::Error: Display,
+    {
+        let time = self.time_of_day(time)?;
+        self.set_second(time.second());
+        self.minute = Some(Field::list(&[time.minute() as i32]));
+        self.hour = Some(Field::list(&[time.hour() as i32]));
+        self.time_groups.clear();
+        Ok(self)
+    }
 
-        let parts: Vec<&str> = time.split(":").collect();
-        let hour = parts[0].parse::<i32>().unwrap();
-        let minute = parts[1].parse::<i32>().unwrap();
+    /// At several times of day, written as for
+    /// [`CronExpressionBuilder::at_time`] and sharing their seconds. Times
+    /// that do not share their minutes or hours need more than one cron
+    /// line; see [`CronExpressionBuilder::compile_lines`].
+    pub fn at_times<T>(mut self, times: Vec<T>) -> Result<Self, String>
+    where
+        T: TryInto<TimeOfDay>,
+        T::Error: Display,
+    {
+        if times.is_empty() {
+            return Err("Invalid times: at least one time is required.".to_string());
+        }
+        let mut parsed = Vec::with_capacity(times.len());
+        let mut second = None;
+        for time in times {
+            let time = self.time_of_day(time)?;
+            if second.is_some_and(|second| second != time.second()) {
+                return Err(
+                    "Invalid times: a single seconds field cannot hold different seconds."
+                        .to_string(),
+                );
+            }
+            second = Some(time.second());
+            parsed.push((time.hour(), time.minute()));
+        }
+        self.set_second(second.unwrap_or(0));
 
-        self.schedule.insert(
-            "minute".to_string(),
-            ScheduleValue::String(minute.to_string()),
-        );
-        self.schedule
-            .insert("hour".to_string(), ScheduleValue::String(hour.to_string()));
+        let mut groups: Vec<(Field, Field)> = group_times(&parsed)
+            .into_iter()
+            .map(|(minutes, hours)| (Field::from_bits(minutes), Field::from_bits(hours)))
+            .collect();
+        if groups.len() == 1 {
+            let (minute, hour) = groups.remove(0);
+            self.minute = Some(minute);
+            self.hour = Some(hour);
+        } else {
+            self.minute = None;
+            self.hour = None;
+            self.time_groups = groups;
+        }
         Ok(self)
     }
 
+    /// Turns the groups of times from `at_times` back into single minute and
+    /// hour fields holding all of their values, so that changing one field
+    /// keeps the other
+    fn merge_time_groups(&mut self) {
+        if self.time_groups.is_empty() {
+            return;
+        }
+        let (minutes, hours) = self
+            .time_groups
+            .drain(..)
+            .fold((0, 0), |(minutes, hours), (minute, hour)| {
+                (minutes | minute.bits, hours | hour.bits)
+            });
+        self.minute = Some(Field::from_bits(minutes));
+        self.hour = Some(Field::from_bits(hours));
+    }
+
+    /// Parses a time, which may only carry seconds once they are enabled
+    fn time_of_day<T>(&self, time: T) -> Result<TimeOfDay, String>
+    where
+        T: TryInto<TimeOfDay>,
+        T::Error: Display,
+    {
+        let time = time.try_into().map_err(|e| e.to_string())?;
+        if time.second() != 0 && self.second.is_none() {
+            return Err(format!(
+                "Invalid time for 'at': {} has seconds; enable them with with_seconds() first.",
+                time
+            ));
+        }
+        Ok(time)
+    }
+
+    fn set_second(&mut self, second: u32) {
+        if self.second.is_some() {
+            self.second = Some(Field::list(&[second as i32]));
+        }
+    }
+
     /// Every unit (minute, hour, day, etc.)
     pub fn every(mut self, unit: &str) -> Result<Self, String> {
         CronValidators::validate_time_unit(unit)?;
+        self.merge_time_groups();
 
+        let midnight = |builder: &mut Self| {
+            builder.minute = Some(Field::list(&[0]));
+            builder.hour = Some(Field::list(&[0]));
+        };
         match unit {
             "minute" => {
-                self.schedule
-                    .insert("minute".to_string(), ScheduleValue::String("*".to_string()));
+                self.minute = Some(Field::wildcard(0, 59));
             }
             "hour" => {
-                self.schedule
-                    .insert("minute".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule
-                    .insert("hour".to_string(), ScheduleValue::String("*".to_string()));
+                self.minute = Some(Field::list(&[0]));
+                self.hour = Some(Field::wildcard(0, 23));
             }
             "day" => {
-                self.schedule
-                    .insert("minute".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule
-                    .insert("hour".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule.insert(
-                    "dayOfMonth".to_string(),
-                    ScheduleValue::String("*".to_string()),
-                );
+                midnight(&mut self);
+                self.day_of_month = Some(Field::wildcard(1, 31));
             }
             "month" => {
-                self.schedule
-                    .insert("minute".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule
-                    .insert("hour".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule.insert(
-                    "dayOfMonth".to_string(),
-                    ScheduleValue::String("1".to_string()),
-                );
-                self.schedule
-                    .insert("month".to_string(), ScheduleValue::String("*".to_string()));
+                midnight(&mut self);
+                self.day_of_month = Some(Field::list(&[1]));
+                self.month = Some(Field::wildcard(1, 12));
             }
             "week" => {
-                self.schedule
-                    .insert("minute".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule
-                    .insert("hour".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule.insert(
-                    "dayOfWeek".to_string(),
-                    ScheduleValue::String("0".to_string()),
-                );
-                self.schedule.insert(
-                    "dayOfMonth".to_string(),
-                    ScheduleValue::String("*".to_string()),
-                );
-                self.schedule
-                    .insert("month".to_string(), ScheduleValue::String("*".to_string()));
+                midnight(&mut self);
+                self.day_of_week = Some(Field::list(&[0]));
+                self.day_of_month = Some(Field::wildcard(1, 31));
+                self.month = Some(Field::wildcard(1, 12));
             }
             _ => {}
         }
@@ -149,113 +453,570 @@ impl CronExpressionBuilder {
     }
 
     /// Every X unit (every X minutes, hours, etc.)
+    ///
+    /// Cron steps restart with their field, so every 7 minutes runs at :56
+    /// and again at :00. The step is written as given, whatever the unit;
+    /// [`CronLinter::lint_builder`](crate::cron_lint::CronLinter::lint_builder)
+    /// warns when it does not divide its field. Use
+    /// [`IntervalSchedule`](crate::interval::IntervalSchedule) for a fixed period.
     pub fn every_x(mut self, interval: i32, unit: CronTimeUnit) -> Result<Self, String> {
+        self.time_groups.clear();
+        match unit {
+            CronTimeUnit::Minute => {
+                self.hour = Some(Field::wildcard(0, 23));
+            }
+            CronTimeUnit::Hour => {
+                self.minute = Some(Field::list(&[0]));
+            }
+            CronTimeUnit::DayOfMonth | CronTimeUnit::DayOfWeek => {
+                self.minute = Some(Field::list(&[0]));
+                self.hour = Some(Field::list(&[0]));
+            }
+            CronTimeUnit::Month => {
+                self.minute = Some(Field::list(&[0]));
+                self.hour = Some(Field::list(&[0]));
+                self.day_of_month = Some(Field::list(&[1]));
+            }
+        }
+        self.with_step(interval, unit)
+    }
+
+    /// Sets the field of a unit to a step, leaving the other fields as they
+    /// are
+    pub(crate) fn with_step(mut self, interval: i32, unit: CronTimeUnit) -> Result<Self, String> {
         match unit {
             CronTimeUnit::Minute => {
                 CronValidators::validate_minute(interval)?;
-                self.schedule.insert(
-                    "minute".to_string(),
-                    ScheduleValue::String(format!("*/{}", interval)),
-                );
-                self.schedule
-                    .insert("hour".to_string(), ScheduleValue::String("*".to_string()));
+                self.merge_time_groups();
+                self.minute = Some(Field::step(interval, 0, 59));
             }
             CronTimeUnit::Hour => {
                 CronValidators::validate_hour(interval)?;
-                self.schedule
-                    .insert("minute".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule.insert(
-                    "hour".to_string(),
-                    ScheduleValue::String(format!("*/{}", interval)),
-                );
+                self.merge_time_groups();
+                self.hour = Some(Field::step(interval, 0, 23));
             }
             CronTimeUnit::DayOfMonth => {
                 CronValidators::validate_day_of_month(interval)?;
-                self.schedule
-                    .insert("minute".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule
-                    .insert("hour".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule.insert(
-                    "dayOfMonth".to_string(),
-                    ScheduleValue::String(format!("*/{}", interval)),
-                );
+                self.day_of_month = Some(Field::step(interval, 1, 31));
             }
             CronTimeUnit::Month => {
                 CronValidators::validate_month(interval)?;
-                self.schedule
-                    .insert("minute".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule
-                    .insert("hour".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule.insert(
-                    "dayOfMonth".to_string(),
-                    ScheduleValue::String("1".to_string()),
-                );
-                self.schedule.insert(
-                    "month".to_string(),
-                    ScheduleValue::String(format!("*/{}", interval)),
-                );
+                self.month = Some(Field::step(interval, 1, 12));
             }
             CronTimeUnit::DayOfWeek => {
                 CronValidators::validate_day_of_week(interval)?;
-                self.schedule
-                    .insert("minute".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule
-                    .insert("hour".to_string(), ScheduleValue::String("0".to_string()));
-                self.schedule.insert(
-                    "dayOfWeek".to_string(),
-                    ScheduleValue::String(format!("*/{}", interval)),
-                );
+                self.day_of_week = Some(Field::step(interval, 0, 6));
             }
         }
         Ok(self)
     }
 
     /// On specific weekdays
+    ///
+    /// Replaces any weekdays set before rather than adding to them, as the
+    /// builder always has; narrow a schedule with [`except`](Self::except).
     pub fn on_week_days(mut self, days: Vec<i32>) -> Result<Self, String> {
         for day in &days {
             CronValidators::validate_day_of_week(*day)?;
         }
-        let formatted = CronUtils::format_cron_part(&days);
-        self.schedule
-            .insert("dayOfWeek".to_string(), ScheduleValue::String(formatted));
+        self.day_of_week = Some(Field::list(&days));
         Ok(self)
     }
 
     /// On specific days of the month
+    ///
+    /// Replaces any days of the month set before.
     pub fn on_days_of_month(mut self, days: Vec<i32>) -> Result<Self, String> {
         for day in &days {
             CronValidators::validate_day_of_month(*day)?;
         }
-        let formatted = CronUtils::format_cron_part(&days);
-        self.schedule
-            .insert("dayOfMonth".to_string(), ScheduleValue::String(formatted));
+        self.day_of_month = Some(Field::list(&days));
         Ok(self)
     }
 
     /// During specific months
+    ///
+    /// Replaces any months set before.
     pub fn during_months(mut self, months: Vec<i32>) -> Result<Self, String> {
         for month in &months {
             CronValidators::validate_month(*month)?;
         }
-        let formatted = CronUtils::format_cron_part(&months);
-        self.schedule
-            .insert("month".to_string(), ScheduleValue::String(formatted));
+        self.month = Some(Field::list(&months));
+        Ok(self)
+    }
+
+    /// Excludes the times of a blackout from the schedule. Blackouts cron
+    /// cannot express make [`CronExpressionBuilder::try_compile`] fail.
+    pub fn except(mut self, blackout: Blackout) -> Result<Self, String> {
+        self.blackouts.push(blackout);
+        Ok(self)
+    }
+
+    /// On the nth business day of the month, a weekday that is not one of
+    /// the holidays. Months with fewer business days are skipped.
+    pub fn on_business_day(self, day: i32) -> Result<Self, String> {
+        CronValidators::validate_business_day(day)?;
+        self.with_business_day(BusinessDay::Nth(day as u32))
+    }
+
+    /// On the last business day of the month
+    pub fn on_last_business_day(self) -> Result<Self, String> {
+        self.with_business_day(BusinessDay::Last)
+    }
+
+    fn with_business_day(mut self, day: BusinessDay) -> Result<Self, String> {
+        self.business_day = Some(day);
+        Ok(self)
+    }
+
+    /// A number of days before the last day of each month, e.g. 3 runs on
+    /// January 28th, February 25th (26th in leap years) and April 27th; zero
+    /// is the last day itself
+    pub fn on_days_before_month_end(mut self, days: i32) -> Result<Self, String> {
+        CronValidators::validate_days_before_month_end(days)?;
+        self.days_before_month_end = Some(days as u32);
+        Ok(self)
+    }
+
+    /// Only in odd ISO weeks. Years with 53 weeks run in both week 53 and
+    /// week 1; use [`CronExpressionBuilder::every_other_week`] to keep a
+    /// strict two-week rhythm. Cron has no weeks, so
+    /// [`CronExpressionBuilder::try_compile`] fails.
+    pub fn on_odd_weeks(self) -> Result<Self, String> {
+        self.with_alternate_weeks(AlternateWeeks::Odd)
+    }
+
+    /// Only in even ISO weeks
+    pub fn on_even_weeks(self) -> Result<Self, String> {
+        self.with_alternate_weeks(AlternateWeeks::Even)
+    }
+
+    /// Only in the week of `anchor` and every second week before and after it
+    pub fn every_other_week(self, anchor: NaiveDate) -> Result<Self, String> {
+        self.with_alternate_weeks(AlternateWeeks::From(anchor))
+    }
+
+    fn with_alternate_weeks(mut self, weeks: AlternateWeeks) -> Result<Self, String> {
+        self.alternate_weeks = Some(weeks);
+        Ok(self)
+    }
+
+    /// Holidays skipped when counting business days
+    pub fn with_holidays(mut self, holidays: Vec<NaiveDate>) -> Result<Self, String> {
+        for holiday in holidays {
+            self.calendar = self.calendar.with_rule(HolidayRule::Date(holiday))?;
+        }
         Ok(self)
     }
 
-    /// Compile the schedule into a final cron expression
-    pub fn compile(mut self) -> String {
-        self.ensure_default_values();
+    /// Counts business days with a calendar, replacing any holidays set so far
+    pub fn with_calendar(mut self, calendar: Calendar) -> Result<Self, String> {
+        self.calendar = calendar;
+        Ok(self)
+    }
+
+    /// Whether a blackout, business day, day before month end or alternate
+    /// weeks cannot be folded into the cron expression, so the schedule has
+    /// to be evaluated with `BlackoutSchedule`, `BusinessDaySchedule`,
+    /// `MonthEndSchedule` or `AlternateWeekSchedule`
+    pub fn requires_runtime_filter(&self) -> bool {
+        self.lines()
+            .iter()
+            .any(|line| line.fold().is_err_and(|error| error.needs_runtime_filter()))
+    }
+
+    /// Joins expressions that differ only in their minutes and hours, as
+    /// written by [`CronExpressionBuilder::compile_lines`], back into one
+    /// builder
+    pub fn from_lines(lines: &[&str]) -> Result<Self, String> {
+        let builders = lines
+            .iter()
+            .map(|line| Self::from_expression(line))
+            .collect::<Result<Vec<_>, _>>()?;
+        let Some(first) = builders.first() else {
+            return Err("Invalid cron lines: at least one line is required.".to_string());
+        };
+        if builders.len() == 1 {
+            return Ok(first.clone());
+        }
+        let without_times = |builder: &Self| Self {
+            minute: None,
+            hour: None,
+            ..builder.clone()
+        };
+        if builders
+            .iter()
+            .any(|builder| without_times(builder) != without_times(first))
+        {
+            return Err(
+                "Invalid cron lines: they may only differ in their minutes and hours.".to_string(),
+            );
+        }
+        let time_groups = builders
+            .iter()
+            .map(|builder| {
+                (
+                    builder.minute.clone().unwrap_or(Field::wildcard(0, 59)),
+                    builder.hour.clone().unwrap_or(Field::wildcard(0, 23)),
+                )
+            })
+            .collect();
+        Ok(Self {
+            time_groups,
+            ..without_times(first)
+        })
+    }
+
+    /// One builder per cron line: the same schedule at each group of times
+    pub(crate) fn lines(&self) -> Vec<Self> {
+        if self.time_groups.is_empty() {
+            return vec![self.clone()];
+        }
+        self.time_groups
+            .iter()
+            .map(|(minute, hour)| Self {
+                minute: Some(minute.clone()),
+                hour: Some(hour.clone()),
+                time_groups: Vec::new(),
+                ..self.clone()
+            })
+            .collect()
+    }
+
+    /// The values of each field a schedule is limited to, seconds first;
+    /// `None` for a field left at every value
+    #[cfg(feature = "serde")]
+    pub(crate) fn restricted_fields(&self) -> [Option<Vec<u32>>; 6] {
+        let values = |field: &Option<Field>| {
+            field
+                .as_ref()
+                .filter(|field| !field.is_wildcard())
+                .map(|field| bit_values(field.bits))
+        };
+        [
+            self.second.as_ref().map(|field| bit_values(field.bits)),
+            values(&self.minute),
+            values(&self.hour),
+            values(&self.day_of_month),
+            values(&self.month),
+            values(&self.day_of_week),
+        ]
+    }
+
+    /// The interval of each field written as a step such as `*/5`, minutes
+    /// first; `None` for a field written any other way
+    #[cfg(feature = "serde")]
+    pub(crate) fn steps(&self) -> [Option<i32>; 5] {
+        let step = |field: &Option<Field>| match field.as_ref().map(|field| &field.text) {
+            Some(FieldText::Step(interval)) => Some(*interval),
+            Some(FieldText::Raw(text)) => text.strip_prefix("*/")?.parse().ok(),
+            _ => None,
+        };
+        [
+            step(&self.minute),
+            step(&self.hour),
+            step(&self.day_of_month),
+            step(&self.month),
+            step(&self.day_of_week),
+        ]
+    }
+
+    /// The `(minutes, hours)` of each line of times from `at_times`
+    #[cfg(feature = "serde")]
+    pub(crate) fn time_groups(&self) -> Vec<(Vec<u32>, Vec<u32>)> {
+        self.time_groups
+            .iter()
+            .map(|(minute, hour)| (bit_values(minute.bits), bit_values(hour.bits)))
+            .collect()
+    }
+
+    pub(crate) fn blackouts(&self) -> &[Blackout] {
+        &self.blackouts
+    }
+
+    pub(crate) fn business_day(&self) -> Option<BusinessDay> {
+        self.business_day
+    }
+
+    pub(crate) fn days_before_month_end(&self) -> Option<u32> {
+        self.days_before_month_end
+    }
+
+    pub(crate) fn alternate_weeks(&self) -> Option<AlternateWeeks> {
+        self.alternate_weeks
+    }
+
+    pub(crate) fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    /// The same schedule without its business day or day before month end
+    pub(crate) fn without_month_day(&self) -> Self {
+        Self {
+            business_day: None,
+            days_before_month_end: None,
+            ..self.clone()
+        }
+    }
+
+    /// Checks that the schedule built so far fires on at least one date,
+    /// e.g. day 31 combined with February never does, and that it is one
+    /// cron line without a runtime filter, so that
+    /// [`CronExpressionBuilder::compile`] writes all of it
+    pub fn validate(&self) -> Result<(), String> {
+        let schedule = self
+            .fold()
+            .map_err(|error| error.message())?
+            .unfiltered_schedule()?;
+        if schedule.can_fire() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid schedule: '{}'. No date can ever match it.",
+                self.clone().compile()
+            ))
+        }
+    }
+
+    /// Builds the evaluable schedule straight from the field bitsets, with
+    /// the blackouts folded in
+    pub(crate) fn to_schedule(&self) -> Result<CronSchedule, String> {
+        self.fold()
+            .map_err(|error| error.message())?
+            .unfiltered_schedule()
+    }
+
+    /// Builds the evaluable schedule of every line, ignoring blackouts
+    pub(crate) fn unfiltered_schedules(&self) -> Result<CompositeSchedule, String> {
+        let schedules = self
+            .lines()
+            .iter()
+            .map(Self::unfiltered_schedule)
+            .collect::<Result<Vec<_>, _>>()?;
+        CompositeSchedule::new(schedules)
+    }
+
+    /// Builds the evaluable schedule from the fields, ignoring blackouts;
+    /// fails for times that need several lines
+    pub(crate) fn unfiltered_schedule(&self) -> Result<CronSchedule, String> {
+        if !self.time_groups.is_empty() {
+            return Err(FoldError::MultipleTimes(self.time_groups.len()).message());
+        }
+        let fields = [
+            bits_or_all(&self.minute, 0, 59),
+            bits_or_all(&self.hour, 0, 23),
+            bits_or_all(&self.day_of_month, 1, 31),
+            bits_or_all(&self.month, 1, 12),
+            bits_or_all(&self.day_of_week, 0, 6),
+        ];
+        let seconds = self.second.as_ref().map_or(1, |field| field.bits);
+        if fields.contains(&0) || seconds == 0 {
+            // Only a zero step selects nothing; let the parser describe it
+            return CronSchedule::parse(&self.format_fields());
+        }
+
+        Ok(CronSchedule::from_parts(
+            seconds,
+            fields[0],
+            fields[1] as u32,
+            fields[2] as u32,
+            fields[3] as u16,
+            fields[4] as u8,
+            is_restricted(&self.day_of_month),
+            is_restricted(&self.day_of_week),
+            self.second.is_some(),
+        ))
+    }
+
+    /// Returns a copy with every blackout removed from the fields, when
+    /// the whole schedule fits a single expression
+    fn fold(&self) -> Result<Self, FoldError<'_>> {
+        if !self.time_groups.is_empty() {
+            return Err(FoldError::MultipleTimes(self.time_groups.len()));
+        }
+        let mut folded = Self {
+            blackouts: Vec::new(),
+            ..self.clone()
+        };
+        for blackout in &self.blackouts {
+            let fold = match blackout {
+                Blackout::Schedule(schedule) => folded.fold_schedule(schedule),
+                Blackout::TimeWindow { start, end } => folded.fold_time_window(start, end),
+                // Cron has no year field, so a date range never folds
+                Blackout::DateRange { .. } => Fold::RuntimeFilter,
+            };
+            match fold {
+                Fold::Folded => {}
+                Fold::RuntimeFilter => return Err(FoldError::RuntimeFilter(blackout)),
+                Fold::Everything => return Err(FoldError::Everything(blackout)),
+            }
+        }
+        if let Some(day) = self.business_day {
+            return Err(FoldError::BusinessDay(day));
+        }
+        if let Some(days) = self.days_before_month_end {
+            return Err(FoldError::MonthEnd(days));
+        }
+        if let Some(weeks) = self.alternate_weeks {
+            return Err(FoldError::AlternateWeeks(weeks));
+        }
+        Ok(folded)
+    }
+
+    /// Removes the times of another schedule. Both are sets of field
+    /// combinations, so the difference is again one expression only when
+    /// the blackout narrows a single field.
+    fn fold_schedule(&mut self, blackout: &CronSchedule) -> Fold {
+        // Without a seconds field the builder runs at second 0 only
+        let current = [
+            bits_or_all(&self.minute, 0, 59),
+            bits_or_all(&self.hour, 0, 23),
+            bits_or_all(&self.day_of_month, 1, 31),
+            bits_or_all(&self.month, 1, 12),
+            bits_or_all(&self.day_of_week, 0, 6),
+            self.second.as_ref().map_or(1, |field| field.bits),
+        ];
+        let excluded = [
+            to_bits(&blackout.minutes()),
+            to_bits(&blackout.hours()),
+            to_bits(&blackout.days_of_month()),
+            to_bits(&blackout.months()),
+            to_bits(&blackout.days_of_week()),
+            to_bits(&blackout.seconds()),
+        ];
+        let overlap = |i: usize| current[i] & excluded[i] != 0;
+        if !overlap(5) || !overlap(0) || !overlap(1) || !overlap(3) {
+            return Fold::Folded;
+        }
+
+        // With both day fields set, days match either field and are no
+        // longer a plain combination of the two
+        let either_day = (is_restricted(&self.day_of_month) && is_restricted(&self.day_of_week))
+            || (blackout.is_day_of_month_restricted() && blackout.is_day_of_week_restricted());
+        let mut fields = vec![5, 0, 1, 3];
+        if either_day {
+            if blackout.is_day_of_month_restricted() || blackout.is_day_of_week_restricted() {
+                return Fold::RuntimeFilter;
+            }
+        } else {
+            if !overlap(2) || !overlap(4) {
+                return Fold::Folded;
+            }
+            fields.extend([2, 4]);
+        }
+
+        let narrowed: Vec<usize> = fields
+            .into_iter()
+            .filter(|i| current[*i] & !excluded[*i] != 0)
+            .collect();
+        let &[field] = narrowed.as_slice() else {
+            return if narrowed.is_empty() {
+                Fold::Everything
+            } else {
+                Fold::RuntimeFilter
+            };
+        };
+        let remaining = Some(Field::from_bits(current[field] & !excluded[field]));
+        match field {
+            0 => self.minute = remaining,
+            1 => self.hour = remaining,
+            3 => self.month = remaining,
+            5 => self.second = remaining,
+            // Restricting one day field while the other is set would switch
+            // the expression to matching either of them
+            2 if is_restricted(&self.day_of_week) => return Fold::RuntimeFilter,
+            2 => self.day_of_month = remaining,
+            _ if is_restricted(&self.day_of_month) => return Fold::RuntimeFilter,
+            _ => self.day_of_week = remaining,
+        }
+        Fold::Folded
+    }
+
+    /// Removes the times of day inside a window. The result is one
+    /// expression when every remaining hour keeps the same minutes.
+    fn fold_time_window(&mut self, start: &NaiveTime, end: &NaiveTime) -> Fold {
+        let minutes = bits_or_all(&self.minute, 0, 59);
+        let hours = bits_or_all(&self.hour, 0, 23);
+        let mut kept_hours = 0u64;
+        let mut kept_minutes = None;
+        for hour in (0..24).filter(|h| hours & (1 << h) != 0) {
+            let kept = (0..60)
+                .filter(|m| minutes & (1 << m) != 0)
+                .filter(|m| {
+                    let time = NaiveTime::from_hms_opt(hour, *m, 0).unwrap_or_default();
+                    !in_window(&time, start, end)
+                })
+                .fold(0u64, |bits, m| bits | (1 << m));
+            if kept == 0 {
+                continue;
+            }
+            if kept_minutes.is_some_and(|bits| bits != kept) {
+                return Fold::RuntimeFilter;
+            }
+            kept_minutes = Some(kept);
+            kept_hours |= 1 << hour;
+        }
 
-        let minute = self.schedule.get("minute").unwrap().to_string();
-        let hour = self.schedule.get("hour").unwrap().to_string();
-        let day_of_month = self.schedule.get("dayOfMonth").unwrap().to_string();
-        let month = self.schedule.get("month").unwrap().to_string();
-        let day_of_week = self.schedule.get("dayOfWeek").unwrap().to_string();
+        let Some(kept_minutes) = kept_minutes else {
+            return Fold::Everything;
+        };
+        // Fields left untouched keep their original text, such as `*/5`
+        if kept_hours != hours {
+            self.hour = Some(Field::from_bits(kept_hours));
+        }
+        if kept_minutes != minutes {
+            self.minute = Some(Field::from_bits(kept_minutes));
+        }
+        Fold::Folded
+    }
 
-        format!(
+    /// Compiles the schedule, like [`CronExpressionBuilder::compile`], but
+    /// fails when a blackout or day of the month cannot be expressed in cron
+    pub fn try_compile(self) -> Result<String, String> {
+        self.fold()
+            .map(|folded| folded.format_fields())
+            .map_err(|error| error.message())
+    }
+
+    /// Compile the schedule into a final cron expression.
+    ///
+    /// Blackouts are folded in when cron can express the result; otherwise
+    /// they are left out and the expression is the schedule without them.
+    /// Business days, days before month end and alternate weeks are always
+    /// left out.
+    /// Use [`CronExpressionBuilder::try_compile`] or
+    /// [`CronExpressionBuilder::validate`] to detect that case.
+    /// Times from `at_times` that need several lines only have their first
+    /// line written; [`CronExpressionBuilder::compile_lines`] is the only
+    /// way to write all of them.
+    pub fn compile(self) -> String {
+        let line = self.lines().remove(0);
+        match line.fold() {
+            Ok(folded) => folded.format_fields(),
+            Err(_) => line.format_fields(),
+        }
+    }
+
+    /// Compiles the schedule into as few cron lines as the times of day
+    /// allow, together running at the union of their times. Fails like
+    /// [`CronExpressionBuilder::try_compile`] for anything else cron cannot
+    /// express.
+    pub fn compile_lines(self) -> Result<Vec<String>, String> {
+        self.lines().into_iter().map(Self::try_compile).collect()
+    }
+
+    fn format_fields(&self) -> String {
+        let fields = format!(
             "{} {} {} {} {}",
-            minute, hour, day_of_month, month, day_of_week
-        )
+            format_or_wildcard(&self.minute),
+            format_or_wildcard(&self.hour),
+            format_or_wildcard(&self.day_of_month),
+            format_or_wildcard(&self.month),
+            format_or_wildcard(&self.day_of_week)
+        );
+        match &self.second {
+            Some(second) => format!("{} {}", second.format(), fields),
+            None => fields,
+        }
     }
 }
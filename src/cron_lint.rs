@@ -0,0 +1,306 @@
+//! Warnings about cron expressions that are valid but probably not what was meant.
+use std::fmt;
+
+use chrono::{Datelike, Duration, LocalResult, NaiveDate, NaiveTime, Offset, TimeZone};
+
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::{CronSchedule, DAY_NAMES, MONTH_NAMES, parse_field};
+
+const MONTH_LENGTHS: [u32; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+/// The kind of mistake a lint warning points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintKind {
+    /// `* 3 * * *` runs every minute of 3am rather than once
+    WildcardMinuteWithFixedHour,
+    /// A day of month that some of the selected months do not have
+    DayMissingInMonth,
+    /// The expression is valid but can never fire
+    NeverFires,
+    /// Both day fields are set, so a date matching either one fires
+    DayFieldsCombined,
+    /// A step that does not divide its range, leaving an uneven gap
+    UnevenStep,
+    /// A run time that is skipped or repeated by a daylight saving change
+    DaylightSaving,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    pub kind: LintKind,
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+pub struct CronLinter;
+
+impl CronLinter {
+    /// Checks a five- or six-field cron expression for common mistakes
+    pub fn lint(expression: &str) -> Result<Vec<LintWarning>, String> {
+        let schedule = CronSchedule::parse(expression)?;
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let mut warnings = Vec::new();
+
+        if let Some(warning) = never_fires(expression, &schedule) {
+            // Nothing else matters for a schedule that never runs
+            warnings.push(warning);
+            return Ok(warnings);
+        }
+        warnings.extend(wildcard_minute(&schedule));
+        warnings.extend(days_missing_in_months(&schedule));
+        if schedule.is_day_of_month_restricted() && schedule.is_day_of_week_restricted() {
+            warnings.push(LintWarning {
+                kind: LintKind::DayFieldsCombined,
+                message: "Both day-of-month and day-of-week are set, so the job runs on days matching either one, not both.".to_string(),
+            });
+        }
+        warnings.extend(uneven_steps(&fields)?);
+        Ok(warnings)
+    }
+
+    /// Checks each line compiled from a builder; fails for a builder that
+    /// needs a runtime filter rather than check a wider schedule
+    pub fn lint_builder(builder: &CronExpressionBuilder) -> Result<Vec<LintWarning>, String> {
+        let mut warnings = Vec::new();
+        for line in builder.clone().compile_lines()? {
+            for warning in Self::lint(&line)? {
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
+        }
+        Ok(warnings)
+    }
+
+    /// Runs [`CronLinter::lint`] and also reports run times in the given year
+    /// that fall into a daylight saving gap or overlap of the zone
+    pub fn lint_in_zone<Tz: TimeZone>(
+        expression: &str,
+        zone: &Tz,
+        year: i32,
+    ) -> Result<Vec<LintWarning>, String> {
+        let mut warnings = Self::lint(expression)?;
+        let schedule = CronSchedule::parse(expression)?;
+        warnings.extend(daylight_saving(&schedule, zone, year));
+        Ok(warnings)
+    }
+}
+
+fn never_fires(expression: &str, schedule: &CronSchedule) -> Option<LintWarning> {
+    if schedule.can_fire() {
+        return None;
+    }
+    Some(LintWarning {
+        kind: LintKind::NeverFires,
+        message: format!(
+            "'{}' never fires: none of its days of month exist in its months.",
+            expression
+        ),
+    })
+}
+
+fn wildcard_minute(schedule: &CronSchedule) -> Option<LintWarning> {
+    let hours = schedule.hours();
+    if schedule.minutes().len() != 60 || hours.len() == 24 {
+        return None;
+    }
+    Some(LintWarning {
+        kind: LintKind::WildcardMinuteWithFixedHour,
+        message: format!(
+            "The minute field is '*', so the job runs every minute of hour {} rather than once. Use '0' to run on the hour.",
+            join(&hours.iter().map(|h| h.to_string()).collect::<Vec<_>>())
+        ),
+    })
+}
+
+fn days_missing_in_months(schedule: &CronSchedule) -> Vec<LintWarning> {
+    if !schedule.is_day_of_month_restricted() || schedule.is_day_of_week_restricted() {
+        return Vec::new();
+    }
+    let months = schedule.months();
+    let mut warnings = Vec::new();
+    for day in schedule.days_of_month().into_iter().filter(|d| *d >= 29) {
+        let missing: Vec<String> = months
+            .iter()
+            .filter(|m| MONTH_LENGTHS[**m as usize - 1] < day)
+            .map(|m| month_name(*m))
+            .collect();
+        // Every month missing the day is reported by never_fires instead
+        if !missing.is_empty() && missing.len() < months.len() {
+            warnings.push(LintWarning {
+                kind: LintKind::DayMissingInMonth,
+                message: format!(
+                    "Day {} does not exist in {}, so the job does not run in those months.",
+                    day,
+                    join(&missing)
+                ),
+            });
+        }
+        if day == 29 && months.contains(&2) {
+            warnings.push(LintWarning {
+                kind: LintKind::DayMissingInMonth,
+                message: "Day 29 of February only exists in leap years.".to_string(),
+            });
+        }
+    }
+    warnings
+}
+
+fn uneven_steps(fields: &[&str]) -> Result<Vec<LintWarning>, String> {
+    // Name, cycle range, highest accepted value (7 is Sunday again) and names
+    let mut specs = vec![
+        ("minute", 0, 59, 59, None),
+        ("hour", 0, 23, 23, None),
+        ("day of month", 1, 31, 31, None),
+        ("month", 1, 12, 12, Some(&MONTH_NAMES[..])),
+        ("day of week", 0, 6, 7, Some(&DAY_NAMES[..])),
+    ];
+    if fields.len() == 6 {
+        specs.insert(0, ("second", 0, 59, 59, None));
+    }
+
+    let mut warnings = Vec::new();
+    for (field, (name, min, max, limit, names)) in fields.iter().zip(specs) {
+        for part in field.split(',') {
+            let Some((range, step)) = part.split_once('/') else {
+                continue;
+            };
+            let step: u32 = step
+                .parse()
+                .map_err(|_| format!("Invalid step in {} field: '{}'", name, part))?;
+            let (start, end, wraps) = if range == "*" || range == "?" {
+                (min, max, true)
+            } else if range.contains('-') {
+                let bits = parse_field(range, name, min, limit, names)?;
+                (bits.trailing_zeros(), 63 - bits.leading_zeros(), false)
+            } else {
+                let bits = parse_field(range, name, min, limit, names)?;
+                (bits.trailing_zeros(), max, true)
+            };
+
+            if start > end {
+                continue;
+            }
+            let last = end - (end - start) % step;
+            let message = if wraps && (max + 1 - last) + (start - min) != step {
+                // "*/7" minutes runs at :56 and then again at :00
+                format!(
+                    "'{}' in the {} field does not divide the range {}-{} evenly: after {} the next run is {} later instead of {}.",
+                    part,
+                    name,
+                    min,
+                    max,
+                    last,
+                    (max + 1 - last) + (start - min),
+                    step
+                )
+            } else if !wraps && last != end {
+                format!(
+                    "'{}' in the {} field never reaches {}; the last value is {}.",
+                    part, name, end, last
+                )
+            } else {
+                continue;
+            };
+            warnings.push(LintWarning {
+                kind: LintKind::UnevenStep,
+                message,
+            });
+        }
+    }
+    Ok(warnings)
+}
+
+fn daylight_saving<Tz: TimeZone>(
+    schedule: &CronSchedule,
+    zone: &Tz,
+    year: i32,
+) -> Vec<LintWarning> {
+    let Some(mut date) = NaiveDate::from_ymd_opt(year, 1, 1) else {
+        return Vec::new();
+    };
+    let mut warnings = Vec::new();
+    while date.year() == year {
+        let next = date + Duration::days(1);
+        if schedule.matches_date(&date) && offset_changes(zone, date) {
+            let mut skipped = Vec::new();
+            let mut repeated = Vec::new();
+            for hour in schedule.hours() {
+                for minute in schedule.minutes() {
+                    let Some(time) = NaiveTime::from_hms_opt(hour, minute, 0) else {
+                        continue;
+                    };
+                    match zone.from_local_datetime(&date.and_time(time)) {
+                        LocalResult::None => skipped.push(time),
+                        LocalResult::Ambiguous(..) => repeated.push(time),
+                        LocalResult::Single(_) => {}
+                    }
+                }
+            }
+            if !skipped.is_empty() {
+                warnings.push(LintWarning {
+                    kind: LintKind::DaylightSaving,
+                    message: format!(
+                        "On {}, {} skipped by the daylight saving change and will not run.",
+                        date,
+                        describe_times(&skipped)
+                    ),
+                });
+            }
+            if !repeated.is_empty() {
+                warnings.push(LintWarning {
+                    kind: LintKind::DaylightSaving,
+                    message: format!(
+                        "On {}, {} repeated by the daylight saving change and will only run the first time.",
+                        date,
+                        describe_times(&repeated)
+                    ),
+                });
+            }
+        }
+        date = next;
+    }
+    warnings
+}
+
+/// Checks whether the zone's UTC offset may change during the local date.
+/// The window is widened by a day on each side so that zones far from UTC,
+/// whose local date starts on the previous or next UTC date, are covered.
+pub(crate) fn offset_changes<Tz: TimeZone>(zone: &Tz, date: NaiveDate) -> bool {
+    let offset = |date: NaiveDate| {
+        date.and_hms_opt(0, 0, 0)
+            .map(|time| zone.offset_from_utc_datetime(&time).fix())
+    };
+    offset(date - Duration::days(1)) != offset(date + Duration::days(2))
+}
+
+fn describe_times(times: &[NaiveTime]) -> String {
+    match times {
+        [only] => format!("the run at {} is", only.format("%H:%M")),
+        [first, .., last] => format!(
+            "{} runs from {} to {} are",
+            times.len(),
+            first.format("%H:%M"),
+            last.format("%H:%M")
+        ),
+        [] => String::new(),
+    }
+}
+
+fn month_name(month: u32) -> String {
+    let name = MONTH_NAMES[month as usize - 1];
+    format!("{}{}", &name[..1], name[1..].to_ascii_lowercase())
+}
+
+fn join(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
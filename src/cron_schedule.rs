@@ -0,0 +1,674 @@
+//! Parsed cron schedules that can be evaluated against dates.
+use std::fmt;
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Timelike,
+};
+
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_utils::CronUtils;
+
+/// Number of years searched before a schedule is considered to never fire.
+/// 400 years is a full Gregorian cycle, so every date pattern repeats within it.
+pub(crate) const SEARCH_LIMIT_YEARS: i32 = 400;
+
+pub(crate) const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+pub(crate) const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Anything that produces run times, so schedulers and misfire handling work
+/// the same for cron expressions, fixed intervals and runtime filters
+pub trait Schedule: Send + Sync {
+    /// Returns the first run strictly after the given time
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime>;
+
+    /// The smallest step between two runs; a run younger than this is on time
+    fn resolution(&self) -> Duration {
+        Duration::minutes(1)
+    }
+
+    /// Returns every run after `from` up to and including `to`
+    fn occurrences_between(&self, from: &NaiveDateTime, to: &NaiveDateTime) -> Vec<NaiveDateTime> {
+        let mut occurrences = Vec::new();
+        let mut cursor = *from;
+        while let Some(next) = self.next_after(&cursor) {
+            if next > *to {
+                break;
+            }
+            occurrences.push(next);
+            cursor = next;
+        }
+        occurrences
+    }
+
+    /// Returns up to `count` runs strictly after the given time
+    fn upcoming(&self, after: &NaiveDateTime, count: usize) -> Vec<NaiveDateTime> {
+        let mut occurrences = Vec::new();
+        let mut cursor = *after;
+        while occurrences.len() < count {
+            match self.next_after(&cursor) {
+                Some(next) => {
+                    occurrences.push(next);
+                    cursor = next;
+                }
+                None => break,
+            }
+        }
+        occurrences
+    }
+}
+
+/// A cron expression parsed into one bitset per field.
+///
+/// Five-field expressions (`minute hour day-of-month month day-of-week`) and
+/// six-field expressions with a leading seconds field are both accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CronSchedule {
+    seconds: u64,
+    minutes: u64,
+    hours: u32,
+    days_of_month: u32,
+    months: u16,
+    days_of_week: u8,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+    has_seconds: bool,
+}
+
+impl CronSchedule {
+    /// Parses a five- or six-field cron expression
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        scan_expression(expression.as_bytes()).map_err(|error| match error {
+            ExpressionError::FieldCount => format!(
+                "Invalid cron expression: '{}'. Expected 5 or 6 fields.",
+                expression
+            ),
+            ExpressionError::Field {
+                field,
+                start,
+                end,
+                error,
+            } => {
+                let (name, min, max, _) = FIELDS[field];
+                error.message(&expression[start..end], name, min, max)
+            }
+        })
+    }
+
+    /// Parses a five- or six-field cron expression in a const context, so that
+    /// schedules can be declared as constants:
+    ///
+    /// ```
+    /// use natural_cron::CronSchedule;
+    ///
+    /// const NIGHTLY: CronSchedule = CronSchedule::parse_const("30 2 * * *");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expression is invalid, which fails the build when the
+    /// result initialises a constant.
+    pub const fn parse_const(expression: &str) -> Self {
+        match scan_expression(expression.as_bytes()) {
+            Ok(schedule) => schedule,
+            Err(ExpressionError::FieldCount) => {
+                panic!("Invalid cron expression: expected 5 or 6 fields")
+            }
+            Err(ExpressionError::Field { field, .. }) => match field {
+                0 => panic!("Invalid cron expression: bad second field"),
+                1 => panic!("Invalid cron expression: bad minute field"),
+                2 => panic!("Invalid cron expression: bad hour field"),
+                3 => panic!("Invalid cron expression: bad day of month field"),
+                4 => panic!("Invalid cron expression: bad month field"),
+                _ => panic!("Invalid cron expression: bad day of week field"),
+            },
+        }
+    }
+
+    /// Assembles a schedule from already validated field bitsets.
+    /// Used by the `cron!` macro of the `natural-cron-macros` crate.
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    pub const fn from_parts(
+        seconds: u64,
+        minutes: u64,
+        hours: u32,
+        days_of_month: u32,
+        months: u16,
+        days_of_week: u8,
+        day_of_month_restricted: bool,
+        day_of_week_restricted: bool,
+        has_seconds: bool,
+    ) -> Self {
+        Self {
+            seconds,
+            minutes,
+            hours,
+            days_of_month,
+            months,
+            days_of_week,
+            day_of_month_restricted,
+            day_of_week_restricted,
+            has_seconds,
+        }
+    }
+
+    /// Builds a schedule from a builder's fields without re-parsing them
+    pub fn from_builder(builder: &CronExpressionBuilder) -> Result<Self, String> {
+        builder.to_schedule()
+    }
+
+    /// Whether the expression carried a seconds field
+    pub const fn has_seconds(&self) -> bool {
+        self.has_seconds
+    }
+
+    /// Seconds the schedule fires at; `[0]` for five-field expressions
+    pub fn seconds(&self) -> Vec<u32> {
+        values(self.seconds, 0, 59)
+    }
+
+    /// Minutes the schedule fires at
+    pub fn minutes(&self) -> Vec<u32> {
+        values(self.minutes, 0, 59)
+    }
+
+    /// Hours the schedule fires at
+    pub fn hours(&self) -> Vec<u32> {
+        values(self.hours as u64, 0, 23)
+    }
+
+    /// Days of the month (1-31) the schedule fires on
+    pub fn days_of_month(&self) -> Vec<u32> {
+        values(self.days_of_month as u64, 1, 31)
+    }
+
+    /// Months (1-12) the schedule fires in
+    pub fn months(&self) -> Vec<u32> {
+        values(self.months as u64, 1, 12)
+    }
+
+    /// Days of the week (0 = Sunday) the schedule fires on
+    pub fn days_of_week(&self) -> Vec<u32> {
+        values(self.days_of_week as u64, 0, 6)
+    }
+
+    /// Whether the day-of-month field was anything other than `*`
+    pub fn is_day_of_month_restricted(&self) -> bool {
+        self.day_of_month_restricted
+    }
+
+    /// Whether the day-of-week field was anything other than `*`
+    pub fn is_day_of_week_restricted(&self) -> bool {
+        self.day_of_week_restricted
+    }
+
+    /// Checks if the given date and time matches every field of the schedule
+    pub fn matches(&self, time: &NaiveDateTime) -> bool {
+        self.matches_date(&time.date())
+            && bit(self.hours as u64, time.hour())
+            && bit(self.minutes, time.minute())
+            && bit(self.seconds, time.second())
+    }
+
+    /// Checks if the schedule can fire at some point on the given date
+    pub fn matches_date(&self, date: &NaiveDate) -> bool {
+        if !bit(self.months as u64, date.month()) {
+            return false;
+        }
+        let day_of_month = bit(self.days_of_month as u64, date.day());
+        let day_of_week = bit(
+            self.days_of_week as u64,
+            date.weekday().num_days_from_sunday(),
+        );
+        // Classic cron semantics: when both day fields are restricted,
+        // a date matching either one of them is enough.
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+
+    /// Returns the first occurrence strictly after the given time
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut time = if self.has_seconds {
+            after.with_nanosecond(0)? + Duration::seconds(1)
+        } else {
+            after.with_nanosecond(0)?.with_second(0)? + Duration::minutes(1)
+        };
+        let limit = time.year() + SEARCH_LIMIT_YEARS;
+
+        while time.year() <= limit {
+            if !bit(self.months as u64, time.month()) {
+                time = first_of_next_month(&time.date())?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.matches_date(&time.date()) {
+                time = time.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !bit(self.hours as u64, time.hour()) {
+                time = time.date().and_hms_opt(time.hour(), 0, 0)? + Duration::hours(1);
+                continue;
+            }
+            if !bit(self.minutes, time.minute()) {
+                time = time.with_second(0)? + Duration::minutes(1);
+                continue;
+            }
+            if !bit(self.seconds, time.second()) {
+                time += Duration::seconds(1);
+                continue;
+            }
+            return Some(time);
+        }
+        None
+    }
+
+    /// The same times of day, on every date
+    pub(crate) fn every_day(&self) -> Self {
+        Self {
+            days_of_month: 0xFFFF_FFFE,
+            months: 0x1FFE,
+            days_of_week: 0x7F,
+            day_of_month_restricted: false,
+            day_of_week_restricted: false,
+            ..self.clone()
+        }
+    }
+
+    /// Checks if any date and time can ever match the schedule
+    pub fn can_fire(&self) -> bool {
+        NaiveDate::from_ymd_opt(2000, 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .and_then(|start| self.next_after(&start))
+            .is_some()
+    }
+
+    /// Returns the first occurrence strictly after the given time, evaluating
+    /// the schedule against the wall clock of the time's zone.
+    ///
+    /// Local times skipped by a daylight saving transition do not fire, and
+    /// local times repeated by one fire only the first time round.
+    pub fn next_after_in<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let zone = after.timezone();
+        let mut cursor = after.naive_local();
+        loop {
+            let next = self.next_after(&cursor)?;
+            let candidate = match zone.from_local_datetime(&next) {
+                LocalResult::Single(time) => Some(time),
+                LocalResult::Ambiguous(earliest, _) => Some(earliest),
+                LocalResult::None => None,
+            };
+            if let Some(time) = candidate.filter(|time| time > after) {
+                return Some(time);
+            }
+            cursor = next;
+        }
+    }
+
+    /// The smallest step between two occurrences: a second or a minute
+    pub fn resolution(&self) -> Duration {
+        if self.has_seconds {
+            Duration::seconds(1)
+        } else {
+            Duration::minutes(1)
+        }
+    }
+
+    /// Returns every occurrence after `from` up to and including `to`
+    pub fn occurrences_between(
+        &self,
+        from: &NaiveDateTime,
+        to: &NaiveDateTime,
+    ) -> Vec<NaiveDateTime> {
+        Schedule::occurrences_between(self, from, to)
+    }
+
+    /// Returns up to `count` occurrences strictly after the given time
+    pub fn upcoming(&self, after: &NaiveDateTime, count: usize) -> Vec<NaiveDateTime> {
+        Schedule::upcoming(self, after, count)
+    }
+}
+
+impl Schedule for CronSchedule {
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        CronSchedule::next_after(self, after)
+    }
+
+    fn resolution(&self) -> Duration {
+        CronSchedule::resolution(self)
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.has_seconds {
+            write!(f, "{} ", format_field(self.seconds, 0, 59, true, false))?;
+        }
+        write!(
+            f,
+            "{} {} {} {} {}",
+            format_field(self.minutes, 0, 59, true, false),
+            format_field(self.hours as u64, 0, 23, true, false),
+            format_field(
+                self.days_of_month as u64,
+                1,
+                31,
+                self.day_of_month_restricted,
+                true
+            ),
+            format_field(self.months as u64, 1, 12, true, false),
+            format_field(
+                self.days_of_week as u64,
+                0,
+                6,
+                self.day_of_week_restricted,
+                true
+            ),
+        )
+    }
+}
+
+fn bit(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+fn values(bits: u64, min: u32, max: u32) -> Vec<u32> {
+    (min..=max).filter(|v| bit(bits, *v)).collect()
+}
+
+fn first_of_next_month(date: &NaiveDate) -> Option<NaiveDate> {
+    if date.month() == 12 {
+        NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1)
+    }
+}
+
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|date| first_of_next_month(&date))
+        .and_then(|date| date.pred_opt())
+        .map_or(31, |date| date.day())
+}
+
+/// Name, range and value names of each field, seconds first
+const FIELDS: [(&str, u32, u32, Option<&[&str]>); 6] = [
+    ("second", 0, 59, None),
+    ("minute", 0, 59, None),
+    ("hour", 0, 23, None),
+    ("day of month", 1, 31, None),
+    ("month", 1, 12, Some(&MONTH_NAMES)),
+    // 7 is accepted as an alias for Sunday
+    ("day of week", 0, 7, Some(&DAY_NAMES)),
+];
+
+/// Why an expression failed to parse; offsets are bytes into the expression
+enum ExpressionError {
+    FieldCount,
+    Field {
+        field: usize,
+        start: usize,
+        end: usize,
+        error: FieldError,
+    },
+}
+
+/// Why a field failed to parse; offsets are bytes into the field
+enum FieldError {
+    Value(usize, usize),
+    Step(usize, usize),
+    Range(usize, usize),
+}
+
+impl FieldError {
+    fn message(&self, field: &str, name: &str, min: u32, max: u32) -> String {
+        match *self {
+            FieldError::Value(start, end) => format!(
+                "Invalid {}: '{}'. Value should be between {} and {}.",
+                name,
+                &field[start..end],
+                min,
+                max
+            ),
+            FieldError::Step(start, end) => {
+                format!("Invalid step in {} field: '{}'", name, &field[start..end])
+            }
+            FieldError::Range(start, end) => {
+                format!("Invalid range in {} field: '{}'", name, &field[start..end])
+            }
+        }
+    }
+}
+
+/// Parses a single cron field (lists, ranges, steps and names) into a bitset
+pub(crate) fn parse_field(
+    field: &str,
+    name: &str,
+    min: u32,
+    max: u32,
+    names: Option<&[&str]>,
+) -> Result<u64, String> {
+    scan_field(field.as_bytes(), 0, field.len(), min, max, names)
+        .map_err(|error| error.message(field, name, min, max))
+}
+
+// The scanners below are `const fn` so that `CronSchedule::parse_const` can
+// run them at compile time; that rules out iterators, `?` and slicing by range.
+
+const fn scan_expression(expression: &[u8]) -> Result<CronSchedule, ExpressionError> {
+    let mut bounds = [(0usize, 0usize); 6];
+    let mut count = 0;
+    let mut i = 0;
+    while i < expression.len() {
+        if expression[i].is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < expression.len() && !expression[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if count == 6 {
+            return Err(ExpressionError::FieldCount);
+        }
+        bounds[count] = (start, i);
+        count += 1;
+    }
+    // Five-field expressions have no seconds field
+    let first = match count {
+        5 => 1,
+        6 => 0,
+        _ => return Err(ExpressionError::FieldCount),
+    };
+
+    let mut bits = [1u64, 0, 0, 0, 0, 0];
+    let mut field = first;
+    while field < 6 {
+        let (start, end) = bounds[field - first];
+        let (_, min, max, names) = FIELDS[field];
+        bits[field] = match scan_field(expression, start, end, min, max, names) {
+            Ok(bits) => bits,
+            Err(error) => {
+                return Err(ExpressionError::Field {
+                    field,
+                    start,
+                    end,
+                    error,
+                });
+            }
+        };
+        field += 1;
+    }
+
+    // 7 is an alias for Sunday
+    let mut days_of_week = bits[5];
+    if days_of_week & (1 << 7) != 0 {
+        days_of_week = (days_of_week | 1) & !(1 << 7);
+    }
+    let (day_of_month_start, day_of_month_end) = bounds[3 - first];
+    let (day_of_week_start, day_of_week_end) = bounds[5 - first];
+
+    Ok(CronSchedule {
+        seconds: bits[0],
+        minutes: bits[1],
+        hours: bits[2] as u32,
+        days_of_month: bits[3] as u32,
+        months: bits[4] as u16,
+        days_of_week: days_of_week as u8,
+        day_of_month_restricted: !is_wildcard_at(expression, day_of_month_start, day_of_month_end),
+        day_of_week_restricted: !is_wildcard_at(expression, day_of_week_start, day_of_week_end),
+        has_seconds: count == 6,
+    })
+}
+
+/// Scans `bytes[start..end]`; error offsets are relative to `start`
+const fn scan_field(
+    bytes: &[u8],
+    start: usize,
+    end: usize,
+    min: u32,
+    max: u32,
+    names: Option<&[&str]>,
+) -> Result<u64, FieldError> {
+    let mut bits = 0u64;
+    let mut part_start = start;
+    while part_start <= end {
+        let part_end = find(bytes, part_start, end, b',');
+        let slash = find(bytes, part_start, part_end, b'/');
+        let part_error = (part_start - start, part_end - start);
+
+        let (range_end, step) = if slash < part_end {
+            match scan_number(bytes, slash + 1, part_end) {
+                Some(step) if step > 0 => (slash, step),
+                _ => return Err(FieldError::Step(part_error.0, part_error.1)),
+            }
+        } else {
+            (part_end, 1)
+        };
+
+        let (first, last) = if is_wildcard_at(bytes, part_start, range_end) {
+            (min, max)
+        } else {
+            let dash = find(bytes, part_start, range_end, b'-');
+            let first_end = if dash < range_end { dash } else { range_end };
+            let first = match scan_value(bytes, part_start, first_end, min, max, names) {
+                Some(value) => value,
+                None => return Err(FieldError::Value(part_start - start, first_end - start)),
+            };
+            if dash < range_end {
+                match scan_value(bytes, dash + 1, range_end, min, max, names) {
+                    Some(last) => (first, last),
+                    None => return Err(FieldError::Value(dash + 1 - start, range_end - start)),
+                }
+            } else if slash < part_end {
+                // "5/15" means "from 5 to the end of the range in steps of 15"
+                (first, max)
+            } else {
+                (first, first)
+            }
+        };
+
+        if first > last {
+            return Err(FieldError::Range(part_error.0, part_error.1));
+        }
+        let mut value = first;
+        loop {
+            bits |= 1 << value;
+            if last - value < step {
+                break;
+            }
+            value += step;
+        }
+        part_start = part_end + 1;
+    }
+    Ok(bits)
+}
+
+/// Returns the index of the first `needle` in `bytes[start..end]`, or `end`
+const fn find(bytes: &[u8], start: usize, end: usize, needle: u8) -> usize {
+    let mut i = start;
+    while i < end && bytes[i] != needle {
+        i += 1;
+    }
+    i
+}
+
+const fn is_wildcard_at(bytes: &[u8], start: usize, end: usize) -> bool {
+    end == start + 1 && (bytes[start] == b'*' || bytes[start] == b'?')
+}
+
+const fn scan_number(bytes: &[u8], start: usize, end: usize) -> Option<u32> {
+    if start == end {
+        return None;
+    }
+    let mut value: u32 = 0;
+    let mut i = start;
+    while i < end {
+        let digit = bytes[i];
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+        value = match value.checked_mul(10) {
+            Some(v) => match v.checked_add((digit - b'0') as u32) {
+                Some(v) => v,
+                None => return None,
+            },
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(value)
+}
+
+const fn scan_value(
+    bytes: &[u8],
+    start: usize,
+    end: usize,
+    min: u32,
+    max: u32,
+    names: Option<&[&str]>,
+) -> Option<u32> {
+    if let Some(names) = names {
+        let mut index = 0;
+        while index < names.len() {
+            if eq_ignore_case(bytes, start, end, names[index].as_bytes()) {
+                // Month names start at 1, day names at 0
+                return Some(index as u32 + min);
+            }
+            index += 1;
+        }
+    }
+    match scan_number(bytes, start, end) {
+        Some(value) if value >= min && value <= max => Some(value),
+        _ => None,
+    }
+}
+
+const fn eq_ignore_case(bytes: &[u8], start: usize, end: usize, name: &[u8]) -> bool {
+    if end - start != name.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < name.len() {
+        if bytes[start + i].to_ascii_uppercase() != name[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Formats a bitset back into a cron field.
+/// Day fields never collapse a full set into `*`, since a restricted day field
+/// switches the day-of-month/day-of-week matching to OR semantics.
+fn format_field(bits: u64, min: u32, max: u32, restricted: bool, day_field: bool) -> String {
+    let all = (min..=max).fold(0u64, |acc, v| acc | (1 << v));
+    if !restricted || (!day_field && bits & all == all) {
+        return "*".to_string();
+    }
+    let values: Vec<i32> = values(bits, min, max).iter().map(|v| *v as i32).collect();
+    CronUtils::format_cron_part(&values)
+}
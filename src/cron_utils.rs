@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+#[allow(deprecated)]
 use crate::interfaces::ScheduleValue;
 
 pub struct CronUtils;
@@ -45,6 +46,8 @@ impl CronUtils {
     }
 
     /// Sets a default value for a field in the schedule if not already set
+    #[deprecated(note = "the builder keeps its fields as bitsets and no longer uses schedule maps")]
+    #[allow(deprecated)]
     pub fn set_default(
         sehedule: &mut HashMap<String, ScheduleValue>,
         field: &str,
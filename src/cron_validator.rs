@@ -1,8 +1,23 @@
-use regex::Regex;
+use crate::cron_schedule::CronSchedule;
+use crate::time_of_day::TimeOfDay;
 
 pub struct CronValidators;
 
+// The original range checks are kept as they were written
+#[allow(clippy::manual_range_contains, clippy::needless_return)]
 impl CronValidators {
+    /// Validates if the second is between 0 and 59
+    pub fn validate_second(second: i32) -> Result<(), String> {
+        if !(0..=59).contains(&second) {
+            Err(format!(
+                "Invalid second: {}. Second should be between 0 and 59.",
+                second
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Validates if the minute is between 0 and 59
     pub fn validate_minute(minute: i32) -> Result<(), String> {
         if minute < 0 || minute > 59 {
@@ -51,6 +66,32 @@ impl CronValidators {
         }
     }
 
+    /// Validates if the business day of the month is between 1 and 23, the
+    /// most weekdays a month can have
+    pub fn validate_business_day(day: i32) -> Result<(), String> {
+        if !(1..=23).contains(&day) {
+            Err(format!(
+                "Invalid business day: {}. Day should be between 1 and 23.",
+                day
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Validates if a number of days before the end of the month is between
+    /// 0 and 27, so that it falls inside February too
+    pub fn validate_days_before_month_end(days: i32) -> Result<(), String> {
+        if !(0..=27).contains(&days) {
+            Err(format!(
+                "Invalid days before month end: {}. Days should be between 0 and 27.",
+                days
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Validates if the day of week is between 0 and 6
     pub fn validate_day_of_week(day: i32) -> Result<(), String> {
         if day < 0 || day > 6 {
@@ -63,14 +104,10 @@ impl CronValidators {
         }
     }
 
-    /// Validates if a time string matches HH:MM format
+    /// Validates if a time string is a time of day, such as `08:30`,
+    /// `08:30:15`, `8:30pm` or `noon`
     pub fn validate_time(time: &str) -> Result<(), String> {
-        let re = Regex::new(r"^([01]?[0-9]|2[0-3]):([0-5]?[0-9])$").unwrap();
-        if re.is_match(time) {
-            Ok(())
-        } else {
-            return Err(format!("Invalid time format for 'at': {}", time));
-        }
+        time.parse::<TimeOfDay>().map(|_| ())
     }
 
     /// Validates if the time unit is valid
@@ -82,4 +119,16 @@ impl CronValidators {
             return Err(format!("Invalid time unit for cron: {}", unit));
         }
     }
+
+    /// Validates that a cron expression can fire on at least one date
+    pub fn validate_satisfiable(expression: &str) -> Result<(), String> {
+        if CronSchedule::parse(expression)?.can_fire() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid schedule: '{}'. No date can ever match it.",
+                expression
+            ))
+        }
+    }
 }
@@ -0,0 +1,333 @@
+//! Reading and writing crontab files.
+//!
+//! A [`Crontab`] keeps every line of the file in order — comments, blank
+//! lines, environment assignments such as `MAILTO=` and jobs — so it can be
+//! edited and written back with [`Crontab::render`] without losing anything.
+//! Jobs whose builder needs a runtime filter, such as a business day, cannot
+//! be written; `render` names the line.
+//!
+//! ```rust
+//! use natural_cron::crontab::Crontab;
+//!
+//! let mut crontab = Crontab::parse("MAILTO=ops@example.com\n# nightly\n0 2 * * * backup.sh\n").unwrap();
+//! for entry in crontab.entries_mut() {
+//!     entry.command = "backup.sh --full".to_string();
+//! }
+//! assert_eq!(
+//!     crontab.render().unwrap(),
+//!     "MAILTO=ops@example.com\n# nightly\n0 2 * * * backup.sh --full\n"
+//! );
+//! ```
+use crate::cron_expression_builder::CronExpressionBuilder;
+
+/// Shorthand schedules such as `@daily`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrontabMacro {
+    Reboot,
+    Yearly,
+    Annually,
+    Monthly,
+    Weekly,
+    Daily,
+    Midnight,
+    Hourly,
+}
+
+impl CrontabMacro {
+    /// Parses a macro including its leading `@`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "@reboot" => Ok(CrontabMacro::Reboot),
+            "@yearly" => Ok(CrontabMacro::Yearly),
+            "@annually" => Ok(CrontabMacro::Annually),
+            "@monthly" => Ok(CrontabMacro::Monthly),
+            "@weekly" => Ok(CrontabMacro::Weekly),
+            "@daily" => Ok(CrontabMacro::Daily),
+            "@midnight" => Ok(CrontabMacro::Midnight),
+            "@hourly" => Ok(CrontabMacro::Hourly),
+            _ => Err(format!("Invalid crontab macro: {}", value)),
+        }
+    }
+
+    /// The macro as written in a crontab, including the leading `@`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrontabMacro::Reboot => "@reboot",
+            CrontabMacro::Yearly => "@yearly",
+            CrontabMacro::Annually => "@annually",
+            CrontabMacro::Monthly => "@monthly",
+            CrontabMacro::Weekly => "@weekly",
+            CrontabMacro::Daily => "@daily",
+            CrontabMacro::Midnight => "@midnight",
+            CrontabMacro::Hourly => "@hourly",
+        }
+    }
+
+    /// The equivalent schedule; `@reboot` has none since it is not time based
+    pub fn to_builder(&self) -> Option<CronExpressionBuilder> {
+        let builder = CronExpressionBuilder::new();
+        let result = match self {
+            CrontabMacro::Reboot => return None,
+            CrontabMacro::Yearly | CrontabMacro::Annually => builder
+                .every("month")
+                .and_then(|s| s.during_months(vec![1])),
+            CrontabMacro::Monthly => builder.every("month"),
+            CrontabMacro::Weekly => builder.every("week"),
+            CrontabMacro::Daily | CrontabMacro::Midnight => builder.every("day"),
+            CrontabMacro::Hourly => builder.every("hour"),
+        };
+        result.ok()
+    }
+}
+
+/// When a crontab job runs
+#[derive(Debug, Clone, PartialEq)]
+// Nearly every job has an expression, so boxing it would not save memory
+#[allow(clippy::large_enum_variant)]
+pub enum CrontabSchedule {
+    Expression(CronExpressionBuilder),
+    Macro(CrontabMacro),
+}
+
+impl CrontabSchedule {
+    /// The schedule as a builder; `None` for `@reboot`
+    pub fn to_builder(&self) -> Option<CronExpressionBuilder> {
+        match self {
+            CrontabSchedule::Expression(builder) => Some(builder.clone()),
+            CrontabSchedule::Macro(shorthand) => shorthand.to_builder(),
+        }
+    }
+
+    /// The schedule as written in the crontab. Fails for a builder that
+    /// needs a runtime filter, which cron would run more often, or more than
+    /// one line.
+    pub fn to_expression(&self) -> Result<String, String> {
+        match self {
+            CrontabSchedule::Expression(builder) => builder.clone().try_compile(),
+            CrontabSchedule::Macro(shorthand) => Ok(shorthand.as_str().to_string()),
+        }
+    }
+
+    /// The schedule as one or more crontab lines, for times of day that
+    /// share neither their minutes nor their hours
+    pub fn to_expressions(&self) -> Result<Vec<String>, String> {
+        match self {
+            CrontabSchedule::Expression(builder) => builder.clone().compile_lines(),
+            CrontabSchedule::Macro(shorthand) => Ok(vec![shorthand.as_str().to_string()]),
+        }
+    }
+}
+
+/// A single job line
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrontabEntry {
+    pub schedule: CrontabSchedule,
+    /// The user column of system crontabs such as `/etc/crontab`
+    pub user: Option<String>,
+    /// Everything after the schedule (and user), exactly as written
+    pub command: String,
+}
+
+/// One line of a crontab file
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum CrontabLine {
+    Blank,
+    /// A comment, stored exactly as written including its leading `#`
+    Comment(String),
+    /// An assignment such as `MAILTO=ops@example.com`; the value is kept verbatim
+    Environment {
+        name: String,
+        value: String,
+        /// The line as read, written back unchanged while it still assigns
+        /// `value` to `name`
+        raw: Option<String>,
+    },
+    Job(CrontabEntry),
+}
+
+/// The lines of a crontab file, in order
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Crontab {
+    pub lines: Vec<CrontabLine>,
+    has_user_column: bool,
+}
+
+impl Crontab {
+    /// Creates an empty user crontab
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a user crontab, as edited with `crontab -e`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        Self::parse_lines(text, false)
+    }
+
+    /// Parses a system crontab such as `/etc/crontab`, where every job names
+    /// the user it runs as
+    pub fn parse_system(text: &str) -> Result<Self, String> {
+        Self::parse_lines(text, true)
+    }
+
+    fn parse_lines(text: &str, has_user_column: bool) -> Result<Self, String> {
+        let lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                parse_line(line, has_user_column)
+                    .map_err(|e| format!("Invalid crontab line {}: {}", index + 1, e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self {
+            lines,
+            has_user_column,
+        })
+    }
+
+    /// Whether jobs carry a user column
+    pub fn has_user_column(&self) -> bool {
+        self.has_user_column
+    }
+
+    /// Iterates over the jobs
+    pub fn entries(&self) -> impl Iterator<Item = &CrontabEntry> {
+        self.lines.iter().filter_map(|line| match line {
+            CrontabLine::Job(entry) => Some(entry),
+            _ => None,
+        })
+    }
+
+    /// Iterates mutably over the jobs
+    pub fn entries_mut(&mut self) -> impl Iterator<Item = &mut CrontabEntry> {
+        self.lines.iter_mut().filter_map(|line| match line {
+            CrontabLine::Job(entry) => Some(entry),
+            _ => None,
+        })
+    }
+
+    /// Returns the value of the last assignment to `name`
+    pub fn environment(&self, name: &str) -> Option<&str> {
+        self.lines.iter().rev().find_map(|line| match line {
+            CrontabLine::Environment { name: n, value, .. } if n == name => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Appends a job at the end of the file
+    pub fn add_entry(&mut self, entry: CrontabEntry) -> Result<(), String> {
+        if self.has_user_column != entry.user.is_some() {
+            return Err(if self.has_user_column {
+                "System crontab entries require a user.".to_string()
+            } else {
+                "User crontab entries cannot have a user.".to_string()
+            });
+        }
+        entry.schedule.to_expressions()?;
+        self.lines.push(CrontabLine::Job(entry));
+        Ok(())
+    }
+
+    /// Writes the file out, failing on the first job whose schedule cron
+    /// cannot express, such as one edited to skip holidays
+    pub fn render(&self) -> Result<String, String> {
+        let mut text = String::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            let written = match line {
+                CrontabLine::Blank => vec![String::new()],
+                CrontabLine::Comment(comment) => vec![comment.clone()],
+                CrontabLine::Environment { name, value, raw } => match raw {
+                    Some(raw)
+                        if parse_environment(raw.trim())
+                            .is_some_and(|(n, v)| n == *name && v == *value) =>
+                    {
+                        vec![raw.clone()]
+                    }
+                    _ => vec![format!("{}={}", name, value)],
+                },
+                // Times needing several cron lines run the command from each
+                CrontabLine::Job(entry) => entry
+                    .schedule
+                    .to_expressions()
+                    .map_err(|e| format!("Cannot write crontab line {}: {}", index + 1, e))?
+                    .into_iter()
+                    .map(|schedule| match &entry.user {
+                        Some(user) => format!("{} {} {}", schedule, user, entry.command),
+                        None => format!("{} {}", schedule, entry.command),
+                    })
+                    .collect(),
+            };
+            for line in written {
+                text.push_str(&line);
+                text.push('\n');
+            }
+        }
+        Ok(text)
+    }
+}
+
+fn parse_line(line: &str, has_user_column: bool) -> Result<CrontabLine, String> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return Ok(CrontabLine::Blank);
+    }
+    if trimmed.starts_with('#') {
+        return Ok(CrontabLine::Comment(line.to_string()));
+    }
+    if let Some((name, value)) = parse_environment(trimmed) {
+        return Ok(CrontabLine::Environment {
+            name,
+            value,
+            raw: Some(line.to_string()),
+        });
+    }
+
+    let schedule_fields = if trimmed.starts_with('@') { 1 } else { 5 };
+    let user_fields = usize::from(has_user_column);
+    let (fields, command) = split_fields(trimmed, schedule_fields + user_fields)
+        .ok_or_else(|| format!("expected a schedule and a command: '{}'", trimmed))?;
+
+    let schedule = if schedule_fields == 1 {
+        CrontabSchedule::Macro(CrontabMacro::parse(fields[0])?)
+    } else {
+        CrontabSchedule::Expression(CronExpressionBuilder::from_expression(
+            &fields[..5].join(" "),
+        )?)
+    };
+    Ok(CrontabLine::Job(CrontabEntry {
+        schedule,
+        user: has_user_column.then(|| fields[schedule_fields].to_string()),
+        command: command.to_string(),
+    }))
+}
+
+/// Recognises `NAME=value` and `NAME = value`
+fn parse_environment(line: &str) -> Option<(String, String)> {
+    let (name, value) = line.split_once('=')?;
+    let name = name.trim_end();
+    let mut chars = name.chars();
+    let valid_start = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+    if !valid_start || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    Some((name.to_string(), value.trim_start().to_string()))
+}
+
+/// Splits off `count` whitespace separated fields and returns the rest verbatim
+fn split_fields(line: &str, count: usize) -> Option<(Vec<&str>, &str)> {
+    let mut fields = Vec::with_capacity(count);
+    let mut rest = line;
+    for _ in 0..count {
+        rest = rest.trim_start();
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    let command = rest.trim_start();
+    if command.is_empty() {
+        return None;
+    }
+    Some((fields, command))
+}
@@ -1,6 +1,11 @@
 use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum CronTimeUnit {
     Minute,
     Hour,
@@ -9,12 +14,19 @@ pub enum CronTimeUnit {
     DayOfWeek,
 }
 
+#[deprecated(note = "the builder keeps its fields as bitsets and no longer uses schedule maps")]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged)
+)]
 pub enum ScheduleValue {
     String(String),
     Number(i32),
 }
 
+#[allow(deprecated)]
 impl fmt::Display for ScheduleValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -0,0 +1,108 @@
+//! Fixed-period schedules anchored at a start time.
+//!
+//! A cron step restarts with its field: `*/7` minutes runs at :56 and then
+//! again at :00, and `*/14` days runs on the 29th and then on the 1st. An
+//! [`IntervalSchedule`] keeps the same distance between every two runs,
+//! counted from its start, and works wherever a [`Schedule`] does.
+//!
+//! ```rust
+//! use chrono::{Duration, NaiveDate};
+//! use natural_cron::interval::IntervalSchedule;
+//!
+//! // Every other Monday at 09:00, starting on 2025-01-06
+//! let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap().and_hms_opt(9, 0, 0).unwrap();
+//! let schedule = IntervalSchedule::new(start, Duration::weeks(2)).unwrap();
+//!
+//! let after = NaiveDate::from_ymd_opt(2025, 1, 7).unwrap().and_hms_opt(0, 0, 0).unwrap();
+//! assert_eq!(
+//!     schedule.next_after(&after),
+//!     NaiveDate::from_ymd_opt(2025, 1, 20).unwrap().and_hms_opt(9, 0, 0)
+//! );
+//! ```
+use chrono::{Duration, NaiveDateTime};
+
+use crate::cron_schedule::Schedule;
+use crate::interfaces::CronTimeUnit;
+
+/// Runs at `start` and then every `period` after it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntervalSchedule {
+    start: NaiveDateTime,
+    period: Duration,
+}
+
+impl IntervalSchedule {
+    /// Runs every `period` from `start`; the period must be a positive whole
+    /// number of seconds
+    pub fn new(start: NaiveDateTime, period: Duration) -> Result<Self, String> {
+        if period < Duration::seconds(1) || period.subsec_nanos() != 0 {
+            return Err(
+                "Invalid interval: the period should be a whole number of seconds, at least one."
+                    .to_string(),
+            );
+        }
+        Ok(Self { start, period })
+    }
+
+    /// Runs every X units from `start`. Months have no fixed length, and
+    /// days of the week count as days.
+    pub fn every(interval: i32, unit: CronTimeUnit, start: NaiveDateTime) -> Result<Self, String> {
+        let interval = i64::from(interval);
+        let period = match unit {
+            CronTimeUnit::Minute => Duration::minutes(interval),
+            CronTimeUnit::Hour => Duration::hours(interval),
+            CronTimeUnit::DayOfMonth | CronTimeUnit::DayOfWeek => Duration::days(interval),
+            CronTimeUnit::Month => {
+                return Err(
+                    "Invalid interval unit: months differ in length, so they have no fixed period."
+                        .to_string(),
+                );
+            }
+        };
+        Self::new(start, period)
+    }
+
+    pub fn start(&self) -> NaiveDateTime {
+        self.start
+    }
+
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Checks if the time is the start or a whole number of periods after it
+    pub fn matches(&self, time: &NaiveDateTime) -> bool {
+        let elapsed = *time - self.start;
+        elapsed >= Duration::zero()
+            && elapsed.subsec_nanos() == 0
+            && elapsed.num_seconds() % self.period.num_seconds() == 0
+    }
+
+    /// Returns the first run strictly after the given time
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        if *after < self.start {
+            return Some(self.start);
+        }
+        let period = self.period.num_seconds();
+        let periods = (*after - self.start).num_seconds() / period + 1;
+        let offset = Duration::try_seconds(periods.checked_mul(period)?)?;
+        self.start.checked_add_signed(offset)
+    }
+
+    /// Returns up to `count` runs strictly after the given time
+    pub fn upcoming(&self, after: &NaiveDateTime, count: usize) -> Vec<NaiveDateTime> {
+        Schedule::upcoming(self, after, count)
+    }
+}
+
+impl Schedule for IntervalSchedule {
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        IntervalSchedule::next_after(self, after)
+    }
+
+    /// A run stays on time for a minute, or for the whole period if that
+    /// is shorter
+    fn resolution(&self) -> Duration {
+        self.period.min(Duration::minutes(1))
+    }
+}
@@ -0,0 +1,146 @@
+//! Runs a single job while enforcing what happens when runs overlap.
+//!
+//! A job scheduled with `every_x(5, CronTimeUnit::Minute)` that sometimes takes
+//! seven minutes is still running when the next occurrence fires. The
+//! [`OverlapPolicy`] of its [`JobRunner`] decides whether that run is skipped,
+//! queued behind the current one, or started alongside it.
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// The job body shared between the runner and the threads executing it
+pub type Task = Arc<dyn Fn() + Send + Sync>;
+
+/// What to do when a job is due while a previous run is still going
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Drop the new run
+    SkipIfRunning,
+    /// Remember at most one run and start it as soon as the current one ends
+    QueueOne,
+    /// Start the new run alongside the others, up to `max` at once
+    AllowParallel { max: usize },
+}
+
+impl Default for OverlapPolicy {
+    /// Runs may overlap freely; only the worker pool limits them
+    fn default() -> Self {
+        OverlapPolicy::AllowParallel { max: usize::MAX }
+    }
+}
+
+/// Counters describing what happened to the runs of one job
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JobMetrics {
+    /// Runs that started executing
+    pub started: u64,
+    /// Runs that finished, including ones that panicked
+    pub completed: u64,
+    /// Runs that panicked
+    pub failed: u64,
+    /// Runs dropped because the job was already running
+    pub skipped: u64,
+    /// Runs that had to wait for a previous run to finish
+    pub queued: u64,
+    /// Occurrences dropped by the misfire policy
+    pub misfired: u64,
+}
+
+#[derive(Debug, Default)]
+struct RunnerState {
+    running: usize,
+    queued: bool,
+    metrics: JobMetrics,
+}
+
+/// A job together with its overlap policy and run metrics
+pub struct JobRunner {
+    task: Task,
+    policy: OverlapPolicy,
+    state: Mutex<RunnerState>,
+}
+
+impl JobRunner {
+    /// Wraps a job with the given overlap policy
+    pub fn new(task: Task, policy: OverlapPolicy) -> Result<Self, String> {
+        if let OverlapPolicy::AllowParallel { max: 0 } = policy {
+            return Err(
+                "Invalid parallel run limit: 0. At least one run must be allowed.".to_string(),
+            );
+        }
+        Ok(Self {
+            task,
+            policy,
+            state: Mutex::new(RunnerState::default()),
+        })
+    }
+
+    /// Registers a due run and returns whether it should be started now.
+    ///
+    /// Every `true` must be followed by exactly one call to [`JobRunner::run`].
+    pub fn admit(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let limit = match self.policy {
+            OverlapPolicy::AllowParallel { max } => max,
+            OverlapPolicy::SkipIfRunning | OverlapPolicy::QueueOne => 1,
+        };
+        if state.running < limit {
+            state.running += 1;
+            return true;
+        }
+        match self.policy {
+            OverlapPolicy::QueueOne if !state.queued => {
+                state.queued = true;
+                state.metrics.queued += 1;
+            }
+            _ => state.metrics.skipped += 1,
+        }
+        false
+    }
+
+    /// Executes an admitted run, followed by the queued run if there is one
+    pub fn run(&self) {
+        loop {
+            self.state.lock().unwrap().metrics.started += 1;
+            // A panicking job must not take the calling thread down with it
+            let failed = panic::catch_unwind(AssertUnwindSafe(|| (self.task)())).is_err();
+
+            let mut state = self.state.lock().unwrap();
+            state.metrics.completed += 1;
+            if failed {
+                state.metrics.failed += 1;
+            }
+            if state.queued {
+                state.queued = false;
+                continue;
+            }
+            state.running -= 1;
+            return;
+        }
+    }
+
+    /// Admits a run and executes it on a new thread, returning whether it started
+    pub fn trigger(self: &Arc<Self>) -> bool {
+        if !self.admit() {
+            return false;
+        }
+        let runner = Arc::clone(self);
+        thread::spawn(move || runner.run());
+        true
+    }
+
+    /// Records occurrences that the misfire policy decided not to run
+    pub fn record_misfired(&self, count: usize) {
+        self.state.lock().unwrap().metrics.misfired += count as u64;
+    }
+
+    /// Number of runs currently executing
+    pub fn running(&self) -> usize {
+        self.state.lock().unwrap().running
+    }
+
+    /// Returns a snapshot of the run counters
+    pub fn metrics(&self) -> JobMetrics {
+        self.state.lock().unwrap().metrics
+    }
+}
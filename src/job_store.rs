@@ -0,0 +1,238 @@
+//! Persistence of job run times so schedulers can survive restarts.
+//!
+//! A [`JobStore`] remembers, per job name, when the job last ran and when it is
+//! due next. On startup the stored last-run time tells a scheduler which
+//! occurrences were missed while the process was down.
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::NaiveDateTime;
+
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::CronSchedule;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+const EMPTY_TIMESTAMP: &str = "-";
+
+/// The persisted run times of one job
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JobState {
+    /// When the scheduler last handled the job's occurrences
+    pub last_run: Option<NaiveDateTime>,
+    /// When the job is due next
+    pub next_run: Option<NaiveDateTime>,
+    /// When a scheduler first started with the job, so the occurrences
+    /// missed before its first run are still counted after a restart
+    pub first_seen: Option<NaiveDateTime>,
+}
+
+/// Storage backend for job states
+pub trait JobStore: Send + Sync {
+    /// Returns the stored state of a job, if any
+    fn load(&self, job: &str) -> Result<Option<JobState>, String>;
+
+    /// Stores the state of a job, replacing any previous state
+    fn save(&self, job: &str, state: &JobState) -> Result<(), String>;
+
+    /// Forgets a job
+    fn remove(&self, job: &str) -> Result<(), String>;
+
+    /// Lists the names of all stored jobs
+    fn jobs(&self) -> Result<Vec<String>, String>;
+
+    /// Lists the occurrences of `schedule` that passed between the stored
+    /// last run of `job`, or when it was first seen if it never ran, and
+    /// `now`. A job that was never seen has missed nothing.
+    fn missed_occurrences(
+        &self,
+        job: &str,
+        schedule: &CronExpressionBuilder,
+        now: &NaiveDateTime,
+    ) -> Result<Vec<NaiveDateTime>, String> {
+        let schedule = CronSchedule::from_builder(schedule)?;
+        let since = self
+            .load(job)?
+            .and_then(|state| state.last_run.or(state.first_seen));
+        Ok(match since {
+            Some(last_run) => schedule.occurrences_between(&last_run, now),
+            None => Vec::new(),
+        })
+    }
+}
+
+/// Keeps job states in memory; nothing survives the process
+#[derive(Debug, Default)]
+pub struct InMemoryJobStore {
+    states: Mutex<HashMap<String, JobState>>,
+}
+
+impl InMemoryJobStore {
+    /// Creates an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl JobStore for InMemoryJobStore {
+    fn load(&self, job: &str) -> Result<Option<JobState>, String> {
+        Ok(self.states.lock().unwrap().get(job).copied())
+    }
+
+    fn save(&self, job: &str, state: &JobState) -> Result<(), String> {
+        self.states.lock().unwrap().insert(job.to_string(), *state);
+        Ok(())
+    }
+
+    fn remove(&self, job: &str) -> Result<(), String> {
+        self.states.lock().unwrap().remove(job);
+        Ok(())
+    }
+
+    fn jobs(&self) -> Result<Vec<String>, String> {
+        let mut jobs: Vec<String> = self.states.lock().unwrap().keys().cloned().collect();
+        jobs.sort();
+        Ok(jobs)
+    }
+}
+
+/// Keeps job states in a plain text file, one job per line:
+///
+/// ```text
+/// <name>\t<last run>\t<next run>\t<first seen>
+/// ```
+///
+/// Times use `YYYY-MM-DDTHH:MM:SS`, and `-` marks a missing time. Lines
+/// without the first seen column are read as never seen. The file is
+/// rewritten through a temporary file and a rename, so a crash never leaves a
+/// half-written store behind.
+#[derive(Debug)]
+pub struct FileJobStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileJobStore {
+    /// Uses the file at `path`, which is created on the first save
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn read_all(&self) -> Result<Vec<(String, JobState)>, String> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(format!(
+                    "Failed to read job store {}: {}",
+                    self.path.display(),
+                    e
+                ));
+            }
+        };
+
+        let mut states = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() != 3 && parts.len() != 4 {
+                return Err(format!("Invalid job store line {}: '{}'", index + 1, line));
+            }
+            states.push((
+                parts[0].to_string(),
+                JobState {
+                    last_run: parse_timestamp(parts[1], index + 1)?,
+                    next_run: parse_timestamp(parts[2], index + 1)?,
+                    first_seen: match parts.get(3) {
+                        Some(value) => parse_timestamp(value, index + 1)?,
+                        None => None,
+                    },
+                },
+            ));
+        }
+        Ok(states)
+    }
+
+    fn write_all(&self, states: &[(String, JobState)]) -> Result<(), String> {
+        let content: String = states
+            .iter()
+            .map(|(name, state)| {
+                format!(
+                    "{}\t{}\t{}\t{}\n",
+                    name,
+                    format_timestamp(state.last_run),
+                    format_timestamp(state.next_run),
+                    format_timestamp(state.first_seen)
+                )
+            })
+            .collect();
+
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        fs::write(&temporary, content)
+            .and_then(|_| fs::rename(&temporary, &self.path))
+            .map_err(|e| format!("Failed to write job store {}: {}", self.path.display(), e))
+    }
+}
+
+impl JobStore for FileJobStore {
+    fn load(&self, job: &str) -> Result<Option<JobState>, String> {
+        let _guard = self.lock.lock().unwrap();
+        Ok(self
+            .read_all()?
+            .into_iter()
+            .find(|(name, _)| name == job)
+            .map(|(_, state)| state))
+    }
+
+    fn save(&self, job: &str, state: &JobState) -> Result<(), String> {
+        if job.is_empty() || job.contains(['\t', '\n', '\r']) {
+            return Err(format!(
+                "Invalid job name for file store: '{}'. Names must be non-empty and single-line without tabs.",
+                job.escape_debug()
+            ));
+        }
+        let _guard = self.lock.lock().unwrap();
+        let mut states = self.read_all()?;
+        match states.iter_mut().find(|(name, _)| name == job) {
+            Some((_, stored)) => *stored = *state,
+            None => states.push((job.to_string(), *state)),
+        }
+        self.write_all(&states)
+    }
+
+    fn remove(&self, job: &str) -> Result<(), String> {
+        let _guard = self.lock.lock().unwrap();
+        let mut states = self.read_all()?;
+        states.retain(|(name, _)| name != job);
+        self.write_all(&states)
+    }
+
+    fn jobs(&self) -> Result<Vec<String>, String> {
+        let _guard = self.lock.lock().unwrap();
+        Ok(self.read_all()?.into_iter().map(|(name, _)| name).collect())
+    }
+}
+
+fn parse_timestamp(value: &str, line: usize) -> Result<Option<NaiveDateTime>, String> {
+    if value == EMPTY_TIMESTAMP {
+        return Ok(None);
+    }
+    NaiveDateTime::parse_from_str(value, TIMESTAMP_FORMAT)
+        .map(Some)
+        .map_err(|_| format!("Invalid timestamp on job store line {}: '{}'", line, value))
+}
+
+fn format_timestamp(value: Option<NaiveDateTime>) -> String {
+    match value {
+        Some(time) => time.format(TIMESTAMP_FORMAT).to_string(),
+        None => EMPTY_TIMESTAMP.to_string(),
+    }
+}
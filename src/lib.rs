@@ -1,9 +1,37 @@
 //! Natural Cron - Easily build cron expressions in Rust.
+pub mod alternate_weeks;
+pub mod blackout;
+pub mod blocking_scheduler;
+pub mod bounded;
+pub mod calendar;
+pub mod clock;
+pub mod composite;
+pub mod cron_explainer;
 pub mod cron_expression_builder;
+pub mod cron_lint;
+pub mod cron_schedule;
 pub mod cron_utils;
 pub mod cron_validator;
+pub mod crontab;
 pub mod interfaces;
+pub mod interval;
+pub mod job_runner;
+pub mod job_store;
+pub mod misfire;
+pub mod month_end;
+pub mod quartz;
+pub mod rrule;
+mod runtime_days;
+#[cfg(feature = "serde")]
+pub mod schedule_config;
 pub mod schedules;
+#[cfg(feature = "serde")]
+pub mod serde_support;
+pub mod time_of_day;
+pub mod utc;
 
+pub use blocking_scheduler::BlockingScheduler;
 pub use cron_expression_builder::CronExpressionBuilder;
+pub use cron_schedule::{CronSchedule, Schedule};
 pub use cron_validator::CronValidators;
+pub use time_of_day::TimeOfDay;
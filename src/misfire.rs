@@ -0,0 +1,99 @@
+//! Misfire handling for occurrences that were missed while nothing was running.
+//!
+//! A misfire happens when a runner wakes up after one or more occurrences of a
+//! schedule have already passed, e.g. after a restart or when the host slept.
+//! [`MisfirePolicy::resolve`] turns a last-run timestamp and the current time
+//! into a decision, so every runner built on this crate behaves the same way.
+use chrono::{Duration, NaiveDateTime};
+
+use crate::cron_schedule::Schedule;
+
+/// What to do with occurrences that passed without running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MisfirePolicy {
+    /// Run once now, however many occurrences were missed
+    #[default]
+    FireOnceNow,
+    /// Run once for every missed occurrence
+    FireAll,
+    /// Drop missed occurrences and wait for the next one
+    SkipToNext,
+    /// Run once now if the latest missed occurrence is no older than the grace period
+    FireWithinGrace(Duration),
+}
+
+/// The result of applying a [`MisfirePolicy`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MisfireOutcome {
+    /// Scheduled times that should be run now, oldest first
+    pub fire: Vec<NaiveDateTime>,
+    /// Number of due occurrences that were dropped
+    pub skipped: usize,
+    /// The next occurrence after the current time
+    pub next_run: Option<NaiveDateTime>,
+}
+
+impl MisfirePolicy {
+    /// Decides which occurrences in `(last_run, now]` should run.
+    ///
+    /// An occurrence still inside its own slot (less than the schedule's
+    /// resolution old, a minute for five-field cron expressions) is on time,
+    /// not missed, and always runs.
+    pub fn resolve<S: Schedule + ?Sized>(
+        &self,
+        schedule: &S,
+        last_run: &NaiveDateTime,
+        now: &NaiveDateTime,
+    ) -> MisfireOutcome {
+        let next_run = schedule.next_after(now);
+        if *self == MisfirePolicy::FireAll {
+            return MisfireOutcome {
+                fire: schedule.occurrences_between(last_run, now),
+                skipped: 0,
+                next_run,
+            };
+        }
+
+        // The other policies fire at most the latest due run, so the rest are
+        // only counted
+        let (latest, due_count) = latest_between(schedule, last_run, now);
+        let on_time = latest.is_some_and(|latest| *now - latest < schedule.resolution());
+        let fire: Vec<NaiveDateTime> = match (self, latest) {
+            (MisfirePolicy::FireOnceNow, Some(latest)) => vec![latest],
+            (MisfirePolicy::SkipToNext, Some(latest)) if on_time => vec![latest],
+            (MisfirePolicy::FireWithinGrace(grace), Some(latest))
+                if on_time || *now - latest <= *grace =>
+            {
+                vec![latest]
+            }
+            _ => Vec::new(),
+        };
+
+        // Every policy fires a subset of the due runs
+        MisfireOutcome {
+            skipped: due_count - fire.len(),
+            fire,
+            next_run,
+        }
+    }
+}
+
+/// The latest run in `(after, until]` and how many runs there are
+fn latest_between<S: Schedule + ?Sized>(
+    schedule: &S,
+    after: &NaiveDateTime,
+    until: &NaiveDateTime,
+) -> (Option<NaiveDateTime>, usize) {
+    let mut latest = None;
+    let mut count = 0;
+    let mut cursor = *after;
+    while let Some(next) = schedule.next_after(&cursor) {
+        if next > *until {
+            break;
+        }
+        latest = Some(next);
+        count += 1;
+        cursor = next;
+    }
+    (latest, count)
+}
@@ -0,0 +1,114 @@
+//! Schedules counted back from the end of the month.
+//!
+//! "3 days before the end of every month" falls on the 28th of January, the
+//! 25th or 26th of February and the 27th of April. Classic cron has no way to
+//! say that, so [`CronExpressionBuilder::on_days_before_month_end`] is
+//! evaluated by a [`MonthEndSchedule`]. Quartz writes the same thing as `L-3`.
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use natural_cron::CronExpressionBuilder;
+//! use natural_cron::month_end::MonthEndSchedule;
+//!
+//! let builder = CronExpressionBuilder::new()
+//!     .at_time("06:00")
+//!     .unwrap()
+//!     .on_days_before_month_end(3)
+//!     .unwrap();
+//! let schedule = MonthEndSchedule::from_builder(&builder).unwrap();
+//!
+//! let after = NaiveDate::from_ymd_opt(2025, 2, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+//! assert_eq!(
+//!     schedule.next_after(&after),
+//!     NaiveDate::from_ymd_opt(2025, 2, 25).unwrap().and_hms_opt(6, 0, 0)
+//! );
+//! ```
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+
+use crate::blackout::BlackoutSchedule;
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::{CronSchedule, Schedule, days_in_month};
+use crate::runtime_days::{RuntimeDays, missing_runtime_days};
+
+/// A schedule that runs only a number of days before the last day of each
+/// month; zero days is the last day itself
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthEndSchedule {
+    schedule: BlackoutSchedule,
+    days_before: u32,
+    /// Business days or alternate weeks also set on the builder
+    also: RuntimeDays,
+}
+
+impl MonthEndSchedule {
+    /// Runs at the times of the schedule on the given day of each month
+    pub fn new(schedule: CronSchedule, days_before: u32) -> Self {
+        Self {
+            schedule: BlackoutSchedule::new(schedule, Vec::new()),
+            days_before,
+            also: RuntimeDays::default(),
+        }
+    }
+
+    /// Takes the days before month end, blackouts and any other runtime days
+    /// set on a builder
+    pub fn from_builder(builder: &CronExpressionBuilder) -> Result<Self, String> {
+        let Some(days_before) = builder.days_before_month_end() else {
+            return Err(missing_runtime_days("days before month end"));
+        };
+        Ok(Self {
+            schedule: BlackoutSchedule::from_builder(builder)?,
+            days_before,
+            also: RuntimeDays {
+                days_before_month_end: None,
+                ..RuntimeDays::from_builder(builder)
+            },
+        })
+    }
+
+    pub fn days_before(&self) -> u32 {
+        self.days_before
+    }
+
+    /// Checks if the date is the scheduled day of its month
+    pub fn matches_date(&self, date: &NaiveDate) -> bool {
+        is_days_before_end(date, self.days_before) && self.also.matches(date)
+    }
+
+    /// Returns the first run strictly after the given time
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        self.schedule
+            .next_on_dates(after, |date| self.matches_date(date))
+    }
+
+    /// Returns up to `count` runs strictly after the given time
+    pub fn upcoming(&self, after: &NaiveDateTime, count: usize) -> Vec<NaiveDateTime> {
+        Schedule::upcoming(self, after, count)
+    }
+}
+
+impl Schedule for MonthEndSchedule {
+    fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime> {
+        MonthEndSchedule::next_after(self, after)
+    }
+
+    fn resolution(&self) -> Duration {
+        self.schedule.resolution()
+    }
+}
+
+/// Checks if the date is the given number of days before the last day of
+/// its month
+pub(crate) fn is_days_before_end(date: &NaiveDate, days_before: u32) -> bool {
+    days_in_month(date.year(), date.month()).checked_sub(days_before) == Some(date.day())
+}
+
+/// Describes a number of days before month end, e.g. "3 days before the end
+/// of the month"
+pub(crate) fn describe(days_before: u32) -> String {
+    match days_before {
+        0 => "the last day of the month".to_string(),
+        1 => "1 day before the end of the month".to_string(),
+        days => format!("{} days before the end of the month", days),
+    }
+}
@@ -0,0 +1,377 @@
+//! Conversion between classic cron and Quartz scheduler expressions.
+//!
+//! Quartz expressions start with a seconds field, may end with a year field,
+//! number the days of the week from 1 (Sunday) to 7 (Saturday) and require
+//! `?` in exactly one of the two day fields.
+use chrono::{Datelike, NaiveDate};
+
+use crate::calendar::BusinessDay;
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::{CronSchedule, DAY_NAMES, MONTH_NAMES, days_in_month, parse_field};
+use crate::cron_utils::CronUtils;
+use crate::month_end;
+
+/// Quartz-only day field features that classic cron cannot express
+const QUARTZ_ONLY: [char; 3] = ['L', 'W', '#'];
+
+/// Years Quartz accepts in its optional year field
+const QUARTZ_YEARS: (i32, i32) = (1970, 2199);
+
+/// A Quartz day-of-month field
+enum MonthDays {
+    /// `?`
+    Unspecified,
+    /// `L` or `L-n`: the last day of the month, minus an offset
+    Last(u32),
+    /// `nW`: the weekday nearest to day n, within the same month
+    NearestWeekday(u32),
+    /// `LW`: the last weekday of the month
+    LastWeekday,
+    /// Plain days as a bitset
+    Days(u64),
+}
+
+/// A Quartz day-of-week field, numbered 1 (Sunday) to 7 (Saturday)
+enum WeekDays {
+    /// `?`
+    Unspecified,
+    /// `nL`: the last given weekday of the month, which every month has
+    Last,
+    /// `n#k`: the k-th given weekday of the month
+    Nth(u32, u32),
+    /// Plain weekdays as a bitset
+    Days(u64),
+}
+
+pub struct QuartzConverter;
+
+impl QuartzConverter {
+    /// Converts a five- or six-field cron expression into a Quartz expression
+    pub fn to_quartz(expression: &str) -> Result<String, String> {
+        let schedule = CronSchedule::parse(expression)?;
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let (seconds, fields) = if fields.len() == 6 {
+            (fields[0], &fields[1..])
+        } else {
+            ("0", &fields[..])
+        };
+
+        let (day_of_month, day_of_week) = match (
+            schedule.is_day_of_month_restricted(),
+            schedule.is_day_of_week_restricted(),
+        ) {
+            (true, true) => {
+                return Err(format!(
+                    "Cannot convert '{}' to Quartz: Quartz cannot combine day-of-month and day-of-week.",
+                    expression
+                ));
+            }
+            (false, true) => {
+                let days: Vec<i32> = schedule
+                    .days_of_week()
+                    .iter()
+                    .map(|d| *d as i32 + 1)
+                    .collect();
+                ("?".to_string(), CronUtils::format_cron_part(&days))
+            }
+            _ => (fields[2].to_string(), "?".to_string()),
+        };
+
+        Ok(format!(
+            "{} {} {} {} {} {}",
+            seconds, fields[0], fields[1], day_of_month, fields[3], day_of_week
+        ))
+    }
+
+    /// Converts a builder into a Quartz expression. The last and the first
+    /// business day of the month become `LW` and `1W`, which Quartz supports
+    /// when business days are Monday to Friday without holidays, and days
+    /// before month end become `L-n`.
+    pub fn from_builder(builder: &CronExpressionBuilder) -> Result<String, String> {
+        let (description, day_of_month) = if let Some(days) = builder.days_before_month_end() {
+            if let Some(day) = builder.business_day() {
+                return Err(format!(
+                    "Cannot convert {} to Quartz: its day of month cannot also hold the {}.",
+                    month_end::describe(days),
+                    day
+                ));
+            }
+            let day_of_month = match days {
+                0 => "L".to_string(),
+                days => format!("L-{}", days),
+            };
+            (month_end::describe(days), day_of_month)
+        } else if let Some(day) = builder.business_day() {
+            let day_of_month = match day {
+                BusinessDay::Last => "LW",
+                BusinessDay::Nth(1) => "1W",
+                BusinessDay::Nth(_) => {
+                    return Err(format!(
+                        "Cannot convert the {} to Quartz: Quartz can only express the first and last.",
+                        day
+                    ));
+                }
+            };
+            if !builder.calendar().is_weekdays_only() {
+                return Err(format!(
+                    "Cannot convert the {} to Quartz: Quartz does not know about holidays.",
+                    day
+                ));
+            }
+            (format!("the {}", day), day_of_month.to_string())
+        } else {
+            return Self::to_quartz(&builder.clone().try_compile()?);
+        };
+
+        let expression = builder.without_month_day().try_compile()?;
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let (seconds, fields) = if fields.len() == 6 {
+            (fields[0], &fields[1..])
+        } else {
+            ("0", &fields[..])
+        };
+        if fields[2] != "*" || fields[4] != "*" {
+            return Err(format!(
+                "Cannot convert {} to Quartz: the day fields of '{}' are already set.",
+                description, expression
+            ));
+        }
+        Ok(format!(
+            "{} {} {} {} {} ?",
+            seconds, fields[0], fields[1], day_of_month, fields[3]
+        ))
+    }
+
+    /// Converts a six- or seven-field Quartz expression into a five-field cron expression
+    pub fn from_quartz(expression: &str) -> Result<String, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 6 && fields.len() != 7 {
+            return Err(format!(
+                "Invalid Quartz expression: '{}'. Expected 6 or 7 fields.",
+                expression
+            ));
+        }
+        if fields.len() == 7 && fields[6] != "*" {
+            return Err(format!(
+                "Cannot convert '{}' to cron: cron has no year field.",
+                expression
+            ));
+        }
+        if fields[0] != "0" {
+            return Err(format!(
+                "Cannot convert '{}' to cron: five-field cron always fires at second 0.",
+                expression
+            ));
+        }
+        for field in [fields[3], fields[5]] {
+            if field.contains(QUARTZ_ONLY) {
+                return Err(format!(
+                    "Cannot convert '{}' to cron: '{}' has no cron equivalent.",
+                    expression, field
+                ));
+            }
+        }
+
+        let day_of_month = if fields[3] == "?" { "*" } else { fields[3] };
+        let day_of_week = if fields[5] == "?" || fields[5] == "*" {
+            "*".to_string()
+        } else {
+            let bits = parse_field(fields[5], "day of week", 1, 7, Some(&DAY_NAMES))?;
+            let days: Vec<i32> = (1..=7)
+                .filter(|d| bits & (1 << d) != 0)
+                .map(|d| d - 1)
+                .collect();
+            CronUtils::format_cron_part(&days)
+        };
+
+        let cron = format!(
+            "{} {} {} {} {}",
+            fields[1], fields[2], day_of_month, fields[4], day_of_week
+        );
+        CronSchedule::parse(&cron)?;
+        Ok(cron)
+    }
+
+    /// Validates a six- or seven-field Quartz expression, including `L`, `W`
+    /// and `#`, and checks that at least one date can ever match it
+    pub fn validate(expression: &str) -> Result<(), String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 6 && fields.len() != 7 {
+            return Err(format!(
+                "Invalid Quartz expression: '{}'. Expected 6 or 7 fields.",
+                expression
+            ));
+        }
+        parse_field(fields[0], "second", 0, 59, None)?;
+        parse_field(fields[1], "minute", 0, 59, None)?;
+        parse_field(fields[2], "hour", 0, 23, None)?;
+        let months = parse_field(fields[4], "month", 1, 12, Some(&MONTH_NAMES))?;
+        let month_days = parse_month_days(fields[3])?;
+        let week_days = parse_week_days(fields[5])?;
+        let years = match fields.get(6) {
+            Some(field) => parse_years(field)?,
+            // Weekday and leap year patterns repeat every 400 years
+            None => (2000..2400).collect(),
+        };
+
+        match (&month_days, &week_days) {
+            (MonthDays::Unspecified, WeekDays::Unspecified) => {
+                return Err(format!(
+                    "Invalid Quartz expression: '{}'. Only one of day-of-month and day-of-week can be '?'.",
+                    expression
+                ));
+            }
+            (MonthDays::Unspecified, _) | (_, WeekDays::Unspecified) => {}
+            _ => {
+                return Err(format!(
+                    "Invalid Quartz expression: '{}'. One of day-of-month and day-of-week must be '?'.",
+                    expression
+                ));
+            }
+        }
+
+        let fires = years.iter().any(|year| {
+            (1..=12).any(|month| {
+                months & (1 << month) != 0 && fires_in_month(*year, month, &month_days, &week_days)
+            })
+        });
+        if fires {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid schedule: '{}'. No date can ever match it.",
+                expression
+            ))
+        }
+    }
+}
+
+fn parse_month_days(field: &str) -> Result<MonthDays, String> {
+    let invalid = || format!("Invalid day of month: '{}'", field);
+    if field == "?" {
+        return Ok(MonthDays::Unspecified);
+    }
+    if field == "LW" {
+        return Ok(MonthDays::LastWeekday);
+    }
+    if let Some(offset) = field.strip_prefix('L') {
+        let offset = match offset.strip_prefix('-') {
+            Some(n) => n.parse().ok().filter(|n| *n <= 30).ok_or_else(invalid)?,
+            None if offset.is_empty() => 0,
+            None => return Err(invalid()),
+        };
+        return Ok(MonthDays::Last(offset));
+    }
+    if let Some(day) = field.strip_suffix('W') {
+        let day = day
+            .parse()
+            .ok()
+            .filter(|d| (1..=31).contains(d))
+            .ok_or_else(invalid)?;
+        return Ok(MonthDays::NearestWeekday(day));
+    }
+    Ok(MonthDays::Days(parse_field(
+        field,
+        "day of month",
+        1,
+        31,
+        None,
+    )?))
+}
+
+fn parse_week_days(field: &str) -> Result<WeekDays, String> {
+    let invalid = || format!("Invalid day of week: '{}'", field);
+    let day = |value: &str| -> Result<u32, String> {
+        let bits = parse_field(value, "day of week", 1, 7, Some(&DAY_NAMES))?;
+        Ok(bits.trailing_zeros())
+    };
+    if field == "?" {
+        return Ok(WeekDays::Unspecified);
+    }
+    if let Some((weekday, nth)) = field.split_once('#') {
+        let nth = nth
+            .parse()
+            .ok()
+            .filter(|n| (1..=5).contains(n))
+            .ok_or_else(invalid)?;
+        return Ok(WeekDays::Nth(day(weekday)?, nth));
+    }
+    if let Some(weekday) = field.strip_suffix('L') {
+        day(weekday)?;
+        return Ok(WeekDays::Last);
+    }
+    Ok(WeekDays::Days(parse_field(
+        field,
+        "day of week",
+        1,
+        7,
+        Some(&DAY_NAMES),
+    )?))
+}
+
+fn parse_years(field: &str) -> Result<Vec<i32>, String> {
+    let (min, max) = QUARTZ_YEARS;
+    let invalid = |value: &str| {
+        format!(
+            "Invalid year: '{}'. Value should be between {} and {}.",
+            value, min, max
+        )
+    };
+    let year = |value: &str| {
+        value
+            .parse::<i32>()
+            .ok()
+            .filter(|y| (min..=max).contains(y))
+            .ok_or_else(|| invalid(value))
+    };
+
+    let mut years = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<usize>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("Invalid step in year field: '{}'", part))?,
+            ),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (year(start)?, year(end)?)
+        } else if part.contains('/') {
+            (year(range)?, max)
+        } else {
+            (year(range)?, year(range)?)
+        };
+        years.extend((start..=end).step_by(step));
+    }
+    Ok(years)
+}
+
+fn fires_in_month(year: i32, month: u32, month_days: &MonthDays, week_days: &WeekDays) -> bool {
+    let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return false;
+    };
+    let last = days_in_month(year, month);
+    match month_days {
+        MonthDays::Last(offset) => return *offset < last,
+        // Quartz never moves a W day into another month
+        MonthDays::NearestWeekday(day) => return *day <= last,
+        MonthDays::LastWeekday => return true,
+        MonthDays::Days(bits) => return (1..=last).any(|d| bits & (1 << d) != 0),
+        MonthDays::Unspecified => {}
+    }
+    match week_days {
+        WeekDays::Nth(weekday, nth) => {
+            let first_weekday = first.weekday().num_days_from_sunday() + 1;
+            let day = 1 + (weekday + 7 - first_weekday) % 7 + 7 * (nth - 1);
+            day <= last
+        }
+        WeekDays::Last => true,
+        WeekDays::Days(bits) => *bits != 0,
+        WeekDays::Unspecified => false,
+    }
+}
@@ -0,0 +1,497 @@
+//! Conversion between cron and iCalendar recurrence rules (RFC 5545).
+//!
+//! Every cron expression whose day fields are not both set has an `RRULE`
+//! equivalent. The other way round, the parts of a rule that cron has no
+//! field for, such as `COUNT`, `UNTIL` and `INTERVAL`, come back as metadata
+//! next to the expressions:
+//!
+//! ```rust
+//! use natural_cron::CronExpressionBuilder;
+//! use natural_cron::rrule::RRuleConverter;
+//!
+//! let builder = CronExpressionBuilder::new()
+//!     .at_time("09:00")
+//!     .unwrap()
+//!     .on_week_days(vec![1, 3])
+//!     .unwrap();
+//! assert_eq!(
+//!     RRuleConverter::from_builder(&builder),
+//!     Ok("FREQ=WEEKLY;BYDAY=MO,WE;BYHOUR=9;BYMINUTE=0".to_string())
+//! );
+//!
+//! let imported = RRuleConverter::import("DTSTART:20260105T093000\nRRULE:FREQ=WEEKLY;COUNT=10").unwrap();
+//! assert_eq!(imported.expressions, vec!["30 9 * * 1".to_string()]);
+//! assert_eq!(imported.count, Some(10));
+//! ```
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::CronSchedule;
+use crate::cron_utils::CronUtils;
+
+/// Two-letter weekday codes, Sunday first like cron
+const WEEKDAY_CODES: [&str; 7] = ["SU", "MO", "TU", "WE", "TH", "FR", "SA"];
+
+/// Rule parts that select days cron cannot describe
+const UNSUPPORTED_PARTS: [&str; 3] = ["BYSETPOS", "BYWEEKNO", "BYYEARDAY"];
+
+/// How often a rule repeats, from finest to coarsest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Frequency {
+    Secondly,
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "SECONDLY" => Ok(Frequency::Secondly),
+            "MINUTELY" => Ok(Frequency::Minutely),
+            "HOURLY" => Ok(Frequency::Hourly),
+            "DAILY" => Ok(Frequency::Daily),
+            "WEEKLY" => Ok(Frequency::Weekly),
+            "MONTHLY" => Ok(Frequency::Monthly),
+            "YEARLY" => Ok(Frequency::Yearly),
+            _ => Err(format!("Invalid RRULE frequency: {}", value)),
+        }
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Frequency::Secondly => "SECONDLY",
+            Frequency::Minutely => "MINUTELY",
+            Frequency::Hourly => "HOURLY",
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        })
+    }
+}
+
+/// An `INTERVAL` above one: only every `every`-th period counted from the
+/// anchor runs, which cron cannot express
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RRuleInterval {
+    pub every: u32,
+    pub frequency: Frequency,
+    /// The `DTSTART` the periods are counted from
+    pub anchor: NaiveDateTime,
+}
+
+/// Cron expressions for a recurrence rule, with what they leave out
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedRRule {
+    /// Together run at every time the rules allow
+    pub expressions: Vec<String>,
+    /// `DTSTART`: no run is earlier
+    pub start: Option<NaiveDateTime>,
+    /// `UNTIL`: no run is later
+    pub end: Option<NaiveDateTime>,
+    /// `COUNT`: only the first runs from the start count
+    pub count: Option<u32>,
+    pub interval: Option<RRuleInterval>,
+}
+
+impl ImportedRRule {
+    /// Whether the expressions alone describe the rule, with no bounds or
+    /// interval to apply on top
+    pub fn is_plain_cron(&self) -> bool {
+        self.end.is_none() && self.count.is_none() && self.interval.is_none()
+    }
+}
+
+/// The parts of one `RRULE` value
+struct Rule<'a> {
+    text: &'a str,
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<NaiveDateTime>,
+    seconds: Option<Vec<i32>>,
+    minutes: Option<Vec<i32>>,
+    hours: Option<Vec<i32>>,
+    days_of_week: Option<Vec<i32>>,
+    days_of_month: Option<Vec<i32>>,
+    months: Option<Vec<i32>>,
+}
+
+pub struct RRuleConverter;
+
+impl RRuleConverter {
+    /// Converts a five- or six-field cron expression into an `RRULE` value.
+    /// Five-field rules leave out `BYSECOND`, so `DTSTART` should fall on a
+    /// whole minute.
+    pub fn to_rrule(expression: &str) -> Result<String, String> {
+        let schedule = CronSchedule::parse(expression)?;
+        let day_of_month = schedule.is_day_of_month_restricted();
+        let day_of_week = schedule.is_day_of_week_restricted();
+        if day_of_month && day_of_week {
+            return Err(format!(
+                "Cannot convert '{}' to an RRULE: cron runs on days matching either day field, while an RRULE needs both to match.",
+                expression
+            ));
+        }
+
+        let full = |values: &[u32], count: usize| values.len() == count;
+        let (seconds, minutes, hours, months) = (
+            schedule.seconds(),
+            schedule.minutes(),
+            schedule.hours(),
+            schedule.months(),
+        );
+        let months_restricted = !full(&months, 12);
+        let frequency = if schedule.has_seconds() && full(&seconds, 60) {
+            Frequency::Secondly
+        } else if full(&minutes, 60) {
+            Frequency::Minutely
+        } else if full(&hours, 24) {
+            Frequency::Hourly
+        } else {
+            // The coarsest frequency that still reads naturally; with the
+            // time parts spelled out they all run at the same times
+            match (months_restricted, day_of_month, day_of_week) {
+                (false, false, true) => Frequency::Weekly,
+                (false, true, false) => Frequency::Monthly,
+                (true, true, false) => Frequency::Yearly,
+                _ => Frequency::Daily,
+            }
+        };
+
+        let list = |values: &[u32]| {
+            values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let mut parts = vec![format!("FREQ={}", frequency)];
+        if months_restricted {
+            parts.push(format!("BYMONTH={}", list(&months)));
+        }
+        if day_of_month {
+            parts.push(format!("BYMONTHDAY={}", list(&schedule.days_of_month())));
+        }
+        if day_of_week {
+            let days: Vec<&str> = schedule
+                .days_of_week()
+                .iter()
+                .map(|day| WEEKDAY_CODES[*day as usize])
+                .collect();
+            parts.push(format!("BYDAY={}", days.join(",")));
+        }
+        if !full(&hours, 24) {
+            parts.push(format!("BYHOUR={}", list(&hours)));
+        }
+        if !full(&minutes, 60) {
+            parts.push(format!("BYMINUTE={}", list(&minutes)));
+        }
+        if schedule.has_seconds() && !full(&seconds, 60) {
+            parts.push(format!("BYSECOND={}", list(&seconds)));
+        }
+        Ok(parts.join(";"))
+    }
+
+    /// Converts the expression compiled from a builder into an `RRULE`
+    /// value
+    pub fn from_builder(builder: &CronExpressionBuilder) -> Result<String, String> {
+        Self::to_rrule(&builder.clone().try_compile()?)
+    }
+
+    /// Reads an optional `DTSTART` line and one or more `RRULE` lines, with
+    /// or without their property names, into one cron expression per rule.
+    /// Parts the rules take from `DTSTART`, such as the time of day of a
+    /// daily rule, need it to be present. Time zones are ignored and times
+    /// are taken as written.
+    pub fn import(text: &str) -> Result<ImportedRRule, String> {
+        let mut start = None;
+        let mut rules = Vec::new();
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (name, value) = match line.split_once(':') {
+                Some((name, value)) => (name.split(';').next().unwrap_or(name), value),
+                None => ("RRULE", line),
+            };
+            match name.to_ascii_uppercase().as_str() {
+                "DTSTART" => start = Some(parse_date_time(value, false)?),
+                "RRULE" => rules.push(Rule::parse(value)?),
+                _ => return Err(format!("Invalid RRULE line: {}", line)),
+            }
+        }
+        let Some(first) = rules.first() else {
+            return Err("Invalid RRULE: no rule was given.".to_string());
+        };
+        let limits = |rule: &Rule| {
+            let frequency = (rule.interval > 1).then_some(rule.frequency);
+            (rule.count, rule.until, rule.interval, frequency)
+        };
+        if rules.iter().any(|rule| limits(rule) != limits(first)) {
+            return Err(
+                "Cannot import several RRULEs with different COUNT, UNTIL or INTERVAL parts."
+                    .to_string(),
+            );
+        }
+
+        let interval = match first.interval {
+            1 => None,
+            every => Some(RRuleInterval {
+                every,
+                frequency: first.frequency,
+                anchor: start.ok_or_else(|| {
+                    format!(
+                        "Invalid RRULE: '{}' has an INTERVAL but no DTSTART to count it from.",
+                        first.text
+                    )
+                })?,
+            }),
+        };
+        let (end, count) = (first.until, first.count);
+        let expressions = rules
+            .iter()
+            .map(|rule| rule.to_cron(start))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ImportedRRule {
+            expressions,
+            start,
+            end,
+            count,
+            interval,
+        })
+    }
+}
+
+impl<'a> Rule<'a> {
+    fn parse(text: &'a str) -> Result<Self, String> {
+        let mut frequency = None;
+        let mut rule = Rule {
+            text,
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            seconds: None,
+            minutes: None,
+            hours: None,
+            days_of_week: None,
+            days_of_month: None,
+            months: None,
+        };
+        for part in text.split(';').filter(|part| !part.is_empty()) {
+            let Some((name, value)) = part.split_once('=') else {
+                return Err(format!("Invalid RRULE part: {}", part));
+            };
+            let name = name.to_ascii_uppercase();
+            let value = value.to_ascii_uppercase();
+            match name.as_str() {
+                "FREQ" => frequency = Some(Frequency::parse(&value)?),
+                "INTERVAL" => {
+                    rule.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|interval| *interval > 0)
+                        .ok_or_else(|| format!("Invalid RRULE interval: {}", value))?
+                }
+                "COUNT" => {
+                    rule.count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|count| *count > 0)
+                            .ok_or_else(|| format!("Invalid RRULE count: {}", value))?,
+                    )
+                }
+                "UNTIL" => rule.until = Some(parse_date_time(&value, true)?),
+                "BYSECOND" => rule.seconds = Some(parse_numbers(&name, &value, 0, 59)?),
+                "BYMINUTE" => rule.minutes = Some(parse_numbers(&name, &value, 0, 59)?),
+                "BYHOUR" => rule.hours = Some(parse_numbers(&name, &value, 0, 23)?),
+                "BYMONTH" => rule.months = Some(parse_numbers(&name, &value, 1, 12)?),
+                "BYMONTHDAY" => {
+                    if value.starts_with('-') || value.contains(",-") {
+                        return Err(format!(
+                            "Cannot convert '{}' to cron: cron cannot count days back from the end of the month.",
+                            text
+                        ));
+                    }
+                    rule.days_of_month = Some(parse_numbers(&name, &value, 1, 31)?);
+                }
+                "BYDAY" => rule.days_of_week = Some(parse_weekdays(text, &value)?),
+                // Only matters for weekly intervals, which are left to the caller
+                "WKST" => {}
+                _ if UNSUPPORTED_PARTS.contains(&name.as_str()) => {
+                    return Err(format!(
+                        "Cannot convert '{}' to cron: {} is not supported.",
+                        text, name
+                    ));
+                }
+                _ => return Err(format!("Invalid RRULE part: {}", part)),
+            }
+        }
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err(format!(
+                "Invalid RRULE: '{}' sets both COUNT and UNTIL.",
+                text
+            ));
+        }
+        rule.frequency =
+            frequency.ok_or_else(|| format!("Invalid RRULE: '{}' has no FREQ.", text))?;
+        if rule.frequency == Frequency::Weekly && rule.days_of_month.is_some() {
+            return Err(format!(
+                "Invalid RRULE: '{}' uses BYMONTHDAY with FREQ=WEEKLY.",
+                text
+            ));
+        }
+        Ok(rule)
+    }
+
+    /// The cron expression for every time the rule allows, ignoring its
+    /// interval and bounds
+    fn to_cron(&self, start: Option<NaiveDateTime>) -> Result<String, String> {
+        if self.days_of_week.is_some() && self.days_of_month.is_some() {
+            return Err(format!(
+                "Cannot convert '{}' to cron: an RRULE needs both BYDAY and BYMONTHDAY to match, while cron runs on days matching either.",
+                self.text
+            ));
+        }
+        let from_start = |value: fn(&NaiveDateTime) -> u32, part: &str| {
+            start
+                .map(|start| vec![value(&start) as i32])
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid RRULE: '{}' has no {} and no DTSTART to take it from.",
+                        self.text, part
+                    )
+                })
+        };
+        // Parts as fine as the frequency or finer repeat through every
+        // value; coarser ones default to the start
+        let field = |given: &Option<Vec<i32>>,
+                     repeats_up_to: Frequency,
+                     value: fn(&NaiveDateTime) -> u32,
+                     part: &str| match given {
+            Some(values) => Ok(Some(values.clone())),
+            None if self.frequency <= repeats_up_to => Ok(None),
+            None => from_start(value, part).map(Some),
+        };
+
+        let seconds = match &self.seconds {
+            Some(values) => Some(values.clone()),
+            None if self.frequency == Frequency::Secondly => None,
+            None => Some(vec![start.map_or(0, |start| start.second() as i32)]),
+        };
+        let minutes = field(
+            &self.minutes,
+            Frequency::Minutely,
+            NaiveDateTime::minute,
+            "BYMINUTE",
+        )?;
+        let hours = field(
+            &self.hours,
+            Frequency::Hourly,
+            NaiveDateTime::hour,
+            "BYHOUR",
+        )?;
+        let days_given = self.days_of_week.is_some() || self.days_of_month.is_some();
+        let months = match &self.months {
+            Some(values) => Some(values.clone()),
+            None if self.frequency == Frequency::Yearly && !days_given => {
+                Some(from_start(|start| start.month(), "BYMONTH")?)
+            }
+            None => None,
+        };
+        let days_of_week = match &self.days_of_week {
+            Some(values) => Some(values.clone()),
+            None if self.frequency == Frequency::Weekly => Some(from_start(
+                |start| start.weekday().num_days_from_sunday(),
+                "BYDAY",
+            )?),
+            None => None,
+        };
+        let days_of_month = match &self.days_of_month {
+            Some(values) => Some(values.clone()),
+            None if self.frequency >= Frequency::Monthly && !days_given => {
+                Some(from_start(|start| start.day(), "BYMONTHDAY")?)
+            }
+            None => None,
+        };
+
+        let format = |values: &Option<Vec<i32>>| {
+            values.as_ref().map_or("*".to_string(), |values| {
+                CronUtils::format_cron_part(values)
+            })
+        };
+        let expression = format!(
+            "{} {} {} {} {}",
+            format(&minutes),
+            format(&hours),
+            format(&days_of_month),
+            format(&months),
+            format(&days_of_week)
+        );
+        Ok(match seconds {
+            Some(seconds) if seconds == [0] => expression,
+            seconds => format!("{} {}", format(&seconds), expression),
+        })
+    }
+}
+
+fn parse_numbers(name: &str, value: &str, min: i32, max: i32) -> Result<Vec<i32>, String> {
+    value
+        .split(',')
+        .map(|number| {
+            number
+                .parse()
+                .ok()
+                .filter(|n| (min..=max).contains(n))
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid RRULE {}: {}. Values should be between {} and {}.",
+                        name, number, min, max
+                    )
+                })
+        })
+        .collect()
+}
+
+fn parse_weekdays(rule: &str, value: &str) -> Result<Vec<i32>, String> {
+    value
+        .split(',')
+        .map(|day| {
+            if let Some(index) = WEEKDAY_CODES.iter().position(|code| *code == day) {
+                return Ok(index as i32);
+            }
+            if day.len() > 2 && WEEKDAY_CODES.contains(&&day[day.len() - 2..]) {
+                return Err(format!(
+                    "Cannot convert '{}' to cron: cron cannot pick the {} of a month or year.",
+                    rule, day
+                ));
+            }
+            Err(format!("Invalid RRULE weekday: {}", day))
+        })
+        .collect()
+}
+
+/// Reads `YYYYMMDDTHHMMSS`, with an optional `Z`, or a plain `YYYYMMDD`,
+/// which as an end covers the whole day
+fn parse_date_time(value: &str, end: bool) -> Result<NaiveDateTime, String> {
+    let value = value.trim().trim_end_matches(['Z', 'z']);
+    let invalid = || format!("Invalid RRULE date: {}", value);
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid())?;
+        let time = if end {
+            date.and_hms_opt(23, 59, 59)
+        } else {
+            date.and_hms_opt(0, 0, 0)
+        };
+        return time.ok_or_else(invalid);
+    }
+    NaiveDateTime::parse_from_str(&value.to_ascii_uppercase(), "%Y%m%dT%H%M%S")
+        .map_err(|_| invalid())
+}
@@ -0,0 +1,49 @@
+//! The days a runtime day schedule keeps, shared by the business day, month
+//! end and alternate week schedules so that each one also checks the others.
+use chrono::NaiveDate;
+
+use crate::alternate_weeks::AlternateWeeks;
+use crate::calendar::{BusinessDay, Calendar};
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::month_end;
+
+/// The days a builder limits its schedule to that cron cannot count: a
+/// business day, days before month end and alternate weeks
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct RuntimeDays {
+    pub(crate) business_day: Option<(BusinessDay, Calendar)>,
+    pub(crate) days_before_month_end: Option<u32>,
+    pub(crate) alternate_weeks: Option<AlternateWeeks>,
+}
+
+impl RuntimeDays {
+    pub(crate) fn from_builder(builder: &CronExpressionBuilder) -> Self {
+        Self {
+            business_day: builder
+                .business_day()
+                .map(|day| (day, builder.calendar().clone())),
+            days_before_month_end: builder.days_before_month_end(),
+            alternate_weeks: builder.alternate_weeks(),
+        }
+    }
+
+    /// Checks if the date is one of every kind of day that is set
+    pub(crate) fn matches(&self, date: &NaiveDate) -> bool {
+        self.business_day
+            .as_ref()
+            .is_none_or(|(day, calendar)| day.matches(date, calendar))
+            && self
+                .days_before_month_end
+                .is_none_or(|days| month_end::is_days_before_end(date, days))
+            && self.alternate_weeks.is_none_or(|weeks| weeks.matches(date))
+    }
+}
+
+/// The error of a runtime schedule built from a builder without its kind of
+/// day, e.g. "business day"
+pub(crate) fn missing_runtime_days(kind: &str) -> String {
+    format!(
+        "The schedule has no {}; use CronSchedule::from_builder.",
+        kind
+    )
+}
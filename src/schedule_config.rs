@@ -0,0 +1,292 @@
+//! A structured, human-friendly schedule format for config files.
+//!
+//! Each key maps onto one `CronExpressionBuilder` call:
+//!
+//! ```yaml
+//! report:
+//!   at: "08:00"
+//!   on_weekdays: [mon, tue, wed, thu, fri]
+//!   timezone: Europe/Berlin
+//! poll:
+//!   every: 15 minutes
+//!   between: 09:00-17:00
+//!   except: { months: [aug] }
+//! ```
+//!
+//! Errors name the offending key path, e.g. `report.on_weekdays[2]: ...`.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::CronSchedule;
+use crate::interfaces::CronTimeUnit;
+use crate::serde_support::{MONTH_NAMES, NamedValue, WEEKDAY_NAMES};
+use crate::time_of_day::TimeOfDay;
+
+/// One schedule as written in a config file
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduleConfig {
+    /// `minute`, `hour`, `day`, `week`, `month`, or an interval like `15 minutes`
+    every: Option<String>,
+    /// `HH:MM`
+    at: Option<String>,
+    /// `HH:00-HH:00`; the end hour is excluded
+    between: Option<String>,
+    on_weekdays: Option<Vec<NamedValue>>,
+    on_days_of_month: Option<Vec<i32>>,
+    during_months: Option<Vec<NamedValue>>,
+    /// IANA time zone name the schedule is meant for
+    timezone: Option<String>,
+    except: Option<ExceptConfig>,
+}
+
+/// Values removed from the schedule after everything else is applied
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExceptConfig {
+    hours: Option<Vec<i32>>,
+    weekdays: Option<Vec<NamedValue>>,
+    days_of_month: Option<Vec<i32>>,
+    months: Option<Vec<NamedValue>>,
+}
+
+/// A schedule loaded from config
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleDefinition {
+    pub builder: CronExpressionBuilder,
+    pub timezone: Option<String>,
+}
+
+/// Loads a single schedule from any serde data format
+pub fn load_schedule<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<ScheduleDefinition, String> {
+    let config: ScheduleConfig =
+        serde_path_to_error::deserialize(deserializer).map_err(|e| e.to_string())?;
+    config.to_definition("")
+}
+
+/// Loads a map of named schedules from any serde data format
+pub fn load_schedules<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, ScheduleDefinition>, String> {
+    let configs: BTreeMap<String, ScheduleConfig> =
+        serde_path_to_error::deserialize(deserializer).map_err(|e| e.to_string())?;
+    configs
+        .into_iter()
+        .map(|(name, config)| {
+            let definition = config.to_definition(&name)?;
+            Ok((name, definition))
+        })
+        .collect()
+}
+
+impl ScheduleConfig {
+    /// Applies the keys to a new builder, broadest first; `path` prefixes
+    /// the key names in error messages
+    pub fn to_definition(&self, path: &str) -> Result<ScheduleDefinition, String> {
+        let mut builder = CronExpressionBuilder::new();
+        if let Some(every) = &self.every {
+            builder = apply_every(builder, every).map_err(|e| key_error(path, "every", e))?;
+        }
+        if let Some(time) = &self.at {
+            if self.between.is_some() {
+                return Err(key_error(
+                    path,
+                    "at",
+                    "Cannot be combined with 'between'.".to_string(),
+                ));
+            }
+            builder = builder
+                .at_time(time)
+                .map_err(|e| key_error(path, "at", e))?;
+        }
+        if let Some(between) = &self.between {
+            let hours = parse_between(between).map_err(|e| key_error(path, "between", e))?;
+            builder = builder
+                .at_hours(hours)
+                .map_err(|e| key_error(path, "between", e))?;
+        }
+        if let Some(days) = &self.on_weekdays {
+            let days = resolve_all(days, &WEEKDAY_NAMES, 0, "weekday")
+                .map_err(|(i, e)| key_error(path, &format!("on_weekdays[{}]", i), e))?;
+            builder = builder
+                .on_week_days(days)
+                .map_err(|e| key_error(path, "on_weekdays", e))?;
+        }
+        if let Some(days) = &self.on_days_of_month {
+            builder = builder
+                .on_days_of_month(days.clone())
+                .map_err(|e| key_error(path, "on_days_of_month", e))?;
+        }
+        if let Some(months) = &self.during_months {
+            let months = resolve_all(months, &MONTH_NAMES, 1, "month")
+                .map_err(|(i, e)| key_error(path, &format!("during_months[{}]", i), e))?;
+            builder = builder
+                .during_months(months)
+                .map_err(|e| key_error(path, "during_months", e))?;
+        }
+        if let Some(except) = &self.except {
+            builder = apply_except(builder, except, path)?;
+        }
+        if let Some(zone) = &self.timezone {
+            validate_timezone(zone).map_err(|e| key_error(path, "timezone", e))?;
+        }
+
+        Ok(ScheduleDefinition {
+            builder,
+            timezone: self.timezone.clone(),
+        })
+    }
+}
+
+fn apply_every(
+    builder: CronExpressionBuilder,
+    every: &str,
+) -> Result<CronExpressionBuilder, String> {
+    let Some((interval, unit)) = every.trim().split_once(' ') else {
+        return builder.every(every.trim());
+    };
+    let interval = interval
+        .parse::<i32>()
+        .map_err(|_| format!("Invalid interval for 'every': {}", every))?;
+    let unit = match unit.trim().trim_end_matches('s') {
+        "minute" => CronTimeUnit::Minute,
+        "hour" => CronTimeUnit::Hour,
+        "day" => CronTimeUnit::DayOfMonth,
+        "month" => CronTimeUnit::Month,
+        _ => return Err(format!("Invalid time unit for 'every': {}", every)),
+    };
+    builder.every_x(interval, unit)
+}
+
+fn parse_between(between: &str) -> Result<Vec<i32>, String> {
+    let invalid = || format!("Invalid time range: {}", between);
+    let (from, to) = between.split_once('-').ok_or_else(invalid)?;
+    let hour = |time: &str| -> Result<i32, String> {
+        let time = time.trim();
+        match time.parse::<TimeOfDay>()? {
+            parsed if parsed.minute() == 0 && parsed.second() == 0 => Ok(parsed.hour() as i32),
+            _ => Err(format!("Only whole hours are supported, got {}", time)),
+        }
+    };
+    let (from, to) = (hour(from)?, hour(to)?);
+    if from >= to {
+        return Err(invalid());
+    }
+    Ok((from..to).collect())
+}
+
+fn resolve_all(
+    values: &[NamedValue],
+    names: &[&str],
+    first: i32,
+    kind: &str,
+) -> Result<Vec<i32>, (usize, String)> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v.resolve(names, first, kind).map_err(|e| (i, e)))
+        .collect()
+}
+
+fn apply_except(
+    mut builder: CronExpressionBuilder,
+    except: &ExceptConfig,
+    path: &str,
+) -> Result<CronExpressionBuilder, String> {
+    let schedule = CronSchedule::from_builder(&builder)?;
+    let remaining = |current: Vec<u32>, excluded: &[i32], key: &str| {
+        let remaining: Vec<i32> = current
+            .into_iter()
+            .map(|v| v as i32)
+            .filter(|v| !excluded.contains(v))
+            .collect();
+        if remaining.is_empty() {
+            Err(key_error(
+                path,
+                key,
+                "Excludes every value, so the schedule never runs.".to_string(),
+            ))
+        } else {
+            Ok(remaining)
+        }
+    };
+
+    if let Some(hours) = &except.hours {
+        builder = builder
+            .at_hours(remaining(schedule.hours(), hours, "except.hours")?)
+            .map_err(|e| key_error(path, "except.hours", e))?;
+    }
+    // Narrowing one day field while the other is set would make cron run on
+    // days matching either of them
+    let either_day = |key: &str| {
+        Err(key_error(
+            path,
+            key,
+            "Cannot narrow one day field while the other is set; cron would run on days matching either.".to_string(),
+        ))
+    };
+    if let Some(days) = &except.weekdays {
+        if schedule.is_day_of_month_restricted() {
+            return either_day("except.weekdays");
+        }
+        let days = resolve_all(days, &WEEKDAY_NAMES, 0, "weekday")
+            .map_err(|(i, e)| key_error(path, &format!("except.weekdays[{}]", i), e))?;
+        builder = builder
+            .on_week_days(remaining(
+                schedule.days_of_week(),
+                &days,
+                "except.weekdays",
+            )?)
+            .map_err(|e| key_error(path, "except.weekdays", e))?;
+    }
+    if let Some(days) = &except.days_of_month {
+        if schedule.is_day_of_week_restricted() || except.weekdays.is_some() {
+            return either_day("except.days_of_month");
+        }
+        builder = builder
+            .on_days_of_month(remaining(
+                schedule.days_of_month(),
+                days,
+                "except.days_of_month",
+            )?)
+            .map_err(|e| key_error(path, "except.days_of_month", e))?;
+    }
+    if let Some(months) = &except.months {
+        let months = resolve_all(months, &MONTH_NAMES, 1, "month")
+            .map_err(|(i, e)| key_error(path, &format!("except.months[{}]", i), e))?;
+        builder = builder
+            .during_months(remaining(schedule.months(), &months, "except.months")?)
+            .map_err(|e| key_error(path, "except.months", e))?;
+    }
+    Ok(builder)
+}
+
+/// Prefixes an error with the key path it belongs to, like serde_path_to_error
+fn key_error(path: &str, key: &str, message: String) -> String {
+    if path.is_empty() {
+        format!("{}: {}", key, message)
+    } else {
+        format!("{}.{}: {}", path, key, message)
+    }
+}
+
+#[cfg(feature = "tz")]
+fn validate_timezone(zone: &str) -> Result<(), String> {
+    zone.parse::<chrono_tz::Tz>()
+        .map(|_| ())
+        .map_err(|_| format!("Unknown time zone: {}", zone))
+}
+
+/// Without the `tz` feature there is no zone database to check against
+#[cfg(not(feature = "tz"))]
+fn validate_timezone(zone: &str) -> Result<(), String> {
+    if zone.trim().is_empty() {
+        Err("Unknown time zone: ''".to_string())
+    } else {
+        Ok(())
+    }
+}
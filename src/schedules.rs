@@ -1,12 +1,20 @@
-//! Predefined cron expression constants.
+//! Predefined cron schedule constants.
 //!
 //! These constants provide commonly used schedules for cron jobs,
 //! such as every minute, every hour, specific times of the day, weekdays only, etc.
+//! They are parsed at compile time, so they can be evaluated directly without
+//! allocating or parsing at runtime.
+//!
+//! Every constant is tagged with the [`CronDialect`] it is written in. The
+//! [`standard`] module holds five-field versions and [`with_seconds`] six-field
+//! versions with the seconds field set to `0`. Schedules that run more than once
+//! a minute only exist in [`with_seconds`]. The constants at the top level are
+//! the five-field ones, plus the sub-minute ones from [`with_seconds`].
 //!
 //! This module is intended for use with the `natural-cron` crate.
 //! Example usage:
 //! ```rust
-//! use natural_cron::schedules;
+//! use natural_cron::schedules::{self, CronDialect};
 //!
 //! fn main() {
 //!   println!(
@@ -14,89 +22,205 @@
 //!       schedules::EVERY_1ST_DAY_OF_MONTH_AT_MIDNIGHT
 //!   );
 //!   println!("Every 2 hours: {}", schedules::EVERY_2_HOURS);
+//!   assert!(schedules::EVERY_WEEKDAY.schedule().is_day_of_week_restricted());
+//!   assert_eq!(schedules::EVERY_SECOND.dialect(), CronDialect::WithSeconds);
+//!   assert_eq!(schedules::with_seconds::EVERY_HOUR.expression(), "0 0 * * * *");
 //! }
-//!
+//! ```
+use std::fmt;
+
+use crate::cron_schedule::CronSchedule;
+
+/// The cron format an expression is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CronDialect {
+    /// `minute hour day-of-month month day-of-week`
+    Standard,
+    /// A leading seconds field followed by the five standard fields
+    WithSeconds,
+}
+
+impl CronDialect {
+    /// Number of fields an expression in this dialect has
+    pub const fn field_count(&self) -> usize {
+        match self {
+            CronDialect::Standard => 5,
+            CronDialect::WithSeconds => 6,
+        }
+    }
+}
+
+/// A predefined schedule tagged with the dialect of its expression
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DialectSchedule {
+    dialect: CronDialect,
+    expression: &'static str,
+    schedule: CronSchedule,
+}
+
+impl DialectSchedule {
+    /// Parses the expression in a const context; panics when it is invalid
+    /// or does not have the dialect's number of fields
+    pub const fn new(dialect: CronDialect, expression: &'static str) -> Self {
+        let schedule = CronSchedule::parse_const(expression);
+        if schedule.has_seconds() != matches!(dialect, CronDialect::WithSeconds) {
+            panic!("Cron expression does not match its dialect's field count");
+        }
+        Self {
+            dialect,
+            expression,
+            schedule,
+        }
+    }
+
+    pub const fn dialect(&self) -> CronDialect {
+        self.dialect
+    }
+
+    /// The expression as written
+    pub const fn expression(&self) -> &'static str {
+        self.expression
+    }
+
+    /// The parsed schedule, ready to evaluate
+    pub const fn schedule(&self) -> &CronSchedule {
+        &self.schedule
+    }
+}
+
+impl fmt::Display for DialectSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expression)
+    }
+}
+
+/// Declares the [`standard`] and [`with_seconds`] modules from one list of
+/// five-field expressions; the six-field versions get a `0` seconds field
+macro_rules! dialect_schedules {
+    (
+        seconds_only: { $($seconds_name:ident = $seconds_expression:literal,)* }
+        both: { $($name:ident = $expression:literal,)* }
+    ) => {
+        /// Five-field schedules
+        pub mod standard {
+            use super::{CronDialect, DialectSchedule};
+
+            $(pub const $name: DialectSchedule =
+                DialectSchedule::new(CronDialect::Standard, $expression);)*
+
+            /// Every constant in this module with its name
+            pub const ALL: &[(&str, DialectSchedule)] = &[$((stringify!($name), $name)),*];
+        }
+
+        /// Six-field schedules with a leading seconds field
+        pub mod with_seconds {
+            use super::{CronDialect, DialectSchedule};
+
+            $(pub const $seconds_name: DialectSchedule =
+                DialectSchedule::new(CronDialect::WithSeconds, $seconds_expression);)*
+            $(pub const $name: DialectSchedule =
+                DialectSchedule::new(CronDialect::WithSeconds, concat!("0 ", $expression));)*
+
+            /// Every constant in this module with its name
+            pub const ALL: &[(&str, DialectSchedule)] = &[
+                $((stringify!($seconds_name), $seconds_name),)*
+                $((stringify!($name), $name)),*
+            ];
+        }
+
+        pub use standard::{$($name),*};
+        pub use with_seconds::{$($seconds_name),*};
+    };
+}
 
-pub const EVERY_SECOND: &str = "* * * * * *";
-pub const EVERY_5_SECONDS: &str = "*/5 * * * * *";
-pub const EVERY_10_SECONDS: &str = "*/10 * * * * *";
-pub const EVERY_30_SECONDS: &str = "*/30 * * * * *";
-pub const EVERY_MINUTE: &str = "*/1 * * * *";
-pub const EVERY_5_MINUTES: &str = "0 */5 * * * *";
-pub const EVERY_10_MINUTES: &str = "0 */10 * * * *";
-pub const EVERY_30_MINUTES: &str = "0 */30 * * * *";
-pub const EVERY_HOUR: &str = "0 0-23/1 * * *";
-pub const EVERY_2_HOURS: &str = "0 0-23/2 * * *";
-pub const EVERY_3_HOURS: &str = "0 0-23/3 * * *";
-pub const EVERY_4_HOURS: &str = "0 0-23/4 * * *";
-pub const EVERY_5_HOURS: &str = "0 0-23/5 * * *";
-pub const EVERY_6_HOURS: &str = "0 0-23/6 * * *";
-pub const EVERY_7_HOURS: &str = "0 0-23/7 * * *";
-pub const EVERY_8_HOURS: &str = "0 0-23/8 * * *";
-pub const EVERY_9_HOURS: &str = "0 0-23/9 * * *";
-pub const EVERY_10_HOURS: &str = "0 0-23/10 * * *";
-pub const EVERY_11_HOURS: &str = "0 0-23/11 * * *";
-pub const EVERY_12_HOURS: &str = "0 0-23/12 * * *";
-pub const EVERY_DAY_AT_1AM: &str = "0 01 * * *";
-pub const EVERY_DAY_AT_2AM: &str = "0 02 * * *";
-pub const EVERY_DAY_AT_3AM: &str = "0 03 * * *";
-pub const EVERY_DAY_AT_4AM: &str = "0 04 * * *";
-pub const EVERY_DAY_AT_5AM: &str = "0 05 * * *";
-pub const EVERY_DAY_AT_6AM: &str = "0 06 * * *";
-pub const EVERY_DAY_AT_7AM: &str = "0 07 * * *";
-pub const EVERY_DAY_AT_8AM: &str = "0 08 * * *";
-pub const EVERY_DAY_AT_9AM: &str = "0 09 * * *";
-pub const EVERY_DAY_AT_10AM: &str = "0 10 * * *";
-pub const EVERY_DAY_AT_11AM: &str = "0 11 * * *";
-pub const EVERY_DAY_AT_NOON: &str = "0 12 * * *";
-pub const EVERY_DAY_AT_1PM: &str = "0 13 * * *";
-pub const EVERY_DAY_AT_2PM: &str = "0 14 * * *";
-pub const EVERY_DAY_AT_3PM: &str = "0 15 * * *";
-pub const EVERY_DAY_AT_4PM: &str = "0 16 * * *";
-pub const EVERY_DAY_AT_5PM: &str = "0 17 * * *";
-pub const EVERY_DAY_AT_6PM: &str = "0 18 * * *";
-pub const EVERY_DAY_AT_7PM: &str = "0 19 * * *";
-pub const EVERY_DAY_AT_8PM: &str = "0 20 * * *";
-pub const EVERY_DAY_AT_9PM: &str = "0 21 * * *";
-pub const EVERY_DAY_AT_10PM: &str = "0 22 * * *";
-pub const EVERY_DAY_AT_11PM: &str = "0 23 * * *";
-pub const EVERY_DAY_AT_MIDNIGHT: &str = "0 0 * * *";
-pub const EVERY_WEEK: &str = "0 0 * * 0";
-pub const EVERY_WEEKDAY: &str = "0 0 * * 1-5";
-pub const EVERY_WEEKEND: &str = "0 0 * * 6,0";
-pub const EVERY_1ST_DAY_OF_MONTH_AT_MIDNIGHT: &str = "0 0 1 * *";
-pub const EVERY_1ST_DAY_OF_MONTH_AT_NOON: &str = "0 12 1 * *";
-pub const EVERY_2ND_HOUR: &str = "0 */2 * * *";
-pub const EVERY_2ND_HOUR_FROM_1AM_THROUGH_11PM: &str = "0 1-23/2 * * *";
-pub const EVERY_2ND_MONTH: &str = "0 0 1 */2 *";
-pub const EVERY_QUARTER: &str = "0 0 1 */3 *";
-pub const EVERY_6_MONTHS: &str = "0 0 1 */6 *";
-pub const EVERY_YEAR: &str = "0 0 1 1 *";
-pub const EVERY_30_MINUTES_BETWEEN_9AM_AND_5PM: &str = "0 */30 9-17 * * *";
-pub const EVERY_30_MINUTES_BETWEEN_9AM_AND_6PM: &str = "0 */30 9-18 * * *";
-pub const EVERY_30_MINUTES_BETWEEN_10AM_AND_7PM: &str = "0 */30 10-19 * * *";
-pub const MONDAY_TO_FRIDAY_AT_1AM: &str = "0 0 01 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_2AM: &str = "0 0 02 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_3AM: &str = "0 0 03 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_4AM: &str = "0 0 04 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_5AM: &str = "0 0 05 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_6AM: &str = "0 0 06 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_7AM: &str = "0 0 07 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_8AM: &str = "0 0 08 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_9AM: &str = "0 0 09 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_09_30AM: &str = "0 30 09 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_10AM: &str = "0 0 10 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_11AM: &str = "0 0 11 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_11_30AM: &str = "0 30 11 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_12PM: &str = "0 0 12 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_1PM: &str = "0 0 13 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_2PM: &str = "0 0 14 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_3PM: &str = "0 0 15 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_4PM: &str = "0 0 16 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_5PM: &str = "0 0 17 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_6PM: &str = "0 0 18 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_7PM: &str = "0 0 19 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_8PM: &str = "0 0 20 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_9PM: &str = "0 0 21 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_10PM: &str = "0 0 22 * * 1-5";
-pub const MONDAY_TO_FRIDAY_AT_11PM: &str = "0 0 23 * * 1-5";
+dialect_schedules! {
+    seconds_only: {
+        EVERY_SECOND = "* * * * * *",
+        EVERY_5_SECONDS = "*/5 * * * * *",
+        EVERY_10_SECONDS = "*/10 * * * * *",
+        EVERY_30_SECONDS = "*/30 * * * * *",
+    }
+    both: {
+        EVERY_MINUTE = "* * * * *",
+        EVERY_5_MINUTES = "*/5 * * * *",
+        EVERY_10_MINUTES = "*/10 * * * *",
+        EVERY_30_MINUTES = "*/30 * * * *",
+        EVERY_HOUR = "0 * * * *",
+        EVERY_2_HOURS = "0 */2 * * *",
+        EVERY_3_HOURS = "0 */3 * * *",
+        EVERY_4_HOURS = "0 */4 * * *",
+        // Plain cron steps that restart at midnight, so the gap before it is
+        // shorter; the linter warns about them
+        EVERY_5_HOURS = "0 */5 * * *",
+        EVERY_6_HOURS = "0 */6 * * *",
+        EVERY_7_HOURS = "0 */7 * * *",
+        EVERY_8_HOURS = "0 */8 * * *",
+        EVERY_9_HOURS = "0 */9 * * *",
+        EVERY_10_HOURS = "0 */10 * * *",
+        EVERY_11_HOURS = "0 */11 * * *",
+        EVERY_12_HOURS = "0 */12 * * *",
+        EVERY_DAY_AT_1AM = "0 1 * * *",
+        EVERY_DAY_AT_2AM = "0 2 * * *",
+        EVERY_DAY_AT_3AM = "0 3 * * *",
+        EVERY_DAY_AT_4AM = "0 4 * * *",
+        EVERY_DAY_AT_5AM = "0 5 * * *",
+        EVERY_DAY_AT_6AM = "0 6 * * *",
+        EVERY_DAY_AT_7AM = "0 7 * * *",
+        EVERY_DAY_AT_8AM = "0 8 * * *",
+        EVERY_DAY_AT_9AM = "0 9 * * *",
+        EVERY_DAY_AT_10AM = "0 10 * * *",
+        EVERY_DAY_AT_11AM = "0 11 * * *",
+        EVERY_DAY_AT_NOON = "0 12 * * *",
+        EVERY_DAY_AT_1PM = "0 13 * * *",
+        EVERY_DAY_AT_2PM = "0 14 * * *",
+        EVERY_DAY_AT_3PM = "0 15 * * *",
+        EVERY_DAY_AT_4PM = "0 16 * * *",
+        EVERY_DAY_AT_5PM = "0 17 * * *",
+        EVERY_DAY_AT_6PM = "0 18 * * *",
+        EVERY_DAY_AT_7PM = "0 19 * * *",
+        EVERY_DAY_AT_8PM = "0 20 * * *",
+        EVERY_DAY_AT_9PM = "0 21 * * *",
+        EVERY_DAY_AT_10PM = "0 22 * * *",
+        EVERY_DAY_AT_11PM = "0 23 * * *",
+        EVERY_DAY_AT_MIDNIGHT = "0 0 * * *",
+        EVERY_WEEK = "0 0 * * 0",
+        EVERY_WEEKDAY = "0 0 * * 1-5",
+        EVERY_WEEKEND = "0 0 * * 6,0",
+        EVERY_1ST_DAY_OF_MONTH_AT_MIDNIGHT = "0 0 1 * *",
+        EVERY_1ST_DAY_OF_MONTH_AT_NOON = "0 12 1 * *",
+        EVERY_2ND_HOUR = "0 */2 * * *",
+        EVERY_2ND_HOUR_FROM_1AM_THROUGH_11PM = "0 1-23/2 * * *",
+        EVERY_2ND_MONTH = "0 0 1 */2 *",
+        EVERY_QUARTER = "0 0 1 */3 *",
+        EVERY_6_MONTHS = "0 0 1 */6 *",
+        EVERY_YEAR = "0 0 1 1 *",
+        EVERY_30_MINUTES_FROM_9AM_TO_5_30PM = "*/30 9-17 * * *",
+        EVERY_30_MINUTES_FROM_9AM_TO_6_30PM = "*/30 9-18 * * *",
+        EVERY_30_MINUTES_FROM_10AM_TO_7_30PM = "*/30 10-19 * * *",
+        MONDAY_TO_FRIDAY_AT_1AM = "0 1 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_2AM = "0 2 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_3AM = "0 3 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_4AM = "0 4 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_5AM = "0 5 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_6AM = "0 6 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_7AM = "0 7 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_8AM = "0 8 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_9AM = "0 9 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_09_30AM = "30 9 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_10AM = "0 10 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_11AM = "0 11 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_11_30AM = "30 11 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_12PM = "0 12 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_1PM = "0 13 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_2PM = "0 14 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_3PM = "0 15 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_4PM = "0 16 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_5PM = "0 17 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_6PM = "0 18 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_7PM = "0 19 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_8PM = "0 20 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_9PM = "0 21 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_10PM = "0 22 * * 1-5",
+        MONDAY_TO_FRIDAY_AT_11PM = "0 23 * * 1-5",
+    }
+}
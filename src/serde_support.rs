@@ -0,0 +1,473 @@
+//! `Serialize`/`Deserialize` for builders and parsed schedules, behind the
+//! `serde` feature.
+//!
+//! Both types serialize as a compact cron string; a builder whose times need
+//! several lines serializes as a list of them, and one with a runtime filter
+//! such as a business day as a structured object such as
+//! `{ "at": "08:00", "weekdays": ["mon", "fri"] }`. [`structured`] always
+//! writes the object. The object keeps steps such as `"minutes": "*/5"`;
+//! any other field text, such as a range with a step, is written as the
+//! values it selects. A builder deserializes from any of these forms; a
+//! schedule only from a cron string. Everything is validated on the way in.
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::alternate_weeks::AlternateWeeks;
+use crate::blackout::Blackout;
+use crate::calendar::{BusinessDay, Calendar};
+use crate::cron_expression_builder::CronExpressionBuilder;
+use crate::cron_schedule::CronSchedule;
+use crate::interfaces::CronTimeUnit;
+
+pub(crate) const WEEKDAY_NAMES: [&str; 7] = [
+    "sunday",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+];
+pub(crate) const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// A builder described field by field; each key maps onto one builder call
+#[derive(Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StructuredSchedule {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    every: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    every_x: Option<EveryX>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    at: Option<Times>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minutes: Option<FieldValues<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hours: Option<FieldValues<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seconds: Option<Vec<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    days_of_month: Option<FieldValues<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weekdays: Option<FieldValues<NamedValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    months: Option<FieldValues<NamedValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    except: Option<Vec<BlackoutSpec>>,
+    /// A number, or `last`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    business_day: Option<NamedValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    days_before_month_end: Option<i32>,
+    /// `odd`, `even` or the date of an anchor week
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alternate_weeks: Option<String>,
+    /// Rules as read by `Calendar::parse`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    calendar: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EveryX {
+    interval: i32,
+    unit: CronTimeUnit,
+}
+
+/// The values of a field, or a step such as `*/5`
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum FieldValues<T> {
+    Values(Vec<T>),
+    Step(String),
+}
+
+impl<T> FieldValues<T> {
+    fn new(values: Option<Vec<T>>, step: Option<i32>) -> Option<Self> {
+        match step {
+            Some(interval) => Some(FieldValues::Step(format!("*/{}", interval))),
+            None => values.map(FieldValues::Values),
+        }
+    }
+}
+
+fn parse_step(step: &str) -> Result<i32, String> {
+    step.strip_prefix("*/")
+        .and_then(|interval| interval.parse().ok())
+        .ok_or_else(|| format!("Invalid step: '{}'. Expected '*/n'.", step))
+}
+
+/// One time of day, or several for `at_times`
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Times {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// A blackout, tagged with its kind
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum BlackoutSpec {
+    Schedule(String),
+    TimeWindow { start: String, end: String },
+    DateRange { start: String, end: String },
+}
+
+impl BlackoutSpec {
+    fn from_blackout(blackout: &Blackout) -> Self {
+        match blackout {
+            Blackout::Schedule(schedule) => BlackoutSpec::Schedule(schedule.to_string()),
+            Blackout::TimeWindow { start, end } => BlackoutSpec::TimeWindow {
+                start: start.format("%H:%M").to_string(),
+                end: end.format("%H:%M").to_string(),
+            },
+            Blackout::DateRange { start, end } => BlackoutSpec::DateRange {
+                start: start.to_string(),
+                end: end.to_string(),
+            },
+        }
+    }
+
+    fn into_blackout(self) -> Result<Blackout, String> {
+        match self {
+            BlackoutSpec::Schedule(expression) => Blackout::schedule(&expression),
+            BlackoutSpec::TimeWindow { start, end } => Blackout::time_window(&start, &end),
+            BlackoutSpec::DateRange { start, end } => {
+                Blackout::date_range(parse_date(&start)?, parse_date(&end)?)
+            }
+        }
+    }
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("Invalid date: {}", date))
+}
+
+/// A weekday or month given as a number or as a (possibly abbreviated) name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum NamedValue {
+    Number(i32),
+    Name(String),
+}
+
+impl NamedValue {
+    pub(crate) fn resolve(&self, names: &[&str], first: i32, kind: &str) -> Result<i32, String> {
+        match self {
+            NamedValue::Number(n) => Ok(*n),
+            NamedValue::Name(name) => {
+                let lower = name.to_ascii_lowercase();
+                names
+                    .iter()
+                    .position(|n| *n == lower || (lower.len() == 3 && n.starts_with(&lower)))
+                    .map(|index| index as i32 + first)
+                    .ok_or_else(|| format!("Invalid {} name: {}", kind, name))
+            }
+        }
+    }
+}
+
+impl StructuredSchedule {
+    /// Describes every field and runtime filter of a builder, including the
+    /// ones no cron expression can hold
+    fn from_builder(builder: &CronExpressionBuilder) -> Self {
+        let [seconds, minutes, hours, days_of_month, months, weekdays] =
+            builder.restricted_fields();
+        let signed = |values: Vec<u32>| values.into_iter().map(|v| v as i32).collect();
+        let named = |values: Vec<u32>, names: &[&str], first: u32| {
+            values
+                .into_iter()
+                .map(|v| {
+                    NamedValue::Name(names[((v - first) as usize) % names.len()][..3].to_string())
+                })
+                .collect()
+        };
+        let time = |hour: u32, minute: u32| format!("{:02}:{:02}", hour, minute);
+        let [minute_step, hour_step, day_step, month_step, weekday_step] = builder.steps();
+
+        let mut structured = Self::default();
+        let groups = builder.time_groups();
+        if !groups.is_empty() {
+            let mut times: Vec<(u32, u32)> = groups
+                .iter()
+                .flat_map(|(minutes, hours)| {
+                    hours
+                        .iter()
+                        .flat_map(move |hour| minutes.iter().map(move |minute| (*hour, *minute)))
+                })
+                .collect();
+            times.sort();
+            structured.at = Some(Times::Many(
+                times.into_iter().map(|(h, m)| time(h, m)).collect(),
+            ));
+        } else {
+            match (minutes, hours) {
+                (Some(minutes), Some(hours))
+                    if minutes.len() == 1
+                        && hours.len() == 1
+                        && minute_step.is_none()
+                        && hour_step.is_none() =>
+                {
+                    structured.at = Some(Times::One(time(hours[0], minutes[0])));
+                }
+                (minutes, hours) => {
+                    // `hours` alone would also set the minute to 0; a step
+                    // leaves it
+                    if minutes.is_none() && hours.is_some() && hour_step.is_none() {
+                        structured.every = Some("minute".to_string());
+                    }
+                    structured.minutes = FieldValues::new(minutes.map(signed), minute_step);
+                    structured.hours = FieldValues::new(hours.map(signed), hour_step);
+                }
+            }
+        }
+        structured.seconds = seconds.map(signed);
+        structured.days_of_month = FieldValues::new(days_of_month.map(signed), day_step);
+        structured.weekdays = FieldValues::new(
+            weekdays.map(|days| named(days, &WEEKDAY_NAMES, 0)),
+            weekday_step,
+        );
+        structured.months = FieldValues::new(
+            months.map(|months| named(months, &MONTH_NAMES, 1)),
+            month_step,
+        );
+
+        if !builder.blackouts().is_empty() {
+            structured.except = Some(
+                builder
+                    .blackouts()
+                    .iter()
+                    .map(BlackoutSpec::from_blackout)
+                    .collect(),
+            );
+        }
+        structured.business_day = builder.business_day().map(|day| match day {
+            BusinessDay::Nth(n) => NamedValue::Number(n as i32),
+            BusinessDay::Last => NamedValue::Name("last".to_string()),
+        });
+        structured.days_before_month_end = builder.days_before_month_end().map(|days| days as i32);
+        structured.alternate_weeks = builder.alternate_weeks().map(|weeks| match weeks {
+            AlternateWeeks::Odd => "odd".to_string(),
+            AlternateWeeks::Even => "even".to_string(),
+            AlternateWeeks::From(anchor) => anchor.to_string(),
+        });
+        if *builder.calendar() != Calendar::default() {
+            structured.calendar = Some(builder.calendar().to_string());
+        }
+        structured
+    }
+
+    /// Applies the fields in a fixed order, broadest first, so that
+    /// `every` does not overwrite a more specific `at`
+    fn into_builder(self) -> Result<CronExpressionBuilder, String> {
+        let mut builder = CronExpressionBuilder::new();
+        if let Some(unit) = self.every {
+            builder = builder.every(&unit)?;
+        }
+        if let Some(every_x) = self.every_x {
+            builder = builder.every_x(every_x.interval, every_x.unit)?;
+        }
+        match self.at {
+            Some(Times::One(time)) => builder = builder.at_time(&time)?,
+            Some(Times::Many(times)) => builder = builder.at_times(times)?,
+            None => {}
+        }
+        builder = match self.hours {
+            Some(FieldValues::Values(hours)) => builder.at_hours(hours)?,
+            Some(FieldValues::Step(step)) => {
+                builder.with_step(parse_step(&step)?, CronTimeUnit::Hour)?
+            }
+            None => builder,
+        };
+        builder = match self.minutes {
+            Some(FieldValues::Values(minutes)) => builder.at_minutes(minutes)?,
+            Some(FieldValues::Step(step)) => {
+                builder.with_step(parse_step(&step)?, CronTimeUnit::Minute)?
+            }
+            None => builder,
+        };
+        if let Some(seconds) = self.seconds {
+            builder = builder.at_seconds(seconds)?;
+        }
+        builder = match self.days_of_month {
+            Some(FieldValues::Values(days)) => builder.on_days_of_month(days)?,
+            Some(FieldValues::Step(step)) => {
+                builder.with_step(parse_step(&step)?, CronTimeUnit::DayOfMonth)?
+            }
+            None => builder,
+        };
+        builder = match self.weekdays {
+            Some(FieldValues::Values(days)) => {
+                let days = days
+                    .iter()
+                    .map(|d| d.resolve(&WEEKDAY_NAMES, 0, "weekday"))
+                    .collect::<Result<_, _>>()?;
+                builder.on_week_days(days)?
+            }
+            Some(FieldValues::Step(step)) => {
+                builder.with_step(parse_step(&step)?, CronTimeUnit::DayOfWeek)?
+            }
+            None => builder,
+        };
+        builder = match self.months {
+            Some(FieldValues::Values(months)) => {
+                let months = months
+                    .iter()
+                    .map(|m| m.resolve(&MONTH_NAMES, 1, "month"))
+                    .collect::<Result<_, _>>()?;
+                builder.during_months(months)?
+            }
+            Some(FieldValues::Step(step)) => {
+                builder.with_step(parse_step(&step)?, CronTimeUnit::Month)?
+            }
+            None => builder,
+        };
+        for blackout in self.except.unwrap_or_default() {
+            builder = builder.except(blackout.into_blackout()?)?;
+        }
+        match self.business_day {
+            Some(NamedValue::Number(day)) => builder = builder.on_business_day(day)?,
+            Some(NamedValue::Name(name)) if name.eq_ignore_ascii_case("last") => {
+                builder = builder.on_last_business_day()?
+            }
+            Some(NamedValue::Name(name)) => {
+                return Err(format!("Invalid business day: {}", name));
+            }
+            None => {}
+        }
+        if let Some(days) = self.days_before_month_end {
+            builder = builder.on_days_before_month_end(days)?;
+        }
+        builder = match self.alternate_weeks.as_deref() {
+            Some("odd") => builder.on_odd_weeks()?,
+            Some("even") => builder.on_even_weeks()?,
+            Some(anchor) => builder.every_other_week(parse_date(anchor)?)?,
+            None => builder,
+        };
+        if let Some(calendar) = self.calendar {
+            builder = builder.with_calendar(Calendar::parse(&calendar)?)?;
+        }
+        Ok(builder)
+    }
+}
+
+/// A single line as a string, times needing several lines as a list of them,
+/// and a builder with runtime filters as a structured object
+impl Serialize for CronExpressionBuilder {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.clone().compile_lines() {
+            Ok(mut lines) if lines.len() == 1 => serializer.serialize_str(&lines.remove(0)),
+            Ok(lines) => serializer.collect_seq(lines),
+            Err(_) => StructuredSchedule::from_builder(self).serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CronExpressionBuilder {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BuilderVisitor;
+
+        impl<'de> Visitor<'de> for BuilderVisitor {
+            type Value = CronExpressionBuilder;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a cron expression, a list of them or a schedule object")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                CronExpressionBuilder::from_expression(value).map_err(E::custom)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut lines: Vec<String> = Vec::new();
+                while let Some(line) = seq.next_element()? {
+                    lines.push(line);
+                }
+                let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+                CronExpressionBuilder::from_lines(&lines).map_err(de::Error::custom)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                StructuredSchedule::deserialize(MapAccessDeserializer::new(map))?
+                    .into_builder()
+                    .map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_any(BuilderVisitor)
+    }
+}
+
+impl Serialize for CronSchedule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CronSchedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let expression = String::deserialize(deserializer)?;
+        CronSchedule::parse(&expression).map_err(de::Error::custom)
+    }
+}
+
+/// Always writes a builder as a structured object, for
+/// `#[serde(with = "natural_cron::serde_support::structured")]`:
+///
+/// ```rust
+/// use natural_cron::CronExpressionBuilder;
+///
+/// #[derive(serde::Serialize)]
+/// struct Job {
+///     #[serde(with = "natural_cron::serde_support::structured")]
+///     schedule: CronExpressionBuilder,
+/// }
+///
+/// let schedule = CronExpressionBuilder::new()
+///     .at_time("08:00")
+///     .and_then(|s| s.on_week_days(vec![1, 5]))
+///     .unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&Job { schedule }).unwrap(),
+///     r#"{"schedule":{"at":"08:00","weekdays":["mon","fri"]}}"#
+/// );
+/// ```
+///
+/// Reading accepts everything the builder's own `Deserialize` does.
+pub mod structured {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::StructuredSchedule;
+    use crate::cron_expression_builder::CronExpressionBuilder;
+
+    pub fn serialize<S: Serializer>(
+        builder: &CronExpressionBuilder,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        StructuredSchedule::from_builder(builder).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CronExpressionBuilder, D::Error> {
+        CronExpressionBuilder::deserialize(deserializer)
+    }
+}
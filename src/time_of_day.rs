@@ -0,0 +1,170 @@
+//! Times of day, as given to [`CronExpressionBuilder::at_time`].
+//!
+//! Besides `HH:MM`, times can carry seconds (`08:30:15`), use a 12-hour
+//! clock (`8:30pm`, `12 am`) or be written as `noon` or `midnight`:
+//!
+//! ```rust
+//! use natural_cron::CronExpressionBuilder;
+//! use natural_cron::time_of_day::TimeOfDay;
+//!
+//! let time: TimeOfDay = "8:30pm".parse().unwrap();
+//! assert_eq!(time, TimeOfDay::new(20, 30, 0).unwrap());
+//! assert_eq!(CronExpressionBuilder::new().at_time(time).unwrap().compile(), "30 20 * * *");
+//!
+//! // Seconds need a six-field expression
+//! let builder = CronExpressionBuilder::new().with_seconds().at_time("08:30:15").unwrap();
+//! assert_eq!(builder.compile(), "15 30 8 * * *");
+//! ```
+//!
+//! [`CronExpressionBuilder::at_time`]: crate::CronExpressionBuilder::at_time
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{NaiveTime, Timelike};
+
+use crate::cron_validator::CronValidators;
+
+/// An hour, minute and second of the day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeOfDay {
+    hour: u32,
+    minute: u32,
+    second: u32,
+}
+
+impl TimeOfDay {
+    pub const MIDNIGHT: TimeOfDay = TimeOfDay {
+        hour: 0,
+        minute: 0,
+        second: 0,
+    };
+    pub const NOON: TimeOfDay = TimeOfDay {
+        hour: 12,
+        minute: 0,
+        second: 0,
+    };
+
+    /// Creates a time on the 24-hour clock
+    pub fn new(hour: u32, minute: u32, second: u32) -> Result<Self, String> {
+        CronValidators::validate_hour(hour as i32)?;
+        CronValidators::validate_minute(minute as i32)?;
+        CronValidators::validate_second(second as i32)?;
+        Ok(Self {
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    pub fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u32 {
+        self.second
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = String;
+
+    /// Parses `H:MM`, `H:MM:SS`, a 12-hour time such as `8pm` or `8:30 am`,
+    /// `noon` or `midnight`, ignoring case
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid time format for 'at': {}", text);
+        let lower = text.trim().to_ascii_lowercase();
+        match lower.as_str() {
+            "noon" => return Ok(Self::NOON),
+            "midnight" => return Ok(Self::MIDNIGHT),
+            _ => {}
+        }
+
+        let (clock, pm) = match lower.strip_suffix("am").or(lower.strip_suffix("pm")) {
+            Some(clock) => (Some(clock.trim_end()), Some(lower.ends_with("pm"))),
+            None => (None, None),
+        };
+        let clock = clock.unwrap_or(&lower);
+        let mut parts = clock.split(':');
+        let mut number = |required: bool| -> Result<Option<u32>, String> {
+            match parts.next() {
+                Some(part)
+                    if (1..=2).contains(&part.len())
+                        && part.bytes().all(|b| b.is_ascii_digit()) =>
+                {
+                    part.parse().map(Some).map_err(|_| invalid())
+                }
+                None if !required => Ok(None),
+                _ => Err(invalid()),
+            }
+        };
+        let hour = number(true)?.ok_or_else(invalid)?;
+        // A bare hour is only unambiguous on the 12-hour clock
+        let minute = number(pm.is_none())?;
+        let second = minute.map(|_| number(false)).transpose()?.flatten();
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        let hour = match pm {
+            None => hour,
+            Some(_) if !(1..=12).contains(&hour) => return Err(invalid()),
+            Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+        };
+        Self::new(hour, minute.unwrap_or(0), second.unwrap_or(0)).map_err(|_| invalid())
+    }
+}
+
+impl TryFrom<&str> for TimeOfDay {
+    type Error = String;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl TryFrom<&String> for TimeOfDay {
+    type Error = String;
+
+    fn try_from(text: &String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl TryFrom<String> for TimeOfDay {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+/// Drops any fraction of a second
+impl From<NaiveTime> for TimeOfDay {
+    fn from(time: NaiveTime) -> Self {
+        Self {
+            hour: time.hour(),
+            minute: time.minute(),
+            second: time.second(),
+        }
+    }
+}
+
+impl From<TimeOfDay> for NaiveTime {
+    fn from(time: TimeOfDay) -> Self {
+        NaiveTime::from_hms_opt(time.hour, time.minute, time.second).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)?;
+        if self.second != 0 {
+            write!(f, ":{:02}", self.second)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+//! Translation of schedules kept in a local time zone into UTC cron lines.
+//!
+//! CI systems such as GitHub Actions only run cron in UTC. The translator
+//! moves every run of a local schedule to UTC, shifting the days of the week
+//! and month of runs that cross midnight. Offsets of 30 or 45 minutes can
+//! split the runs over several lines:
+//!
+//! ```rust
+//! use chrono::FixedOffset;
+//! use natural_cron::CronExpressionBuilder;
+//! use natural_cron::utc::UtcTranslator;
+//!
+//! // 09:00 on weekdays in India, at UTC+05:30
+//! let india = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+//! let builder = CronExpressionBuilder::new()
+//!     .at_time("09:00")
+//!     .unwrap()
+//!     .on_week_days(vec![1, 2, 3, 4, 5])
+//!     .unwrap();
+//! assert_eq!(
+//!     UtcTranslator::translate_builder(&builder, &india, 2026),
+//!     Ok(vec!["30 3 * * 1-5".to_string()])
+//! );
+//! ```
+//!
+//! A zone whose offset changes for daylight saving during the runs of a
+//! year has no static UTC equivalent, and the translation fails.
+use chrono::{Datelike, FixedOffset, LocalResult, NaiveDate, NaiveTime, Offset, TimeZone};
+
+use crate::cron_expression_builder::{CronExpressionBuilder, group_times};
+use crate::cron_lint::offset_changes;
+use crate::cron_schedule::{CronSchedule, days_in_month};
+use crate::cron_utils::CronUtils;
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+pub struct UtcTranslator;
+
+impl UtcTranslator {
+    /// Translates a five- or six-field expression evaluated on the wall
+    /// clock of `zone` into UTC expressions that together run at the same
+    /// instants throughout `year`
+    pub fn translate<Tz: TimeZone>(
+        expression: &str,
+        zone: &Tz,
+        year: i32,
+    ) -> Result<Vec<String>, String> {
+        let schedule = CronSchedule::parse(expression)?;
+        let offset = fixed_offset(expression, &schedule, zone, year)?;
+        if offset.local_minus_utc() % 60 != 0 {
+            return Err(format!(
+                "Cannot translate '{}' to UTC: the offset {} is not a whole number of minutes.",
+                expression, offset
+            ));
+        }
+        let offset = i64::from(offset.local_minus_utc() / 60);
+
+        // UTC times of day, grouped by the day fields they end up on
+        let mut groups: Vec<(String, Vec<(u32, u32)>)> = Vec::new();
+        for hour in schedule.hours() {
+            for minute in schedule.minutes() {
+                let utc = i64::from(hour * 60 + minute) - offset;
+                let days = shift_days(expression, &schedule, utc.div_euclid(MINUTES_PER_DAY))?;
+                let time = utc.rem_euclid(MINUTES_PER_DAY) as u32;
+                let time = (time / 60, time % 60);
+                match groups.iter_mut().find(|(fields, _)| *fields == days) {
+                    Some((_, times)) => times.push(time),
+                    None => groups.push((days, vec![time])),
+                }
+            }
+        }
+
+        let seconds = expression
+            .split_whitespace()
+            .next()
+            .filter(|_| schedule.has_seconds());
+        let mut lines = Vec::new();
+        for (days, times) in groups {
+            for (minutes, hours) in group_times(&times) {
+                let line = format!(
+                    "{} {} {}",
+                    format_bits(minutes, 0, 59),
+                    format_bits(hours, 0, 23),
+                    days
+                );
+                lines.push(match seconds {
+                    Some(seconds) => format!("{} {}", seconds, line),
+                    None => line,
+                });
+            }
+        }
+        Ok(lines)
+    }
+
+    /// Translates every line the builder compiles to, failing like
+    /// [`CronExpressionBuilder::compile_lines`] for schedules cron cannot
+    /// express
+    pub fn translate_builder<Tz: TimeZone>(
+        builder: &CronExpressionBuilder,
+        zone: &Tz,
+        year: i32,
+    ) -> Result<Vec<String>, String> {
+        let mut lines = Vec::new();
+        for line in builder.clone().compile_lines()? {
+            lines.extend(Self::translate(&line, zone, year)?);
+        }
+        Ok(lines)
+    }
+}
+
+/// The single offset the zone has at every run in the year
+fn fixed_offset<Tz: TimeZone>(
+    expression: &str,
+    schedule: &CronSchedule,
+    zone: &Tz,
+    year: i32,
+) -> Result<FixedOffset, String> {
+    let start =
+        NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(|| format!("Invalid year: {}", year))?;
+    let offset_at = |date: NaiveDate, time: NaiveTime| match zone
+        .from_local_datetime(&date.and_time(time))
+    {
+        LocalResult::Single(local) => Ok(local.offset().fix()),
+        _ => Err(format!(
+            "Cannot translate '{}' to UTC: on {}, the run at {} falls into a daylight saving change.",
+            expression,
+            date,
+            time.format("%H:%M")
+        )),
+    };
+
+    let mut found: Option<FixedOffset> = None;
+    for date in start.iter_days().take_while(|date| date.year() == year) {
+        if !schedule.matches_date(&date) {
+            continue;
+        }
+        // Away from a transition one time stands for the whole day
+        let times = if offset_changes(zone, date) {
+            run_times(schedule)
+        } else {
+            vec![NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default()]
+        };
+        for time in times {
+            let offset = offset_at(date, time)?;
+            match found {
+                Some(first) if first != offset => {
+                    return Err(format!(
+                        "Cannot translate '{}' to UTC: on {} the zone is at {} instead of {}, so no static UTC expression covers {}.",
+                        expression, date, offset, first, year
+                    ));
+                }
+                _ => found = Some(offset),
+            }
+        }
+    }
+    match found {
+        Some(offset) => Ok(offset),
+        // Nothing runs this year, so any offset will do
+        None => offset_at(start, NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default()),
+    }
+}
+
+fn run_times(schedule: &CronSchedule) -> Vec<NaiveTime> {
+    schedule
+        .hours()
+        .into_iter()
+        .flat_map(|hour| {
+            schedule
+                .minutes()
+                .into_iter()
+                .filter_map(move |minute| NaiveTime::from_hms_opt(hour, minute, 0))
+        })
+        .collect()
+}
+
+/// The day of month, month and day of week fields for runs moved by
+/// `shift` days
+fn shift_days(expression: &str, schedule: &CronSchedule, shift: i64) -> Result<String, String> {
+    let months = schedule.months();
+    let months_restricted = months.len() != 12;
+    let day_of_month_restricted = schedule.is_day_of_month_restricted();
+    let day_of_week_restricted = schedule.is_day_of_week_restricted();
+    let cannot = |reason: String| {
+        Err(format!(
+            "Cannot translate '{}' to UTC: {}.",
+            expression, reason
+        ))
+    };
+    if shift != 0 && months_restricted && (day_of_week_restricted || !day_of_month_restricted) {
+        return cannot(
+            "runs moved across midnight would leave or enter the months it is limited to"
+                .to_string(),
+        );
+    }
+
+    let mut days_of_month = Vec::new();
+    if day_of_month_restricted {
+        for day in schedule.days_of_month() {
+            let moved = i64::from(day) + shift;
+            // The moved day has to exist in exactly the months the day does
+            let same_months = (1..=31).contains(&moved)
+                && months.iter().all(|month| {
+                    [2000, 2001].iter().all(|year| {
+                        let length = i64::from(days_in_month(*year, *month));
+                        (i64::from(day) <= length) == (moved <= length)
+                    })
+                });
+            if !same_months {
+                return cannot(format!(
+                    "runs on day {} of the month would move into another month",
+                    day
+                ));
+            }
+            days_of_month.push(moved as i32);
+        }
+    }
+    let days_of_week: Vec<i32> = schedule
+        .days_of_week()
+        .iter()
+        .map(|day| (i64::from(*day) + shift).rem_euclid(7) as i32)
+        .collect();
+
+    let field = |restricted: bool, values: &[i32]| {
+        if restricted {
+            CronUtils::format_cron_part(values)
+        } else {
+            "*".to_string()
+        }
+    };
+    let months: Vec<i32> = months.iter().map(|month| *month as i32).collect();
+    Ok(format!(
+        "{} {} {}",
+        field(day_of_month_restricted, &days_of_month),
+        field(months_restricted, &months),
+        field(day_of_week_restricted, &days_of_week)
+    ))
+}
+
+fn format_bits(bits: u64, min: u32, max: u32) -> String {
+    let values: Vec<i32> = (min..=max)
+        .filter(|v| bits & (1 << v) != 0)
+        .map(|v| v as i32)
+        .collect();
+    if values.len() as u32 == max - min + 1 {
+        "*".to_string()
+    } else {
+        CronUtils::format_cron_part(&values)
+    }
+}
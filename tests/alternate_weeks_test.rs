@@ -0,0 +1,122 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use natural_cron::alternate_weeks::{AlternateWeekSchedule, AlternateWeeks};
+    use natural_cron::cron_lint::CronLinter;
+    use natural_cron::{CronExpressionBuilder, CronSchedule};
+
+    use crate::common::{at, date};
+
+    fn mondays_at_ten() -> CronExpressionBuilder {
+        CronExpressionBuilder::new()
+            .at_time("10:00")
+            .unwrap()
+            .on_week_days(vec![1])
+            .unwrap()
+    }
+
+    mod weeks {
+        use super::*;
+
+        #[test]
+        fn follow_iso_week_numbers() {
+            // 2024-12-30 starts ISO week 1 of 2025
+            assert!(AlternateWeeks::Odd.matches(&date(2024, 12, 30)));
+            assert!(AlternateWeeks::Odd.matches(&date(2025, 1, 5)));
+            assert!(AlternateWeeks::Even.matches(&date(2025, 1, 6)));
+            assert!(!AlternateWeeks::Odd.matches(&date(2025, 1, 6)));
+        }
+
+        #[test]
+        fn count_from_the_anchor_week() {
+            let weeks = AlternateWeeks::From(date(2025, 1, 8));
+            assert!(weeks.matches(&date(2025, 1, 6)));
+            assert!(weeks.matches(&date(2025, 1, 20)));
+            assert!(!weeks.matches(&date(2025, 1, 19)));
+            // Weeks before the anchor too
+            assert!(weeks.matches(&date(2024, 12, 23)));
+            assert!(!weeks.matches(&date(2024, 12, 30)));
+        }
+    }
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn requires_a_runtime_filter() {
+            let builder = mondays_at_ten().every_other_week(date(2025, 1, 6)).unwrap();
+            assert!(builder.requires_runtime_filter());
+            let message = "Cannot express every other week from 2025-01-06 in a cron expression; it requires a runtime filter such as AlternateWeekSchedule.";
+            assert_eq!(builder.clone().try_compile(), Err(message.to_string()));
+            assert_eq!(builder.validate(), Err(message.to_string()));
+            assert!(CronSchedule::from_builder(&builder).is_err());
+            assert!(CronLinter::lint_builder(&builder).is_err());
+        }
+    }
+
+    mod evaluation {
+        use super::*;
+
+        #[test]
+        fn runs_every_other_monday() {
+            let builder = mondays_at_ten().on_even_weeks().unwrap();
+            let schedule = AlternateWeekSchedule::from_builder(&builder).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2025, 1, 1, 0, 0), 3),
+                vec![
+                    at(2025, 1, 6, 10, 0),
+                    at(2025, 1, 20, 10, 0),
+                    at(2025, 2, 3, 10, 0)
+                ]
+            );
+        }
+
+        #[test]
+        fn keeps_the_rhythm_across_53_week_years() {
+            // ISO week 53 of 2026 is followed by week 1 of 2027
+            let odd =
+                AlternateWeekSchedule::from_builder(&mondays_at_ten().on_odd_weeks().unwrap())
+                    .unwrap();
+            assert_eq!(
+                odd.upcoming(&at(2026, 12, 22, 0, 0), 2),
+                vec![at(2026, 12, 28, 10, 0), at(2027, 1, 4, 10, 0)]
+            );
+
+            let anchored = AlternateWeekSchedule::new(
+                CronSchedule::parse("0 10 * * 1").unwrap(),
+                AlternateWeeks::From(date(2026, 12, 21)),
+            );
+            assert_eq!(
+                anchored.upcoming(&at(2026, 12, 22, 0, 0), 2),
+                vec![at(2027, 1, 4, 10, 0), at(2027, 1, 18, 10, 0)]
+            );
+        }
+
+        #[test]
+        fn combines_with_a_business_day() {
+            // The first business day of the month, in even ISO weeks only
+            let builder = CronExpressionBuilder::new()
+                .at_time("10:00")
+                .unwrap()
+                .on_business_day(1)
+                .unwrap()
+                .on_even_weeks()
+                .unwrap();
+            let schedule = AlternateWeekSchedule::from_builder(&builder).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2025, 1, 1, 0, 0), 3),
+                vec![
+                    at(2025, 2, 3, 10, 0),
+                    at(2025, 3, 3, 10, 0),
+                    at(2025, 4, 1, 10, 0)
+                ]
+            );
+        }
+
+        #[test]
+        fn needs_alternate_weeks() {
+            assert!(AlternateWeekSchedule::from_builder(&CronExpressionBuilder::new()).is_err());
+        }
+    }
+}
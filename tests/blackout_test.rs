@@ -0,0 +1,227 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use natural_cron::blackout::{Blackout, BlackoutSchedule};
+    use natural_cron::interfaces::CronTimeUnit;
+    use natural_cron::{CronExpressionBuilder, CronSchedule};
+
+    use crate::common::{at, date};
+
+    fn every_5_minutes() -> CronExpressionBuilder {
+        CronExpressionBuilder::new()
+            .every_x(5, CronTimeUnit::Minute)
+            .unwrap()
+    }
+
+    mod constructors {
+        use super::*;
+
+        #[test]
+        fn rejects_empty_time_window() {
+            assert_eq!(
+                Blackout::time_window("02:00", "02:00"),
+                Err("Invalid time window: 02:00-02:00. Start and end must differ.".to_string())
+            );
+            assert!(Blackout::time_window("02:00", "25:00").is_err());
+        }
+
+        #[test]
+        fn reads_12_hour_time_windows() {
+            assert_eq!(
+                Blackout::time_window("10pm", "6am"),
+                Blackout::time_window("22:00", "06:00")
+            );
+            assert!(Blackout::time_window("22:00:30", "06:00").is_err());
+        }
+
+        #[test]
+        fn rejects_reversed_date_range() {
+            assert_eq!(
+                Blackout::date_range(date(2025, 12, 31), date(2025, 12, 24)),
+                Err(
+                    "Invalid date range: 2025-12-31 to 2025-12-24. The start is after the end."
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn checks_containment() {
+            let night = Blackout::time_window("22:00", "06:00").unwrap();
+            assert!(night.contains(&at(2025, 3, 10, 23, 0)));
+            assert!(night.contains(&at(2025, 3, 10, 5, 59)));
+            assert!(!night.contains(&at(2025, 3, 10, 6, 0)));
+
+            let holidays = Blackout::date_range(date(2025, 12, 24), date(2025, 12, 26)).unwrap();
+            assert!(holidays.contains(&at(2025, 12, 26, 23, 59)));
+            assert!(!holidays.contains(&at(2025, 12, 27, 0, 0)));
+        }
+    }
+
+    mod compile {
+        use super::*;
+
+        #[test]
+        fn folds_time_window_spanning_whole_hours() {
+            let builder = every_5_minutes()
+                .except(Blackout::time_window("02:00", "04:00").unwrap())
+                .unwrap();
+            assert!(!builder.requires_runtime_filter());
+            assert_eq!(
+                builder.compile(),
+                "*/5 0,1,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23 * * *"
+            );
+        }
+
+        #[test]
+        fn folds_schedule_narrowing_one_field() {
+            let builder = CronExpressionBuilder::new()
+                .every("hour")
+                .unwrap()
+                .except(Blackout::schedule("* * * * SUN").unwrap())
+                .unwrap();
+            assert_eq!(builder.try_compile(), Ok("0 * * * 1-6".to_string()));
+        }
+
+        #[test]
+        fn leaves_expression_alone_for_disjoint_blackouts() {
+            let builder = CronExpressionBuilder::new()
+                .at_time("09:00")
+                .unwrap()
+                .except(Blackout::schedule("* 2-3 * * *").unwrap())
+                .unwrap()
+                .except(Blackout::time_window("12:00", "13:00").unwrap())
+                .unwrap();
+            assert_eq!(builder.compile(), "0 9 * * *");
+        }
+
+        #[test]
+        fn reports_blackouts_needing_a_runtime_filter() {
+            let builder = every_5_minutes()
+                .except(Blackout::schedule("* 2-3 * * 0").unwrap())
+                .unwrap();
+            assert!(builder.requires_runtime_filter());
+            let message = "Cannot express blackout '* 2,3 * * 0' in a cron expression; it requires a runtime filter such as BlackoutSchedule.";
+            assert_eq!(builder.clone().try_compile(), Err(message.to_string()));
+            assert_eq!(builder.validate(), Err(message.to_string()));
+            // Without the blackout applied
+            assert_eq!(builder.compile(), "*/5 * * * *");
+        }
+
+        #[test]
+        fn needs_runtime_filter_for_uneven_windows_and_date_ranges() {
+            let builder = CronExpressionBuilder::new()
+                .every_x(15, CronTimeUnit::Minute)
+                .unwrap()
+                .except(Blackout::time_window("09:30", "10:00").unwrap())
+                .unwrap();
+            assert!(builder.requires_runtime_filter());
+
+            let builder = every_5_minutes()
+                .except(Blackout::date_range(date(2025, 12, 24), date(2025, 12, 26)).unwrap())
+                .unwrap();
+            assert!(builder.requires_runtime_filter());
+        }
+
+        #[test]
+        fn keeps_day_fields_from_switching_to_either_matching() {
+            // Restricting the weekday of a day-of-month schedule would make
+            // it run on the 1st or any Monday to Saturday
+            let builder = CronExpressionBuilder::new()
+                .at_time("09:00")
+                .unwrap()
+                .on_days_of_month(vec![1])
+                .unwrap()
+                .except(Blackout::schedule("* * * * 0").unwrap())
+                .unwrap();
+            assert!(builder.requires_runtime_filter());
+        }
+
+        #[test]
+        fn rejects_blackouts_covering_every_run() {
+            let builder = CronExpressionBuilder::new()
+                .at_time("03:00")
+                .unwrap()
+                .except(Blackout::time_window("02:00", "04:00").unwrap())
+                .unwrap();
+            let message = "Invalid blackout: '02:00-04:00' excludes every run of the schedule.";
+            assert_eq!(builder.validate(), Err(message.to_string()));
+            assert_eq!(builder.try_compile(), Err(message.to_string()));
+        }
+    }
+
+    mod evaluation {
+        use super::*;
+
+        #[test]
+        fn from_builder_folds_representable_blackouts() {
+            let builder = every_5_minutes()
+                .except(Blackout::time_window("02:00", "04:00").unwrap())
+                .unwrap();
+            let schedule = CronSchedule::from_builder(&builder).unwrap();
+            assert_eq!(
+                schedule.next_after(&at(2025, 3, 10, 1, 58)),
+                Some(at(2025, 3, 10, 4, 0))
+            );
+        }
+
+        #[test]
+        fn from_builder_rejects_blackouts_needing_a_runtime_filter() {
+            let builder = every_5_minutes()
+                .except(Blackout::schedule("* 2-3 * * 0").unwrap())
+                .unwrap();
+            assert!(CronSchedule::from_builder(&builder).is_err());
+        }
+
+        #[test]
+        fn skips_maintenance_window() {
+            let builder = every_5_minutes()
+                .except(Blackout::schedule("* 2-3 * * 0").unwrap())
+                .unwrap();
+            let schedule = BlackoutSchedule::from_builder(&builder).unwrap();
+            // 2025-03-09 is a Sunday, 2025-03-10 a Monday
+            assert_eq!(
+                schedule.upcoming(&at(2025, 3, 9, 1, 50), 3),
+                vec![
+                    at(2025, 3, 9, 1, 55),
+                    at(2025, 3, 9, 4, 0),
+                    at(2025, 3, 9, 4, 5)
+                ]
+            );
+            assert_eq!(
+                schedule.next_after(&at(2025, 3, 10, 2, 0)),
+                Some(at(2025, 3, 10, 2, 5))
+            );
+            assert!(!schedule.matches(&at(2025, 3, 9, 3, 0)));
+        }
+
+        #[test]
+        fn skips_windows_past_midnight_and_date_ranges() {
+            let schedule = BlackoutSchedule::new(
+                CronSchedule::parse("0 * * * *").unwrap(),
+                vec![
+                    Blackout::time_window("22:00", "06:00").unwrap(),
+                    Blackout::date_range(date(2025, 12, 24), date(2025, 12, 26)).unwrap(),
+                ],
+            );
+            assert_eq!(
+                schedule.next_after(&at(2025, 3, 10, 21, 30)),
+                Some(at(2025, 3, 11, 6, 0))
+            );
+            assert_eq!(
+                schedule.next_after(&at(2025, 12, 23, 21, 30)),
+                Some(at(2025, 12, 27, 6, 0))
+            );
+        }
+
+        #[test]
+        fn returns_none_when_everything_is_blacked_out() {
+            let schedule = BlackoutSchedule::new(
+                CronSchedule::parse("0 3 1 1 *").unwrap(),
+                vec![Blackout::schedule("* 3 * * *").unwrap()],
+            );
+            assert_eq!(schedule.next_after(&at(2025, 1, 1, 0, 0)), None);
+        }
+    }
+}
@@ -0,0 +1,337 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration as StdDuration;
+
+    use chrono::{Duration, NaiveDateTime};
+    use natural_cron::blocking_scheduler::JobOptions;
+    use natural_cron::bounded::OneShotSchedule;
+    use natural_cron::clock::ManualClock;
+    use natural_cron::interfaces::CronTimeUnit;
+    use natural_cron::interval::IntervalSchedule;
+    use natural_cron::job_runner::OverlapPolicy;
+    use natural_cron::job_store::{InMemoryJobStore, JobState, JobStore};
+    use natural_cron::misfire::MisfirePolicy;
+    use natural_cron::{BlockingScheduler, CronExpressionBuilder};
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        crate::common::at(2025, 3, 10, hour, minute)
+    }
+
+    fn counting_job(counter: &Arc<AtomicUsize>) -> impl Fn() + Send + Sync + 'static {
+        let counter = Arc::clone(counter);
+        move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn scheduler(clock: &Arc<ManualClock>) -> BlockingScheduler {
+        BlockingScheduler::new()
+            .with_clock(clock.clone())
+            .with_poll_interval(StdDuration::from_millis(5))
+    }
+
+    #[test]
+    fn rejects_zero_workers() {
+        assert_eq!(
+            BlockingScheduler::new().with_workers(0).err(),
+            Some("Invalid worker count: 0. At least one worker is required.".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_job_names() {
+        let result = BlockingScheduler::new()
+            .add_job("a", CronExpressionBuilder::new(), || {})
+            .and_then(|s| s.add_job("a", CronExpressionBuilder::new(), || {}));
+        assert_eq!(result.err(), Some("Duplicate job name: a".to_string()));
+    }
+
+    #[test]
+    fn rejects_schedules_that_never_fire() {
+        let schedule = CronExpressionBuilder::new()
+            .on_days_of_month(vec![31])
+            .and_then(|s| s.during_months(vec![2]))
+            .unwrap();
+        let result = BlockingScheduler::new().add_job("never", schedule, || {});
+        assert_eq!(
+            result.err(),
+            Some("Invalid schedule: '* * 31 2 *'. No date can ever match it.".to_string())
+        );
+    }
+
+    #[test]
+    fn computes_next_run_from_the_clock() {
+        let clock = Arc::new(ManualClock::new(at(8, 10)));
+        let handle = scheduler(&clock)
+            .add_job(
+                "quarter",
+                CronExpressionBuilder::new()
+                    .every_x(15, CronTimeUnit::Minute)
+                    .unwrap(),
+                || {},
+            )
+            .and_then(|s| s.start())
+            .unwrap();
+        assert_eq!(handle.next_run("quarter"), Some(at(8, 15)));
+        assert_eq!(handle.next_run("missing"), None);
+        handle.shutdown();
+    }
+
+    #[test]
+    fn runs_jobs_on_any_schedule() {
+        let clock = Arc::new(ManualClock::new(at(8, 10)));
+        let every_7_minutes = IntervalSchedule::new(at(8, 0), Duration::minutes(7)).unwrap();
+        let handle = scheduler(&clock)
+            .add_job_with_schedule("poll", every_7_minutes, JobOptions::new(), || {})
+            .and_then(|s| s.start())
+            .unwrap();
+        assert_eq!(handle.next_run("poll"), Some(at(8, 14)));
+        handle.shutdown();
+    }
+
+    #[test]
+    fn runs_one_shot_jobs_once() {
+        let clock = Arc::new(ManualClock::new(at(8, 59)));
+        let counter = Arc::new(AtomicUsize::new(0));
+        let handle = scheduler(&clock)
+            .add_job_with_schedule(
+                "once",
+                OneShotSchedule::new(at(9, 0)),
+                JobOptions::new(),
+                counting_job(&counter),
+            )
+            .and_then(|s| s.start())
+            .unwrap();
+        clock.advance(Duration::minutes(1));
+        handle.run_pending();
+        assert_eq!(handle.next_run("once"), None);
+        handle.shutdown();
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn runs_job_when_clock_reaches_schedule() {
+        let clock = Arc::new(ManualClock::new(at(7, 59)));
+        let counter = Arc::new(AtomicUsize::new(0));
+        let handle = scheduler(&clock)
+            .add_job(
+                "morning",
+                CronExpressionBuilder::new().at_time("08:00").unwrap(),
+                counting_job(&counter),
+            )
+            .and_then(|s| s.start())
+            .unwrap();
+
+        assert_eq!(handle.run_pending(), 0);
+        clock.advance(Duration::minutes(1));
+        // Dispatch may already have happened on the dispatcher thread
+        handle.run_pending();
+        assert_eq!(
+            handle.next_run("morning"),
+            Some(at(8, 0) + Duration::days(1))
+        );
+        handle.shutdown();
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn keeps_running_after_a_job_panics() {
+        let clock = Arc::new(ManualClock::new(at(7, 59)));
+        let counter = Arc::new(AtomicUsize::new(0));
+        let handle = scheduler(&clock)
+            .with_workers(1)
+            .and_then(|s| {
+                s.add_job(
+                    "panics",
+                    CronExpressionBuilder::new().at_time("08:00")?,
+                    || panic!("job failed"),
+                )
+            })
+            .and_then(|s| {
+                s.add_job(
+                    "counts",
+                    CronExpressionBuilder::new().at_time("08:00")?,
+                    counting_job(&counter),
+                )
+            })
+            .and_then(|s| s.start())
+            .unwrap();
+
+        clock.advance(Duration::minutes(1));
+        handle.run_pending();
+        handle.shutdown();
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn applies_misfire_policy_after_clock_jump() {
+        let clock = Arc::new(ManualClock::new(at(5, 30)));
+        let replayed = Arc::new(AtomicUsize::new(0));
+        let coalesced = Arc::new(AtomicUsize::new(0));
+        let handle = scheduler(&clock)
+            .add_job_with_options(
+                "replayed",
+                CronExpressionBuilder::new().every("hour").unwrap(),
+                JobOptions::new().with_misfire_policy(MisfirePolicy::FireAll),
+                counting_job(&replayed),
+            )
+            .and_then(|s| {
+                s.add_job(
+                    "coalesced",
+                    CronExpressionBuilder::new().every("hour")?,
+                    counting_job(&coalesced),
+                )
+            })
+            .and_then(|s| s.start())
+            .unwrap();
+
+        // Simulate the host sleeping through three occurrences
+        clock.advance(Duration::hours(3));
+        handle.run_pending();
+        handle.shutdown();
+        assert_eq!(replayed.load(Ordering::SeqCst), 3);
+        assert_eq!(coalesced.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn skips_runs_that_overlap_a_long_running_job() {
+        let clock = Arc::new(ManualClock::new(at(8, 0)));
+        let gate = Arc::new(Mutex::new(()));
+        let held = gate.lock().unwrap();
+        let job_gate = Arc::clone(&gate);
+        let handle = scheduler(&clock)
+            .add_job_with_options(
+                "slow",
+                CronExpressionBuilder::new()
+                    .every_x(5, CronTimeUnit::Minute)
+                    .unwrap(),
+                JobOptions::new().with_overlap_policy(OverlapPolicy::SkipIfRunning),
+                move || {
+                    let _guard = job_gate.lock().unwrap();
+                },
+            )
+            .and_then(|s| s.start())
+            .unwrap();
+
+        clock.advance(Duration::minutes(5));
+        handle.run_pending();
+        clock.advance(Duration::minutes(5));
+        handle.run_pending();
+        assert_eq!(handle.metrics("slow").unwrap().skipped, 1);
+
+        drop(held);
+        while handle.metrics("slow").unwrap().completed == 0 {
+            std::thread::yield_now();
+        }
+        let metrics = handle.metrics("slow").unwrap();
+        assert_eq!(metrics.started, 1);
+        assert_eq!(metrics.skipped, 1);
+        handle.shutdown();
+    }
+
+    #[test]
+    fn replays_runs_missed_while_the_process_was_down() {
+        let clock = Arc::new(ManualClock::new(at(8, 30)));
+        let store = Arc::new(InMemoryJobStore::new());
+        store
+            .save(
+                "hourly",
+                &JobState {
+                    last_run: Some(at(5, 30)),
+                    next_run: Some(at(6, 0)),
+                    first_seen: None,
+                },
+            )
+            .unwrap();
+        let counter = Arc::new(AtomicUsize::new(0));
+        let handle = scheduler(&clock)
+            .with_store(store.clone())
+            .add_job_with_options(
+                "hourly",
+                CronExpressionBuilder::new().every("hour").unwrap(),
+                JobOptions::new().with_misfire_policy(MisfirePolicy::FireAll),
+                counting_job(&counter),
+            )
+            .and_then(|s| s.start())
+            .unwrap();
+
+        handle.run_pending();
+        handle.shutdown();
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            store.load("hourly"),
+            Ok(Some(JobState {
+                last_run: Some(at(8, 30)),
+                next_run: Some(at(9, 0)),
+                first_seen: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn records_first_start_in_the_store() {
+        let clock = Arc::new(ManualClock::new(at(8, 30)));
+        let store = Arc::new(InMemoryJobStore::new());
+        let handle = scheduler(&clock)
+            .with_store(store.clone())
+            .add_job(
+                "hourly",
+                CronExpressionBuilder::new().every("hour").unwrap(),
+                || {},
+            )
+            .and_then(|s| s.start())
+            .unwrap();
+        handle.shutdown();
+        assert_eq!(
+            store.load("hourly"),
+            Ok(Some(JobState {
+                last_run: None,
+                next_run: Some(at(9, 0)),
+                first_seen: Some(at(8, 30)),
+            }))
+        );
+    }
+
+    #[test]
+    fn counts_runs_missed_before_the_first_run() {
+        let clock = Arc::new(ManualClock::new(at(8, 30)));
+        let store = Arc::new(InMemoryJobStore::new());
+        store
+            .save(
+                "hourly",
+                &JobState {
+                    last_run: None,
+                    next_run: Some(at(6, 0)),
+                    first_seen: Some(at(5, 30)),
+                },
+            )
+            .unwrap();
+        let counter = Arc::new(AtomicUsize::new(0));
+        let handle = scheduler(&clock)
+            .with_store(store.clone())
+            .add_job_with_options(
+                "hourly",
+                CronExpressionBuilder::new().every("hour").unwrap(),
+                JobOptions::new().with_misfire_policy(MisfirePolicy::FireAll),
+                counting_job(&counter),
+            )
+            .and_then(|s| s.start())
+            .unwrap();
+
+        handle.run_pending();
+        handle.shutdown();
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            store.load("hourly"),
+            Ok(Some(JobState {
+                last_run: Some(at(8, 30)),
+                next_run: Some(at(9, 0)),
+                first_seen: Some(at(5, 30)),
+            }))
+        );
+    }
+}
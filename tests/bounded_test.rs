@@ -0,0 +1,182 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use natural_cron::bounded::{BoundedSchedule, OneShotSchedule};
+    use natural_cron::interval::IntervalSchedule;
+    use natural_cron::{CronExpressionBuilder, CronSchedule};
+
+    use crate::common::at;
+
+    fn daily_at_nine() -> BoundedSchedule {
+        let builder = CronExpressionBuilder::new().at_time("09:00").unwrap();
+        BoundedSchedule::from_builder(&builder).unwrap()
+    }
+
+    mod bounds {
+        use super::*;
+
+        #[test]
+        fn stops_after_the_end() {
+            let schedule = daily_at_nine().until(at(2026, 12, 31, 23, 59)).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2026, 12, 29, 12, 0), 5),
+                vec![at(2026, 12, 30, 9, 0), at(2026, 12, 31, 9, 0)]
+            );
+            assert_eq!(schedule.next_after(&at(2027, 1, 1, 0, 0)), None);
+        }
+
+        #[test]
+        fn includes_a_run_at_the_start_and_end() {
+            let schedule = daily_at_nine()
+                .starting(at(2026, 11, 1, 9, 0))
+                .and_then(|s| s.until(at(2026, 11, 2, 9, 0)))
+                .unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2026, 1, 1, 0, 0), 5),
+                vec![at(2026, 11, 1, 9, 0), at(2026, 11, 2, 9, 0)]
+            );
+        }
+
+        #[test]
+        fn rejects_an_end_before_the_start() {
+            assert_eq!(
+                daily_at_nine()
+                    .starting(at(2026, 2, 1, 0, 0))
+                    .and_then(|s| s.until(at(2026, 1, 1, 9, 0))),
+                Err("Invalid bounds: the end 2026-01-01 09:00:00 is before the start 2026-02-01 00:00:00.".to_string())
+            );
+            assert!(
+                daily_at_nine()
+                    .until(at(2026, 1, 1, 9, 0))
+                    .and_then(|s| s.starting(at(2026, 2, 1, 0, 0)))
+                    .is_err()
+            );
+        }
+    }
+
+    mod run_limits {
+        use super::*;
+
+        #[test]
+        fn stops_after_the_last_counted_run() {
+            let schedule = daily_at_nine()
+                .starting(at(2026, 11, 1, 12, 0))
+                .and_then(|s| s.with_max_runs(3))
+                .unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2026, 10, 1, 0, 0), 5),
+                vec![
+                    at(2026, 11, 2, 9, 0),
+                    at(2026, 11, 3, 9, 0),
+                    at(2026, 11, 4, 9, 0)
+                ]
+            );
+            // Asking later still counts from the start
+            assert_eq!(
+                schedule.next_after(&at(2026, 11, 3, 12, 0)),
+                Some(at(2026, 11, 4, 9, 0))
+            );
+            assert_eq!(schedule.next_after(&at(2026, 11, 4, 9, 0)), None);
+        }
+
+        #[test]
+        fn combines_with_an_end() {
+            let schedule = daily_at_nine()
+                .starting(at(2026, 11, 1, 0, 0))
+                .and_then(|s| s.until(at(2026, 11, 2, 23, 59)))
+                .and_then(|s| s.with_max_runs(5))
+                .unwrap();
+            assert_eq!(schedule.upcoming(&at(2026, 11, 1, 0, 0), 10).len(), 2);
+        }
+
+        #[test]
+        fn bounds_any_schedule() {
+            let start = at(2026, 11, 1, 0, 0);
+            let every_90_minutes = IntervalSchedule::new(start, Duration::minutes(90)).unwrap();
+            let schedule = BoundedSchedule::new(every_90_minutes)
+                .starting(start)
+                .and_then(|s| s.with_max_runs(2))
+                .unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2026, 10, 31, 0, 0), 5),
+                vec![start, at(2026, 11, 1, 1, 30)]
+            );
+        }
+
+        #[test]
+        fn rejects_limits_without_a_start() {
+            assert_eq!(
+                daily_at_nine().with_max_runs(5),
+                Err(
+                    "Invalid run limit: runs are counted from the start, so set one first."
+                        .to_string()
+                )
+            );
+            let schedule = BoundedSchedule::new(CronSchedule::parse("0 9 * * *").unwrap())
+                .starting(at(2026, 1, 1, 0, 0))
+                .unwrap();
+            assert!(schedule.with_max_runs(0).is_err());
+        }
+
+        #[test]
+        fn rejects_limits_that_cannot_be_reached() {
+            let schedule = daily_at_nine().starting(at(2026, 1, 1, 0, 0)).unwrap();
+            assert_eq!(
+                schedule.clone().with_max_runs(usize::MAX),
+                Err(format!(
+                    "Invalid run limit: {}. At most 100000 runs can be counted; use an end instead.",
+                    usize::MAX
+                ))
+            );
+
+            let once = BoundedSchedule::new(OneShotSchedule::new(at(2026, 1, 2, 9, 0)))
+                .starting(at(2026, 1, 1, 0, 0))
+                .unwrap();
+            assert_eq!(
+                once.with_max_runs(2),
+                Err("Invalid run limit: 2. The schedule ends after 1 of them.".to_string())
+            );
+        }
+
+        #[test]
+        fn counts_runs_once_for_every_lookup() {
+            let start = at(2026, 1, 1, 0, 0);
+            let schedule = daily_at_nine()
+                .starting(start)
+                .and_then(|s| s.with_max_runs(20_000))
+                .unwrap();
+            let runs = schedule.upcoming(&start, 2_000);
+            assert_eq!(runs.len(), 2_000);
+            assert_eq!(runs.last(), Some(&at(2031, 6, 23, 9, 0)));
+        }
+
+        #[test]
+        fn recounts_when_the_start_moves() {
+            let schedule = daily_at_nine()
+                .starting(at(2026, 1, 1, 0, 0))
+                .and_then(|s| s.with_max_runs(2))
+                .and_then(|s| s.starting(at(2026, 3, 1, 0, 0)))
+                .unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2026, 1, 1, 0, 0), 5),
+                vec![at(2026, 3, 1, 9, 0), at(2026, 3, 2, 9, 0)]
+            );
+        }
+    }
+
+    mod one_shot {
+        use super::*;
+
+        #[test]
+        fn runs_once() {
+            let schedule = OneShotSchedule::new(at(2026, 11, 1, 9, 0));
+            assert_eq!(
+                schedule.next_after(&at(2026, 10, 1, 0, 0)),
+                Some(at(2026, 11, 1, 9, 0))
+            );
+            assert_eq!(schedule.next_after(&at(2026, 11, 1, 9, 0)), None);
+        }
+    }
+}
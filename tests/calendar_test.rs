@@ -0,0 +1,352 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use natural_cron::calendar::{
+        Calendar, CalendarSchedule, HolidayRule, HolidayShift, easter_sunday,
+    };
+    use natural_cron::{CronExpressionBuilder, CronSchedule};
+
+    use crate::common::{at, date};
+
+    fn new_years_and_christmas() -> Calendar {
+        Calendar::parse("fixed jan 1\nfixed dec 25\n").unwrap()
+    }
+
+    mod rules {
+        use super::*;
+
+        #[test]
+        fn matches_fixed_dates_every_year() {
+            let rule = HolidayRule::Fixed { month: 12, day: 25 };
+            assert!(rule.matches(&date(2025, 12, 25)));
+            assert!(rule.matches(&date(2030, 12, 25)));
+            assert!(!rule.matches(&date(2025, 12, 24)));
+        }
+
+        #[test]
+        fn matches_nth_and_last_weekday_of_month() {
+            // Third Monday of January and last Monday of May 2025
+            let third = HolidayRule::NthWeekday {
+                n: 3,
+                weekday: 1,
+                month: 1,
+            };
+            assert!(third.matches(&date(2025, 1, 20)));
+            assert!(!third.matches(&date(2025, 1, 13)));
+            let last = HolidayRule::NthWeekday {
+                n: -1,
+                weekday: 1,
+                month: 5,
+            };
+            assert!(last.matches(&date(2025, 5, 26)));
+            assert!(!last.matches(&date(2025, 5, 19)));
+        }
+
+        #[test]
+        fn matches_days_relative_to_easter() {
+            let good_friday = HolidayRule::Easter { offset: -2 };
+            assert!(good_friday.matches(&date(2025, 4, 18)));
+            assert!(good_friday.matches(&date(2024, 3, 29)));
+            assert!(!good_friday.matches(&date(2025, 4, 20)));
+        }
+
+        #[test]
+        fn computes_easter_sunday() {
+            assert_eq!(easter_sunday(2000), Some(date(2000, 4, 23)));
+            assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+            assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+            assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+        }
+
+        #[test]
+        fn rejects_invalid_rules() {
+            assert_eq!(
+                Calendar::new().with_rule(HolidayRule::Fixed { month: 2, day: 30 }),
+                Err("Invalid holiday date: month 2 day 30".to_string())
+            );
+            assert!(
+                Calendar::new()
+                    .with_rule(HolidayRule::NthWeekday {
+                        n: 6,
+                        weekday: 1,
+                        month: 1
+                    })
+                    .is_err()
+            );
+        }
+    }
+
+    mod business_days {
+        use super::*;
+
+        #[test]
+        fn excludes_weekends_and_holidays() {
+            let calendar = new_years_and_christmas();
+            assert!(calendar.is_business_day(&date(2025, 12, 24)));
+            assert!(!calendar.is_business_day(&date(2025, 12, 25)));
+            // 2025-12-27 is a Saturday
+            assert!(!calendar.is_business_day(&date(2025, 12, 27)));
+            assert!(calendar.is_holiday(&date(2025, 12, 25)));
+            assert!(!calendar.is_holiday(&date(2025, 12, 27)));
+        }
+
+        #[test]
+        fn finds_neighbouring_business_days() {
+            let calendar = new_years_and_christmas();
+            assert_eq!(
+                calendar.next_business_day(&date(2025, 12, 24)),
+                Some(date(2025, 12, 26))
+            );
+            assert_eq!(
+                calendar.previous_business_day(&date(2025, 12, 29)),
+                Some(date(2025, 12, 26))
+            );
+        }
+
+        #[test]
+        fn supports_custom_weekends() {
+            let calendar = Calendar::new().with_weekend(vec![5, 6]).unwrap();
+            assert!(calendar.is_business_day(&date(2025, 3, 9))); // Sunday
+            assert!(!calendar.is_business_day(&date(2025, 3, 7))); // Friday
+            let open = Calendar::parse("weekend none").unwrap();
+            assert!(open.is_business_day(&date(2025, 3, 8)));
+        }
+    }
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn reads_every_rule_kind() {
+            let calendar = Calendar::parse(
+                "# exchange holidays\n\
+                 weekend sat sun\n\
+                 fixed 1 1          # New Year's Day\n\
+                 nth 3 mon jan      # Martin Luther King Jr. Day\n\
+                 last MON may       # Memorial Day\n\
+                 easter -2          # Good Friday\n\
+                 date 2025-01-09    # national day of mourning\n",
+            )
+            .unwrap();
+            for holiday in [
+                date(2025, 1, 1),
+                date(2025, 1, 20),
+                date(2025, 5, 26),
+                date(2025, 4, 18),
+                date(2025, 1, 9),
+            ] {
+                assert!(calendar.is_holiday(&holiday), "{}", holiday);
+            }
+            assert!(!calendar.is_holiday(&date(2025, 1, 2)));
+        }
+
+        #[test]
+        fn reports_the_failing_line() {
+            assert_eq!(
+                Calendar::parse("fixed jan 1\nfixed funuary 3"),
+                Err("Invalid calendar line 2: Invalid month name: funuary".to_string())
+            );
+            assert_eq!(
+                Calendar::parse("holiday tomorrow"),
+                Err("Invalid calendar line 1: Unknown rule: 'holiday tomorrow'".to_string())
+            );
+        }
+
+        #[test]
+        fn writes_rules_back_in_the_same_format() {
+            let text = "weekend fri sat\nfixed jan 1\nnth 3 mon jan\nlast mon may\neaster -2\ndate 2025-01-09";
+            let calendar = Calendar::parse(text).unwrap();
+            assert_eq!(calendar.to_string(), text);
+            assert_eq!(Calendar::parse(&calendar.to_string()).unwrap(), calendar);
+        }
+
+        #[test]
+        fn loads_from_a_file() {
+            let path = std::env::temp_dir()
+                .join(format!("natural-cron-calendar-{}.txt", std::process::id()));
+            std::fs::write(&path, "fixed dec 25\n").unwrap();
+            let calendar = Calendar::load(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert!(calendar.is_holiday(&date(2025, 12, 25)));
+
+            assert!(
+                Calendar::load(&path)
+                    .unwrap_err()
+                    .starts_with("Failed to read calendar")
+            );
+        }
+    }
+
+    mod calendar_schedule {
+        use super::*;
+
+        fn first_of_month(shift: HolidayShift) -> CalendarSchedule {
+            let schedule = CronSchedule::parse("0 9 1 * *").unwrap();
+            CalendarSchedule::new(schedule, new_years_and_christmas(), shift)
+        }
+
+        #[test]
+        fn skips_runs_on_non_business_days() {
+            let builder = CronExpressionBuilder::new().at_time("18:00").unwrap();
+            let schedule = CalendarSchedule::from_builder(
+                &builder,
+                new_years_and_christmas(),
+                HolidayShift::Skip,
+            )
+            .unwrap();
+            assert_eq!(
+                schedule.next_after(&at(2025, 12, 24, 19, 0)),
+                Some(at(2025, 12, 26, 18, 0))
+            );
+            // 2026-01-01 is a holiday, and February 1 and March 1 are Sundays
+            assert_eq!(
+                first_of_month(HolidayShift::Skip).next_after(&at(2025, 12, 15, 0, 0)),
+                Some(at(2026, 4, 1, 9, 0))
+            );
+        }
+
+        #[test]
+        fn shifts_runs_to_the_next_business_day() {
+            let schedule = first_of_month(HolidayShift::Next);
+            assert_eq!(
+                schedule.upcoming(&at(2025, 12, 15, 0, 0), 3),
+                vec![
+                    at(2026, 1, 2, 9, 0),
+                    at(2026, 2, 2, 9, 0),
+                    at(2026, 3, 2, 9, 0)
+                ]
+            );
+        }
+
+        #[test]
+        fn shifts_runs_to_the_previous_business_day() {
+            let schedule = first_of_month(HolidayShift::Previous);
+            assert_eq!(
+                schedule.upcoming(&at(2025, 12, 15, 0, 0), 3),
+                vec![
+                    at(2025, 12, 31, 9, 0),
+                    at(2026, 1, 30, 9, 0),
+                    at(2026, 2, 27, 9, 0)
+                ]
+            );
+        }
+
+        #[test]
+        fn runs_once_when_shifted_runs_coincide() {
+            // 2025-03-07 is a Friday; the weekend's runs all move to Monday
+            let schedule = CalendarSchedule::new(
+                CronSchedule::parse("0 18 * * *").unwrap(),
+                Calendar::new(),
+                HolidayShift::Next,
+            );
+            assert_eq!(
+                schedule.upcoming(&at(2025, 3, 7, 19, 0), 2),
+                vec![at(2025, 3, 10, 18, 0), at(2025, 3, 11, 18, 0)]
+            );
+        }
+
+        #[test]
+        fn returns_none_without_business_days() {
+            let calendar = Calendar::new()
+                .with_weekend(vec![0, 1, 2, 3, 4, 5, 6])
+                .unwrap();
+            let schedule = CalendarSchedule::new(
+                CronSchedule::parse("0 0 * * *").unwrap(),
+                calendar,
+                HolidayShift::Next,
+            );
+            assert_eq!(schedule.next_after(&at(2025, 1, 1, 0, 0)), None);
+        }
+    }
+
+    mod business_day_of_month {
+        use natural_cron::calendar::{BusinessDay, BusinessDaySchedule};
+
+        use super::*;
+
+        #[test]
+        fn counts_business_days_of_a_month() {
+            let calendar = Calendar::new();
+            // 2025-03-01 is a Saturday
+            let days = calendar.business_days_of_month(2025, 3);
+            assert_eq!(days.first(), Some(&date(2025, 3, 3)));
+            assert_eq!(days.last(), Some(&date(2025, 3, 31)));
+            assert_eq!(days.len(), 21);
+
+            assert!(BusinessDay::Nth(3).matches(&date(2025, 3, 5), &calendar));
+            assert!(!BusinessDay::Nth(3).matches(&date(2025, 3, 3), &calendar));
+            assert!(BusinessDay::Last.matches(&date(2025, 3, 31), &calendar));
+            assert!(!BusinessDay::Nth(0).matches(&date(2025, 3, 3), &calendar));
+        }
+
+        #[test]
+        fn runs_on_the_nth_business_day() {
+            let builder = CronExpressionBuilder::new()
+                .at_time("09:00")
+                .unwrap()
+                .on_business_day(3)
+                .unwrap();
+            assert!(builder.requires_runtime_filter());
+            assert_eq!(
+                builder.clone().try_compile(),
+                Err("Cannot express the 3rd business day of the month in a cron expression; it requires a runtime filter such as BusinessDaySchedule.".to_string())
+            );
+            assert!(CronSchedule::from_builder(&builder).is_err());
+
+            let schedule = BusinessDaySchedule::from_builder(&builder).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2025, 3, 1, 0, 0), 3),
+                vec![
+                    at(2025, 3, 5, 9, 0),
+                    at(2025, 4, 3, 9, 0),
+                    at(2025, 5, 5, 9, 0)
+                ]
+            );
+        }
+
+        #[test]
+        fn runs_on_the_last_business_day_skipping_holidays() {
+            // 2025-10-31 is a Friday, November 29 and 30 a weekend
+            let builder = CronExpressionBuilder::new()
+                .at_time("18:00")
+                .unwrap()
+                .on_last_business_day()
+                .unwrap()
+                .with_holidays(vec![date(2025, 10, 31)])
+                .unwrap();
+            let schedule = BusinessDaySchedule::from_builder(&builder).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2025, 10, 1, 0, 0), 2),
+                vec![at(2025, 10, 30, 18, 0), at(2025, 11, 28, 18, 0)]
+            );
+        }
+
+        #[test]
+        fn uses_a_calendar_from_the_builder() {
+            let builder = CronExpressionBuilder::new()
+                .at_time("09:00")
+                .unwrap()
+                .on_business_day(1)
+                .unwrap()
+                .with_calendar(new_years_and_christmas())
+                .unwrap();
+            let schedule = BusinessDaySchedule::from_builder(&builder).unwrap();
+            // 2026-01-01 is a holiday
+            assert_eq!(
+                schedule.next_after(&at(2025, 12, 15, 0, 0)),
+                Some(at(2026, 1, 2, 9, 0))
+            );
+        }
+
+        #[test]
+        fn rejects_impossible_business_days() {
+            assert_eq!(
+                CronExpressionBuilder::new().on_business_day(24),
+                Err("Invalid business day: 24. Day should be between 1 and 23.".to_string())
+            );
+            assert!(CronExpressionBuilder::new().on_business_day(0).is_err());
+            assert!(BusinessDaySchedule::from_builder(&CronExpressionBuilder::new()).is_err());
+        }
+    }
+}
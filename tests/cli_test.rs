@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use std::process::{Command, Output};
+
+    fn run(args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_natural-cron"))
+            .args(args)
+            .output()
+            .unwrap()
+    }
+
+    fn stdout(output: &Output) -> String {
+        String::from_utf8(output.stdout.clone()).unwrap()
+    }
+
+    #[test]
+    fn builds_from_flags_in_order() {
+        let output = run(&["build", "--at", "08:00", "--weekdays", "1,2,3,4,5"]);
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(stdout(&output), "0 8 * * 1-5\n");
+
+        let output = run(&["build", "--every-x", "15", "minute"]);
+        assert_eq!(stdout(&output), "*/15 * * * *\n");
+    }
+
+    #[test]
+    fn validates_expressions() {
+        let output = run(&["validate", "*/15 9-17 * * MON-FRI"]);
+        assert_eq!(output.status.code(), Some(0));
+
+        let output = run(&["validate", "61 * * * *"]);
+        assert_eq!(output.status.code(), Some(1));
+    }
+
+    #[test]
+    fn explains_expressions() {
+        let output = run(&["explain", "30 8 * * *"]);
+        assert_eq!(stdout(&output), "At 08:30.\n");
+    }
+
+    #[test]
+    fn lists_next_occurrences_in_a_time_zone() {
+        let output = run(&["next", "30 9 * * *", "-n", "2", "--tz", "Asia/Kolkata"]);
+        assert_eq!(output.status.code(), Some(0));
+        let lines: Vec<String> = stdout(&output).lines().map(String::from).collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.ends_with("T09:30:00+05:30")));
+    }
+
+    #[test]
+    fn lints_expressions() {
+        let output = run(&["lint", "0 0 * * *"]);
+        assert_eq!(stdout(&output), "no warnings\n");
+
+        let output = run(&["--json", "lint", "0 0 30 2 *"]);
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(
+            stdout(&output),
+            "{\"expression\":\"0 0 30 2 *\",\"warnings\":[\"'0 0 30 2 *' never fires: none of its days of month exist in its months.\"]}\n"
+        );
+    }
+
+    #[test]
+    fn converts_to_quartz() {
+        let output = run(&["convert", "0 9 * * 1-5", "--to", "quartz"]);
+        assert_eq!(stdout(&output), "0 0 9 ? * 2-6\n");
+    }
+
+    #[test]
+    fn prints_json() {
+        let output = run(&["--json", "explain", "0 0 * * *"]);
+        assert_eq!(
+            stdout(&output),
+            "{\"expression\":\"0 0 * * *\",\"description\":\"At 00:00.\"}\n"
+        );
+
+        let output = run(&["--json", "validate", "0 0 32 * *"]);
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "{\"error\":\"Invalid day of month: '32'. Value should be between 1 and 31.\",\"kind\":\"invalid_input\"}\n"
+        );
+    }
+
+    #[test]
+    fn separates_usage_errors_from_invalid_input() {
+        assert_eq!(run(&[]).status.code(), Some(2));
+        assert_eq!(run(&["frobnicate"]).status.code(), Some(2));
+        assert_eq!(run(&["build", "--at"]).status.code(), Some(2));
+        assert_eq!(run(&["build", "--at", "25:00"]).status.code(), Some(1));
+        assert_eq!(
+            run(&["next", "* * * * *", "--tz", "Mars/Olympus"])
+                .status
+                .code(),
+            Some(1)
+        );
+        assert_eq!(
+            run(&["next", "0 9 * * *", "-n", "18446744073709551615"])
+                .status
+                .code(),
+            Some(2)
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! Fixtures shared by the integration tests; each test crate uses a subset
+#![allow(dead_code)]
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+pub fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+pub fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+    date(year, month, day).and_hms_opt(hour, minute, 0).unwrap()
+}
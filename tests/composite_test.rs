@@ -0,0 +1,220 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use natural_cron::composite::CompositeSchedule;
+    use natural_cron::{CronExpressionBuilder, CronSchedule};
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        crate::common::at(2025, 3, day, hour, minute)
+    }
+
+    fn lines(times: Vec<&str>) -> Result<Vec<String>, String> {
+        CronExpressionBuilder::new()
+            .at_times(times)
+            .and_then(|s| s.compile_lines())
+    }
+
+    mod compile {
+        use super::*;
+
+        #[test]
+        fn keeps_times_sharing_a_grid_on_one_line() {
+            assert_eq!(
+                lines(vec!["08:00", "08:30", "17:00", "17:30"]),
+                Ok(vec!["0,30 8,17 * * *".to_string()])
+            );
+            let builder = CronExpressionBuilder::new()
+                .at_times(vec!["09:15", "10:15"])
+                .unwrap();
+            assert!(!builder.requires_runtime_filter());
+            assert_eq!(builder.try_compile(), Ok("15 9,10 * * *".to_string()));
+        }
+
+        #[test]
+        fn groups_times_sharing_minutes() {
+            assert_eq!(
+                lines(vec!["17:30", "08:00", "12:00"]),
+                Ok(vec!["0 8,12 * * *".to_string(), "30 17 * * *".to_string()])
+            );
+            // Grouping by hour needs fewer lines here
+            assert_eq!(
+                lines(vec!["08:00", "08:20", "08:40", "09:10"]),
+                Ok(vec![
+                    "0,20,40 8 * * *".to_string(),
+                    "10 9 * * *".to_string()
+                ])
+            );
+        }
+
+        #[test]
+        fn keeps_other_fields_on_every_line() {
+            let builder = CronExpressionBuilder::new()
+                .at_times(vec!["08:00", "17:30"])
+                .unwrap()
+                .on_week_days(vec![1, 2, 3, 4, 5])
+                .unwrap();
+            assert_eq!(
+                builder.clone().try_compile(),
+                Err("Cannot express these times in a single cron expression; they need 2 lines. Use compile_lines() or CompositeSchedule.".to_string())
+            );
+            assert_eq!(
+                builder.validate(),
+                Err("Cannot express these times in a single cron expression; they need 2 lines. Use compile_lines() or CompositeSchedule.".to_string())
+            );
+            // Only the first line is written
+            assert_eq!(builder.compile(), "0 8 * * 1-5");
+        }
+
+        #[test]
+        fn never_compiles_times_that_were_not_given() {
+            let time_sets = vec![
+                vec!["08:00", "17:30"],
+                vec!["17:30", "08:00", "12:00"],
+                vec!["08:00", "08:20", "08:40", "09:10"],
+                vec!["06:45", "12:00", "18:15", "23:59"],
+            ];
+            for times in time_sets {
+                let builder = CronExpressionBuilder::new()
+                    .at_times(times.clone())
+                    .unwrap();
+                let schedule = CronSchedule::parse(&builder.compile()).unwrap();
+                let given: Vec<NaiveDateTime> = times
+                    .iter()
+                    .map(|time| {
+                        let (hour, minute) = time.split_once(':').unwrap();
+                        at(10, hour.parse().unwrap(), minute.parse().unwrap())
+                    })
+                    .collect();
+                let mut minute = at(10, 0, 0);
+                while minute < at(11, 0, 0) {
+                    if schedule.matches(&minute) {
+                        assert!(given.contains(&minute), "{:?} ran at {}", times, minute);
+                    }
+                    minute += Duration::minutes(1);
+                }
+            }
+        }
+
+        #[test]
+        fn replaces_times_with_later_ones() {
+            let builder = CronExpressionBuilder::new()
+                .at_times(vec!["08:00", "17:30"])
+                .unwrap()
+                .at_time("12:00")
+                .unwrap();
+            assert_eq!(builder.compile(), "0 12 * * *");
+        }
+
+        #[test]
+        fn keeps_the_other_field_when_minutes_or_hours_change() {
+            let builder = || {
+                CronExpressionBuilder::new()
+                    .at_times(vec!["08:00", "17:30"])
+                    .unwrap()
+            };
+            assert_eq!(
+                builder()
+                    .at_minutes(vec![15])
+                    .and_then(|s| s.compile_lines()),
+                Ok(vec!["15 8,17 * * *".to_string()])
+            );
+            assert_eq!(
+                builder().at_hours(vec![9]).and_then(|s| s.compile_lines()),
+                Ok(vec!["0,30 9 * * *".to_string()])
+            );
+        }
+
+        #[test]
+        fn rejects_invalid_times() {
+            assert_eq!(
+                lines(vec![]),
+                Err("Invalid times: at least one time is required.".to_string())
+            );
+            assert!(lines(vec!["08:00", "24:00"]).is_err());
+        }
+    }
+
+    mod evaluation {
+        use super::*;
+
+        #[test]
+        fn runs_at_the_union_of_its_lines() {
+            let builder = CronExpressionBuilder::new()
+                .at_times(vec!["08:00", "17:30"])
+                .unwrap();
+            assert!(CronSchedule::from_builder(&builder).is_err());
+            let schedule = CompositeSchedule::from_builder(&builder).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(10, 12, 0), 3),
+                vec![at(10, 17, 30), at(11, 8, 0), at(11, 17, 30)]
+            );
+            assert!(schedule.matches(&at(10, 8, 0)));
+            assert!(!schedule.matches(&at(10, 8, 30)));
+        }
+
+        #[test]
+        fn runs_overlapping_times_once() {
+            let schedule = CompositeSchedule::new(vec![
+                CronSchedule::parse("0 * * * *").unwrap(),
+                CronSchedule::parse("0,30 9 * * *").unwrap(),
+            ])
+            .unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(10, 8, 30), 4),
+                vec![at(10, 9, 0), at(10, 9, 30), at(10, 10, 0), at(10, 11, 0)]
+            );
+            assert!(CompositeSchedule::new(Vec::new()).is_err());
+        }
+    }
+
+    mod runtime_filters {
+        use natural_cron::blackout::{Blackout, BlackoutSchedule};
+        use natural_cron::calendar::BusinessDaySchedule;
+        use natural_cron::month_end::MonthEndSchedule;
+
+        use super::*;
+
+        fn twice_a_day() -> CronExpressionBuilder {
+            CronExpressionBuilder::new()
+                .at_times(vec!["08:00", "17:30"])
+                .unwrap()
+        }
+
+        #[test]
+        fn keep_every_line_of_times_outside_blackouts() {
+            let march = |day| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+            let builder = twice_a_day()
+                .except(Blackout::date_range(march(11), march(12)).unwrap())
+                .unwrap();
+            let schedule = BlackoutSchedule::from_builder(&builder).unwrap();
+            assert_eq!(schedule.schedule().schedules().len(), 2);
+            assert_eq!(
+                schedule.upcoming(&at(10, 12, 0), 3),
+                vec![at(10, 17, 30), at(13, 8, 0), at(13, 17, 30)]
+            );
+        }
+
+        #[test]
+        fn keep_every_line_of_times_at_month_end() {
+            let builder = twice_a_day().on_days_before_month_end(0).unwrap();
+            let schedule = MonthEndSchedule::from_builder(&builder).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(1, 0, 0), 2),
+                vec![at(31, 8, 0), at(31, 17, 30)]
+            );
+        }
+
+        #[test]
+        fn keep_every_line_of_times_on_business_days() {
+            // 2025-03-31 is a Monday
+            let builder = twice_a_day().on_last_business_day().unwrap();
+            let schedule = BusinessDaySchedule::from_builder(&builder).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(1, 0, 0), 2),
+                vec![at(31, 8, 0), at(31, 17, 30)]
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use natural_cron::cron_explainer::CronExplainer;
+
+    fn explain(expression: &str) -> String {
+        CronExplainer::explain(expression).unwrap()
+    }
+
+    #[test]
+    fn describes_a_fixed_time() {
+        assert_eq!(explain("30 17 * * *"), "At 17:30.");
+    }
+
+    #[test]
+    fn describes_every_minute() {
+        assert_eq!(explain("* * * * *"), "At every minute.");
+    }
+
+    #[test]
+    fn describes_steps_and_ranges() {
+        assert_eq!(
+            explain("*/15 9-17 * * MON-FRI"),
+            "At every 15th minute past every hour from 9 through 17 on every day-of-week from Monday through Friday."
+        );
+    }
+
+    #[test]
+    fn describes_lists() {
+        assert_eq!(explain("0,30 * * * *"), "At minute 0 and 30.");
+        assert_eq!(
+            explain("0 12 1,15 * *"),
+            "At 12:00 on day-of-month 1 and 15."
+        );
+        assert_eq!(explain("15 10 * * 6,0"), "At 10:15 on Saturday and Sunday.");
+    }
+
+    #[test]
+    fn names_months() {
+        assert_eq!(
+            explain("0 0 1 1,4,7,10 *"),
+            "At 00:00 on day-of-month 1 in January, April, July and October."
+        );
+    }
+
+    #[test]
+    fn says_or_when_both_day_fields_are_set() {
+        assert_eq!(
+            explain("0 0 15 * 1"),
+            "At 00:00 on day-of-month 15 or on Monday."
+        );
+    }
+
+    #[test]
+    fn describes_seconds() {
+        assert_eq!(
+            explain("*/5 * * * * *"),
+            "Every 5th second, at every minute."
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(CronExplainer::explain("0 25 * * *").is_err());
+    }
+}
@@ -1,3 +1,6 @@
+// These tests chain `and_then(|s| Ok(s.compile()))` throughout
+#![allow(clippy::bind_instead_of_map)]
+
 #[cfg(test)]
 mod tests {
     use std::vec;
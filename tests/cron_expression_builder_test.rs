@@ -1,3 +1,6 @@
+// These tests chain `and_then(|s| Ok(s.compile()))` throughout
+#![allow(clippy::bind_instead_of_map)]
+
 #[cfg(test)]
 mod tests {
     use natural_cron::CronExpressionBuilder;
@@ -216,10 +219,13 @@ mod tests {
         }
 
         #[test]
-        fn throws_for_invalid_format_extra_seconds() {
+        fn throws_for_seconds_without_seconds_field() {
             let result = new_schedule().at_time("14:30:10");
             match result {
-                Err(e) => assert_eq!(e, "Invalid time format for 'at': 14:30:10"),
+                Err(e) => assert_eq!(
+                    e,
+                    "Invalid time for 'at': 14:30:10 has seconds; enable them with with_seconds() first."
+                ),
                 Ok(_) => panic!("Expected error but got Ok"),
             }
         }
@@ -371,6 +377,7 @@ mod tests {
 
     mod every_x {
         use super::*;
+        use natural_cron::cron_lint::{CronLinter, LintKind};
         use natural_cron::interfaces::CronTimeUnit;
 
         #[test]
@@ -397,6 +404,27 @@ mod tests {
             assert_eq!(result, Ok("0 0 */5 * *".to_string()));
         }
 
+        #[test]
+        fn lints_intervals_that_restart_early() {
+            for (interval, unit, expression) in [
+                (7, CronTimeUnit::Minute, "*/7 * * * *"),
+                (5, CronTimeUnit::Hour, "0 */5 * * *"),
+                (5, CronTimeUnit::Month, "0 0 1 */5 *"),
+                (14, CronTimeUnit::DayOfMonth, "0 0 */14 * *"),
+            ] {
+                let builder = new_schedule().every_x(interval, unit).unwrap();
+                assert_eq!(builder.clone().compile(), expression);
+                let warnings = CronLinter::lint_builder(&builder).unwrap();
+                assert!(
+                    warnings
+                        .iter()
+                        .any(|warning| warning.kind == LintKind::UnevenStep),
+                    "{}",
+                    expression
+                );
+            }
+        }
+
         #[test]
         fn every_2_months() {
             let result = new_schedule()
@@ -405,4 +433,99 @@ mod tests {
             assert_eq!(result, Ok("0 0 1 */2 *".to_string()));
         }
     }
+
+    mod from_expression {
+        use super::*;
+
+        #[test]
+        fn keeps_fields_as_written() {
+            let result = CronExpressionBuilder::from_expression("*/15  9-17 * * MON-FRI")
+                .and_then(|s| Ok(s.compile()));
+            assert_eq!(result, Ok("*/15 9-17 * * MON-FRI".to_string()));
+        }
+
+        #[test]
+        fn allows_further_chaining() {
+            let result = CronExpressionBuilder::from_expression("0 2 * * *")
+                .and_then(|s| s.on_week_days(vec![0, 6]))
+                .and_then(|s| Ok(s.compile()));
+            assert_eq!(result, Ok("0 2 * * 0,6".to_string()));
+        }
+
+        #[test]
+        fn reads_a_leading_seconds_field() {
+            let result = CronExpressionBuilder::from_expression("30 0 2 * * *")
+                .and_then(|s| s.on_week_days(vec![0, 6]))
+                .and_then(|s| Ok(s.compile()));
+            assert_eq!(result, Ok("30 0 2 * * 0,6".to_string()));
+        }
+
+        #[test]
+        fn throws_when_field_count_is_wrong() {
+            let result = CronExpressionBuilder::from_expression("0 0 2 * * * 2025");
+            assert_eq!(
+                result,
+                Err(
+                    "Invalid cron expression: '0 0 2 * * * 2025'. Expected 5 or 6 fields."
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn throws_when_field_is_invalid() {
+            let result = CronExpressionBuilder::from_expression("0 24 * * *");
+            assert_eq!(
+                result,
+                Err("Invalid hour: '24'. Value should be between 0 and 23.".to_string())
+            );
+        }
+    }
+
+    mod validate {
+        use super::*;
+
+        #[test]
+        fn accepts_schedules_that_fire() {
+            let result = new_schedule()
+                .on_days_of_month(vec![29])
+                .and_then(|s| s.during_months(vec![2]))
+                .and_then(|s| s.validate());
+            assert_eq!(result, Ok(()));
+            assert_eq!(new_schedule().validate(), Ok(()));
+        }
+
+        #[test]
+        fn throws_when_day_never_exists_in_month() {
+            let result = new_schedule()
+                .at_time("00:00")
+                .and_then(|s| s.on_days_of_month(vec![31]))
+                .and_then(|s| s.during_months(vec![2]))
+                .and_then(|s| s.validate());
+            assert_eq!(
+                result,
+                Err("Invalid schedule: '0 0 31 2 *'. No date can ever match it.".to_string())
+            );
+        }
+
+        #[test]
+        fn throws_when_no_month_has_any_of_the_days() {
+            let result = new_schedule()
+                .on_days_of_month(vec![30, 31])
+                .and_then(|s| s.during_months(vec![2]))
+                .and_then(|s| s.validate());
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn accepts_missing_day_when_week_day_is_also_set() {
+            // Both day fields set means either one is enough
+            let result = new_schedule()
+                .on_days_of_month(vec![31])
+                .and_then(|s| s.during_months(vec![2]))
+                .and_then(|s| s.on_week_days(vec![1]))
+                .and_then(|s| s.validate());
+            assert_eq!(result, Ok(()));
+        }
+    }
 }
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use chrono_tz::America::New_York;
+    use chrono_tz::Asia::Tokyo;
+    use natural_cron::CronExpressionBuilder;
+    use natural_cron::cron_lint::{CronLinter, LintKind};
+
+    fn kinds(expression: &str) -> Vec<LintKind> {
+        CronLinter::lint(expression)
+            .unwrap()
+            .into_iter()
+            .map(|w| w.kind)
+            .collect()
+    }
+
+    #[test]
+    fn accepts_ordinary_expressions() {
+        for expression in ["0 3 * * *", "*/15 * * * *", "30 8 * * 1-5", "0 0 1 * *"] {
+            assert_eq!(kinds(expression), vec![], "{}", expression);
+        }
+    }
+
+    #[test]
+    fn warns_about_wildcard_minute_with_fixed_hour() {
+        let warnings = CronLinter::lint("* 3 * * *").unwrap();
+        assert_eq!(warnings[0].kind, LintKind::WildcardMinuteWithFixedHour);
+        assert_eq!(
+            warnings[0].message,
+            "The minute field is '*', so the job runs every minute of hour 3 rather than once. Use '0' to run on the hour."
+        );
+        assert_eq!(kinds("* * * * *"), vec![]);
+    }
+
+    #[test]
+    fn warns_about_days_missing_in_some_months() {
+        let warnings = CronLinter::lint("0 0 31 * *").unwrap();
+        assert_eq!(
+            warnings[0].message,
+            "Day 31 does not exist in Feb, Apr, Jun, Sep and Nov, so the job does not run in those months."
+        );
+        assert_eq!(kinds("0 0 29 2 *"), vec![LintKind::DayMissingInMonth]);
+    }
+
+    #[test]
+    fn warns_about_schedules_that_never_fire() {
+        assert_eq!(kinds("0 0 30 2 *"), vec![LintKind::NeverFires]);
+        assert_eq!(kinds("0 0 31 4,6,9,11 *"), vec![LintKind::NeverFires]);
+    }
+
+    #[test]
+    fn warns_about_combined_day_fields() {
+        assert_eq!(kinds("0 0 13 * 5"), vec![LintKind::DayFieldsCombined]);
+    }
+
+    #[test]
+    fn warns_about_uneven_steps() {
+        let warnings = CronLinter::lint("*/7 * * * *").unwrap();
+        assert_eq!(
+            warnings[0].message,
+            "'*/7' in the minute field does not divide the range 0-59 evenly: after 56 the next run is 4 later instead of 7."
+        );
+        assert_eq!(kinds("0 9-18/4 * * *"), vec![LintKind::UnevenStep]);
+        assert_eq!(kinds("0 */5 * * *"), vec![LintKind::UnevenStep]);
+        assert_eq!(kinds("0 9-17/4 * * *"), vec![]);
+        assert_eq!(kinds("5/15 * * * *"), vec![]);
+    }
+
+    #[test]
+    fn lints_builders() {
+        let builder = CronExpressionBuilder::from_expression("* 3 * * *").unwrap();
+        assert_eq!(
+            CronLinter::lint_builder(&builder).unwrap()[0].kind,
+            LintKind::WildcardMinuteWithFixedHour
+        );
+
+        // Each line is checked, and a warning shared by them is reported once
+        let builder = CronExpressionBuilder::new()
+            .at_times(vec!["08:00", "17:30"])
+            .and_then(|s| s.on_days_of_month(vec![31]))
+            .and_then(|s| s.during_months(vec![1, 4]))
+            .unwrap();
+        let warnings = CronLinter::lint_builder(&builder).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, LintKind::DayMissingInMonth);
+    }
+
+    #[test]
+    fn warns_about_daylight_saving_changes() {
+        let warnings = CronLinter::lint_in_zone("30 2 * * *", &New_York, 2025).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, LintKind::DaylightSaving);
+        assert_eq!(
+            warnings[0].message,
+            "On 2025-03-09, the run at 02:30 is skipped by the daylight saving change and will not run."
+        );
+
+        let warnings = CronLinter::lint_in_zone("30 1 * * *", &New_York, 2025).unwrap();
+        assert_eq!(
+            warnings[0].message,
+            "On 2025-11-02, the run at 01:30 is repeated by the daylight saving change and will only run the first time."
+        );
+
+        assert_eq!(
+            CronLinter::lint_in_zone("30 2 * * *", &Tokyo, 2025).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(CronLinter::lint("0 24 * * *").is_err());
+    }
+}
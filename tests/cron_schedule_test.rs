@@ -0,0 +1,267 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use natural_cron::interfaces::CronTimeUnit;
+    use natural_cron::{CronExpressionBuilder, CronSchedule};
+
+    use crate::common::at;
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn parses_five_field_expression() {
+            let schedule = CronSchedule::parse("30 8 * * 1-5").unwrap();
+            assert!(!schedule.has_seconds());
+            assert_eq!(schedule.to_string(), "30 8 * * 1-5");
+        }
+
+        #[test]
+        fn parses_six_field_expression() {
+            let schedule = CronSchedule::parse("*/30 0 9 * * *").unwrap();
+            assert!(schedule.has_seconds());
+            assert_eq!(schedule.to_string(), "0,30 0 9 * * *");
+        }
+
+        #[test]
+        fn accepts_names_and_sunday_as_seven() {
+            let schedule = CronSchedule::parse("0 0 * JAN,jul SAT-7").unwrap();
+            assert_eq!(schedule.to_string(), "0 0 * 1,7 0,6");
+        }
+
+        #[test]
+        fn rejects_wrong_field_count() {
+            assert_eq!(
+                CronSchedule::parse("* * *"),
+                Err("Invalid cron expression: '* * *'. Expected 5 or 6 fields.".to_string())
+            );
+        }
+
+        #[test]
+        fn rejects_out_of_range_values() {
+            assert_eq!(
+                CronSchedule::parse("60 * * * *"),
+                Err("Invalid minute: '60'. Value should be between 0 and 59.".to_string())
+            );
+        }
+
+        #[test]
+        fn rejects_zero_step() {
+            assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        }
+
+        #[test]
+        fn builds_from_builder() {
+            let builder = CronExpressionBuilder::new().at_time("08:00").unwrap();
+            let schedule = CronSchedule::from_builder(&builder).unwrap();
+            assert_eq!(schedule.to_string(), "0 8 * * *");
+        }
+
+        #[test]
+        fn builder_schedule_matches_parsed_expression() {
+            let builders = vec![
+                CronExpressionBuilder::new().every("week").unwrap(),
+                CronExpressionBuilder::new()
+                    .every_x(15, CronTimeUnit::Minute)
+                    .unwrap()
+                    .at_hours(vec![9, 10, 11])
+                    .unwrap(),
+                CronExpressionBuilder::new()
+                    .on_days_of_month(vec![1, 15])
+                    .unwrap()
+                    .on_week_days(vec![5])
+                    .unwrap(),
+                CronExpressionBuilder::from_expression("*/10 9-17 ? JAN-MAR MON-FRI").unwrap(),
+            ];
+            for builder in builders {
+                let parsed = CronSchedule::parse(&builder.clone().compile()).unwrap();
+                assert_eq!(CronSchedule::from_builder(&builder).unwrap(), parsed);
+            }
+        }
+
+        #[test]
+        fn rejects_builder_with_zero_step() {
+            let builder = CronExpressionBuilder::new()
+                .every_x(0, CronTimeUnit::Minute)
+                .unwrap();
+            assert!(CronSchedule::from_builder(&builder).is_err());
+        }
+    }
+
+    mod parse_const {
+        use natural_cron::schedules;
+
+        use super::*;
+
+        const OFFICE_HOURS: CronSchedule = CronSchedule::parse_const("*/15 9-17 * * MON-FRI");
+
+        #[test]
+        fn matches_runtime_parsing() {
+            assert_eq!(
+                OFFICE_HOURS,
+                CronSchedule::parse("*/15 9-17 * * MON-FRI").unwrap()
+            );
+            for expression in ["0 0 1,15 * 1", "*/10 * * * * *", "5/20 * * jan-mar 7"] {
+                assert_eq!(
+                    CronSchedule::parse_const(expression),
+                    CronSchedule::parse(expression).unwrap(),
+                    "{}",
+                    expression
+                );
+            }
+        }
+
+        #[test]
+        fn evaluates_schedule_constants_directly() {
+            // 2025-03-08 is a Saturday
+            assert_eq!(
+                schedules::EVERY_WEEKDAY
+                    .schedule()
+                    .next_after(&at(2025, 3, 8, 12, 0)),
+                Some(at(2025, 3, 10, 0, 0))
+            );
+            assert_eq!(
+                schedules::EVERY_2_HOURS
+                    .schedule()
+                    .next_after(&at(2025, 3, 8, 12, 30)),
+                Some(at(2025, 3, 8, 14, 0))
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "bad hour field")]
+        fn panics_on_invalid_expressions() {
+            CronSchedule::parse_const("0 24 * * *");
+        }
+    }
+
+    mod next_after {
+        use super::*;
+
+        #[test]
+        fn finds_next_time_later_the_same_day() {
+            let schedule = CronSchedule::parse("30 17 * * *").unwrap();
+            assert_eq!(
+                schedule.next_after(&at(2025, 3, 10, 9, 0)),
+                Some(at(2025, 3, 10, 17, 30))
+            );
+        }
+
+        #[test]
+        fn is_strictly_after_the_given_time() {
+            let schedule = CronSchedule::parse("30 17 * * *").unwrap();
+            assert_eq!(
+                schedule.next_after(&at(2025, 3, 10, 17, 30)),
+                Some(at(2025, 3, 11, 17, 30))
+            );
+        }
+
+        #[test]
+        fn skips_to_matching_weekday() {
+            // 2025-03-08 is a Saturday
+            let schedule = CronSchedule::parse("0 9 * * 1-5").unwrap();
+            assert_eq!(
+                schedule.next_after(&at(2025, 3, 8, 10, 0)),
+                Some(at(2025, 3, 10, 9, 0))
+            );
+        }
+
+        #[test]
+        fn rolls_over_the_year() {
+            let schedule = CronSchedule::parse("0 0 1 1 *").unwrap();
+            assert_eq!(
+                schedule.next_after(&at(2025, 6, 1, 0, 0)),
+                Some(at(2026, 1, 1, 0, 0))
+            );
+        }
+
+        #[test]
+        fn finds_leap_day() {
+            let schedule = CronSchedule::parse("0 0 29 2 *").unwrap();
+            assert_eq!(
+                schedule.next_after(&at(2025, 1, 1, 0, 0)),
+                Some(at(2028, 2, 29, 0, 0))
+            );
+        }
+
+        #[test]
+        fn uses_or_semantics_when_both_day_fields_are_set() {
+            // The 15th or any Monday; 2025-03-10 is a Monday
+            let schedule = CronSchedule::parse("0 0 15 * 1").unwrap();
+            assert_eq!(
+                schedule.next_after(&at(2025, 3, 8, 0, 0)),
+                Some(at(2025, 3, 10, 0, 0))
+            );
+        }
+
+        #[test]
+        fn returns_none_when_schedule_never_fires() {
+            let schedule = CronSchedule::parse("0 0 30 2 *").unwrap();
+            assert_eq!(schedule.next_after(&at(2025, 1, 1, 0, 0)), None);
+        }
+
+        #[test]
+        fn lists_upcoming_occurrences() {
+            let schedule = CronSchedule::parse("*/20 * * * *").unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2025, 3, 10, 9, 50), 3),
+                vec![
+                    at(2025, 3, 10, 10, 0),
+                    at(2025, 3, 10, 10, 20),
+                    at(2025, 3, 10, 10, 40)
+                ]
+            );
+        }
+
+        #[test]
+        fn stops_listing_when_schedule_never_fires() {
+            let schedule = CronSchedule::parse("0 0 30 2 *").unwrap();
+            assert!(
+                schedule
+                    .upcoming(&at(2025, 1, 1, 0, 0), usize::MAX)
+                    .is_empty()
+            );
+        }
+    }
+
+    mod next_after_in {
+        use chrono::{FixedOffset, TimeZone};
+        use chrono_tz::America::New_York;
+
+        use super::*;
+
+        #[test]
+        fn evaluates_in_the_zone_of_the_given_time() {
+            let zone = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+            let after = zone.with_ymd_and_hms(2025, 3, 10, 8, 0, 0).unwrap();
+            let schedule = CronSchedule::parse("0 9 * * *").unwrap();
+            assert_eq!(
+                schedule.next_after_in(&after),
+                Some(zone.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap())
+            );
+        }
+
+        #[test]
+        fn skips_times_lost_to_daylight_saving() {
+            // 02:30 does not exist in New York on 2025-03-09
+            let after = New_York.with_ymd_and_hms(2025, 3, 8, 12, 0, 0).unwrap();
+            let schedule = CronSchedule::parse("30 2 * * *").unwrap();
+            assert_eq!(
+                schedule.next_after_in(&after),
+                Some(New_York.with_ymd_and_hms(2025, 3, 10, 2, 30, 0).unwrap())
+            );
+        }
+
+        #[test]
+        fn fires_once_in_a_repeated_hour() {
+            // 01:30 happens twice in New York on 2025-11-02
+            let after = New_York.with_ymd_and_hms(2025, 11, 2, 0, 0, 0).unwrap();
+            let schedule = CronSchedule::parse("30 1 * * *").unwrap();
+            let first = schedule.next_after_in(&after).unwrap();
+            assert_eq!(first.naive_local(), at(2025, 11, 2, 1, 30));
+            let second = schedule.next_after_in(&first).unwrap();
+            assert_eq!(second.naive_local(), at(2025, 11, 3, 1, 30));
+        }
+    }
+}
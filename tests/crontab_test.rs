@@ -0,0 +1,254 @@
+#[cfg(test)]
+mod tests {
+    use natural_cron::CronExpressionBuilder;
+    use natural_cron::crontab::{
+        Crontab, CrontabEntry, CrontabLine, CrontabMacro, CrontabSchedule,
+    };
+
+    const USER_CRONTAB: &str = "\
+# m h dom mon dow command
+SHELL=/bin/bash
+MAILTO = ops@example.com
+
+*/15 9-17 * * 1-5 /usr/bin/check.sh > /dev/null 2>&1
+@reboot   /usr/bin/start-agent
+0 2 * * * tar -czf /backup/$(date +\\%F).tgz /srv
+";
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn keeps_every_line_in_order() {
+            let crontab = Crontab::parse(USER_CRONTAB).unwrap();
+            assert_eq!(crontab.lines.len(), 7);
+            assert_eq!(
+                crontab.lines[0],
+                CrontabLine::Comment("# m h dom mon dow command".to_string())
+            );
+            assert_eq!(crontab.lines[3], CrontabLine::Blank);
+            assert_eq!(crontab.entries().count(), 3);
+        }
+
+        #[test]
+        fn reads_environment_assignments() {
+            let crontab = Crontab::parse(USER_CRONTAB).unwrap();
+            assert_eq!(crontab.environment("SHELL"), Some("/bin/bash"));
+            assert_eq!(crontab.environment("MAILTO"), Some("ops@example.com"));
+            assert_eq!(crontab.environment("PATH"), None);
+        }
+
+        #[test]
+        fn turns_schedules_into_builders() {
+            let crontab = Crontab::parse(USER_CRONTAB).unwrap();
+            let entries: Vec<&CrontabEntry> = crontab.entries().collect();
+            assert_eq!(
+                entries[0].schedule.to_builder().unwrap().compile(),
+                "*/15 9-17 * * 1-5"
+            );
+            assert_eq!(entries[0].command, "/usr/bin/check.sh > /dev/null 2>&1");
+            assert_eq!(
+                entries[1].schedule,
+                CrontabSchedule::Macro(CrontabMacro::Reboot)
+            );
+            assert_eq!(entries[1].schedule.to_builder(), None);
+            assert_eq!(
+                entries[2].command,
+                "tar -czf /backup/$(date +\\%F).tgz /srv"
+            );
+        }
+
+        #[test]
+        fn expands_macros_to_builders() {
+            let expected = [
+                ("@yearly", "0 0 1 1 *"),
+                ("@annually", "0 0 1 1 *"),
+                ("@monthly", "0 0 1 * *"),
+                ("@weekly", "0 0 * * 0"),
+                ("@daily", "0 0 * * *"),
+                ("@midnight", "0 0 * * *"),
+                ("@hourly", "0 * * * *"),
+            ];
+            for (shorthand, expression) in expected {
+                let builder = CrontabMacro::parse(shorthand)
+                    .unwrap()
+                    .to_builder()
+                    .unwrap();
+                assert_eq!(builder.compile(), expression, "{}", shorthand);
+            }
+        }
+
+        #[test]
+        fn reads_the_user_column_of_system_crontabs() {
+            let crontab =
+                Crontab::parse_system("17 * * * * root cd / && run-parts /etc/cron.hourly\n")
+                    .unwrap();
+            let entry = crontab.entries().next().unwrap();
+            assert_eq!(entry.user.as_deref(), Some("root"));
+            assert_eq!(entry.command, "cd / && run-parts /etc/cron.hourly");
+            assert!(crontab.has_user_column());
+        }
+
+        #[test]
+        fn reports_the_offending_line() {
+            assert_eq!(
+                Crontab::parse("# ok\n61 * * * * job\n").err(),
+                Some(
+                    "Invalid crontab line 2: Invalid minute: '61'. Value should be between 0 and 59."
+                        .to_string()
+                )
+            );
+            assert_eq!(
+                Crontab::parse("@sometimes job\n").err(),
+                Some("Invalid crontab line 1: Invalid crontab macro: @sometimes".to_string())
+            );
+            assert!(Crontab::parse("* * * * *\n").is_err());
+        }
+    }
+
+    mod write {
+        use super::*;
+
+        #[test]
+        fn round_trips_comments_and_ordering() {
+            let crontab = Crontab::parse(USER_CRONTAB).unwrap();
+            assert_eq!(
+                crontab.render().unwrap(),
+                "\
+# m h dom mon dow command
+SHELL=/bin/bash
+MAILTO = ops@example.com
+
+*/15 9-17 * * 1-5 /usr/bin/check.sh > /dev/null 2>&1
+@reboot /usr/bin/start-agent
+0 2 * * * tar -czf /backup/$(date +\\%F).tgz /srv
+"
+            );
+            assert_eq!(Crontab::parse(&crontab.render().unwrap()).unwrap(), crontab);
+        }
+
+        #[test]
+        fn keeps_comments_and_assignments_as_written() {
+            let text = "  # indented  \nPATH = /usr/bin:/bin\n";
+            let mut crontab = Crontab::parse(text).unwrap();
+            assert_eq!(crontab.render().unwrap(), text);
+
+            // An edited assignment is written out afresh
+            if let CrontabLine::Environment { value, .. } = &mut crontab.lines[1] {
+                *value = "/usr/local/bin".to_string();
+            }
+            assert_eq!(
+                crontab.render().unwrap(),
+                "  # indented  \nPATH=/usr/local/bin\n"
+            );
+        }
+
+        #[test]
+        fn writes_edited_entries() {
+            let mut crontab = Crontab::parse("# backup\n0 2 * * * backup.sh\n").unwrap();
+            for entry in crontab.entries_mut() {
+                entry.schedule = CrontabSchedule::Expression(
+                    CronExpressionBuilder::new()
+                        .at_time("03:30")
+                        .and_then(|s| s.on_week_days(vec![1, 2, 3, 4, 5]))
+                        .unwrap(),
+                );
+            }
+            crontab
+                .add_entry(CrontabEntry {
+                    schedule: CrontabSchedule::Macro(CrontabMacro::Hourly),
+                    user: None,
+                    command: "rotate.sh".to_string(),
+                })
+                .unwrap();
+            assert_eq!(
+                crontab.render().unwrap(),
+                "# backup\n30 3 * * 1-5 backup.sh\n@hourly rotate.sh\n"
+            );
+        }
+
+        #[test]
+        fn writes_one_line_per_group_of_times() {
+            let mut crontab = Crontab::new();
+            crontab
+                .add_entry(CrontabEntry {
+                    schedule: CrontabSchedule::Expression(
+                        CronExpressionBuilder::new()
+                            .at_times(vec!["08:00", "17:30"])
+                            .unwrap(),
+                    ),
+                    user: None,
+                    command: "sync.sh".to_string(),
+                })
+                .unwrap();
+            assert_eq!(
+                crontab.render().unwrap(),
+                "0 8 * * * sync.sh\n30 17 * * * sync.sh\n"
+            );
+        }
+
+        #[test]
+        fn rejects_schedules_cron_cannot_express() {
+            let last_business_day = CrontabSchedule::Expression(
+                CronExpressionBuilder::new()
+                    .at_time("18:00")
+                    .and_then(|s| s.on_last_business_day())
+                    .unwrap(),
+            );
+            let message = "Cannot express the last business day of the month in a cron expression; it requires a runtime filter such as BusinessDaySchedule.";
+
+            let mut crontab = Crontab::new();
+            let entry = CrontabEntry {
+                schedule: last_business_day.clone(),
+                user: None,
+                command: "close-books.sh".to_string(),
+            };
+            assert_eq!(crontab.add_entry(entry), Err(message.to_string()));
+
+            let mut crontab = Crontab::parse("# books\n0 18 * * * close-books.sh\n").unwrap();
+            for entry in crontab.entries_mut() {
+                entry.schedule = last_business_day.clone();
+            }
+            assert_eq!(
+                crontab.render(),
+                Err(format!("Cannot write crontab line 2: {}", message))
+            );
+        }
+
+        #[test]
+        fn reports_entries_edited_to_need_a_runtime_filter() {
+            let mut crontab = Crontab::parse("0 2 * * * backup.sh\n").unwrap();
+            for entry in crontab.entries_mut() {
+                entry.schedule = CrontabSchedule::Expression(
+                    CronExpressionBuilder::new()
+                        .at_time("02:00")
+                        .and_then(|s| s.on_business_day(1))
+                        .unwrap(),
+                );
+            }
+            let entry = crontab.entries().next().unwrap();
+            assert!(entry.schedule.to_expression().is_err());
+            assert!(entry.schedule.to_expressions().is_err());
+            assert!(
+                crontab
+                    .render()
+                    .unwrap_err()
+                    .starts_with("Cannot write crontab line 1: ")
+            );
+        }
+
+        #[test]
+        fn requires_matching_user_column() {
+            let mut crontab = Crontab::new();
+            let entry = CrontabEntry {
+                schedule: CrontabSchedule::Macro(CrontabMacro::Daily),
+                user: Some("root".to_string()),
+                command: "job".to_string(),
+            };
+            assert_eq!(
+                crontab.add_entry(entry),
+                Err("User crontab entries cannot have a user.".to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,113 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use natural_cron::Schedule;
+    use natural_cron::interfaces::CronTimeUnit;
+    use natural_cron::interval::IntervalSchedule;
+
+    use crate::common::at;
+
+    mod constructors {
+        use super::*;
+
+        #[test]
+        fn rejects_periods_shorter_than_a_second() {
+            let message =
+                "Invalid interval: the period should be a whole number of seconds, at least one.";
+            let start = at(2025, 1, 1, 0, 0);
+            assert_eq!(
+                IntervalSchedule::new(start, Duration::zero()),
+                Err(message.to_string())
+            );
+            assert!(IntervalSchedule::new(start, Duration::minutes(-5)).is_err());
+            assert!(IntervalSchedule::new(start, Duration::milliseconds(1500)).is_err());
+        }
+
+        #[test]
+        fn converts_time_units() {
+            let start = at(2025, 1, 1, 0, 0);
+            assert_eq!(
+                IntervalSchedule::every(7, CronTimeUnit::Minute, start)
+                    .unwrap()
+                    .period(),
+                Duration::minutes(7)
+            );
+            assert_eq!(
+                IntervalSchedule::every(10, CronTimeUnit::DayOfMonth, start)
+                    .unwrap()
+                    .period(),
+                Duration::days(10)
+            );
+            assert_eq!(
+                IntervalSchedule::every(2, CronTimeUnit::Month, start),
+                Err(
+                    "Invalid interval unit: months differ in length, so they have no fixed period."
+                        .to_string()
+                )
+            );
+        }
+    }
+
+    mod evaluation {
+        use super::*;
+
+        #[test]
+        fn keeps_the_gap_across_hours() {
+            let schedule =
+                IntervalSchedule::every(7, CronTimeUnit::Minute, at(2025, 1, 1, 0, 0)).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2025, 1, 1, 0, 50), 3),
+                vec![
+                    at(2025, 1, 1, 0, 56),
+                    at(2025, 1, 1, 1, 3),
+                    at(2025, 1, 1, 1, 10)
+                ]
+            );
+            assert!(schedule.matches(&at(2025, 1, 1, 1, 3)));
+            assert!(!schedule.matches(&at(2025, 1, 1, 1, 0)));
+        }
+
+        #[test]
+        fn keeps_the_gap_across_months() {
+            let schedule =
+                IntervalSchedule::new(at(2025, 1, 20, 9, 0), Duration::days(14)).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2025, 1, 20, 9, 0), 3),
+                vec![
+                    at(2025, 2, 3, 9, 0),
+                    at(2025, 2, 17, 9, 0),
+                    at(2025, 3, 3, 9, 0)
+                ]
+            );
+        }
+
+        #[test]
+        fn starts_at_the_anchor() {
+            let schedule =
+                IntervalSchedule::new(at(2025, 6, 1, 12, 0), Duration::hours(5)).unwrap();
+            assert_eq!(
+                schedule.next_after(&at(2025, 1, 1, 0, 0)),
+                Some(at(2025, 6, 1, 12, 0))
+            );
+            assert!(!schedule.matches(&at(2025, 6, 1, 7, 0)));
+        }
+
+        #[test]
+        fn works_as_a_schedule() {
+            let schedule =
+                IntervalSchedule::new(at(2025, 1, 1, 0, 0), Duration::hours(10)).unwrap();
+            let schedule: &dyn Schedule = &schedule;
+            assert_eq!(
+                schedule.occurrences_between(&at(2025, 1, 1, 0, 0), &at(2025, 1, 2, 6, 0)),
+                vec![
+                    at(2025, 1, 1, 10, 0),
+                    at(2025, 1, 1, 20, 0),
+                    at(2025, 1, 2, 6, 0)
+                ]
+            );
+            assert_eq!(schedule.resolution(), Duration::minutes(1));
+        }
+    }
+}
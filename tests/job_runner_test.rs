@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use natural_cron::job_runner::{JobMetrics, JobRunner, OverlapPolicy, Task};
+
+    fn counting_task(counter: &Arc<AtomicUsize>) -> Task {
+        let counter = Arc::clone(counter);
+        Arc::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    #[test]
+    fn rejects_zero_parallel_runs() {
+        let result = JobRunner::new(Arc::new(|| {}), OverlapPolicy::AllowParallel { max: 0 });
+        assert_eq!(
+            result.err(),
+            Some("Invalid parallel run limit: 0. At least one run must be allowed.".to_string())
+        );
+    }
+
+    #[test]
+    fn skip_if_running_drops_overlapping_runs() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let runner = JobRunner::new(counting_task(&counter), OverlapPolicy::SkipIfRunning).unwrap();
+
+        assert!(runner.admit());
+        assert!(!runner.admit());
+        assert!(!runner.admit());
+        runner.run();
+
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+        assert_eq!(runner.running(), 0);
+        assert_eq!(
+            runner.metrics(),
+            JobMetrics {
+                started: 1,
+                completed: 1,
+                skipped: 2,
+                ..JobMetrics::default()
+            }
+        );
+    }
+
+    #[test]
+    fn queue_one_runs_a_single_queued_run_afterwards() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let runner = JobRunner::new(counting_task(&counter), OverlapPolicy::QueueOne).unwrap();
+
+        assert!(runner.admit());
+        assert!(!runner.admit());
+        assert!(!runner.admit());
+        runner.run();
+
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+        assert_eq!(runner.running(), 0);
+        let metrics = runner.metrics();
+        assert_eq!(metrics.queued, 1);
+        assert_eq!(metrics.skipped, 1);
+        assert_eq!(metrics.completed, 2);
+    }
+
+    #[test]
+    fn allow_parallel_admits_up_to_the_limit() {
+        let runner =
+            JobRunner::new(Arc::new(|| {}), OverlapPolicy::AllowParallel { max: 2 }).unwrap();
+
+        assert!(runner.admit());
+        assert!(runner.admit());
+        assert!(!runner.admit());
+        assert_eq!(runner.running(), 2);
+        runner.run();
+        assert!(runner.admit());
+        assert_eq!(runner.metrics().skipped, 1);
+    }
+
+    #[test]
+    fn counts_panicking_runs_as_failed() {
+        let runner = JobRunner::new(
+            Arc::new(|| panic!("job failed")),
+            OverlapPolicy::SkipIfRunning,
+        )
+        .unwrap();
+        assert!(runner.admit());
+        runner.run();
+        assert_eq!(runner.metrics().failed, 1);
+        assert_eq!(runner.running(), 0);
+    }
+
+    #[test]
+    fn trigger_runs_on_a_new_thread() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let runner =
+            Arc::new(JobRunner::new(counting_task(&counter), OverlapPolicy::default()).unwrap());
+        assert!(runner.trigger());
+        while runner.running() > 0 {
+            std::thread::yield_now();
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+}
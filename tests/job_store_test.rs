@@ -0,0 +1,177 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use chrono::NaiveDateTime;
+    use natural_cron::CronExpressionBuilder;
+    use natural_cron::job_store::{FileJobStore, InMemoryJobStore, JobState, JobStore};
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        crate::common::at(2025, 3, day, hour, minute)
+    }
+
+    fn temp_store_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("natural-cron-{}-{}.jobs", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn state(last_run: NaiveDateTime, next_run: NaiveDateTime) -> JobState {
+        JobState {
+            last_run: Some(last_run),
+            next_run: Some(next_run),
+            first_seen: None,
+        }
+    }
+
+    mod in_memory {
+        use super::*;
+
+        #[test]
+        fn saves_loads_and_removes_states() {
+            let store = InMemoryJobStore::new();
+            assert_eq!(store.load("report"), Ok(None));
+
+            store
+                .save("report", &state(at(10, 8, 0), at(11, 8, 0)))
+                .unwrap();
+            store.save("backup", &JobState::default()).unwrap();
+            assert_eq!(
+                store.load("report"),
+                Ok(Some(state(at(10, 8, 0), at(11, 8, 0))))
+            );
+            assert_eq!(
+                store.jobs(),
+                Ok(vec!["backup".to_string(), "report".to_string()])
+            );
+
+            store.remove("report").unwrap();
+            assert_eq!(store.load("report"), Ok(None));
+        }
+    }
+
+    mod file {
+        use super::*;
+
+        #[test]
+        fn persists_states_across_instances() {
+            let path = temp_store_path("persist");
+            let store = FileJobStore::new(&path);
+            store
+                .save("report", &state(at(10, 8, 0), at(11, 8, 0)))
+                .unwrap();
+            store
+                .save(
+                    "backup",
+                    &JobState {
+                        last_run: None,
+                        next_run: Some(at(11, 2, 0)),
+                        first_seen: Some(at(10, 23, 0)),
+                    },
+                )
+                .unwrap();
+            store
+                .save("report", &state(at(11, 8, 0), at(12, 8, 0)))
+                .unwrap();
+
+            let reopened = FileJobStore::new(&path);
+            assert_eq!(
+                reopened.load("report"),
+                Ok(Some(state(at(11, 8, 0), at(12, 8, 0))))
+            );
+            assert_eq!(
+                reopened.jobs(),
+                Ok(vec!["report".to_string(), "backup".to_string()])
+            );
+            assert_eq!(
+                fs::read_to_string(&path).unwrap(),
+                "report\t2025-03-11T08:00:00\t2025-03-12T08:00:00\t-\nbackup\t-\t2025-03-11T02:00:00\t2025-03-10T23:00:00\n"
+            );
+            fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn treats_missing_file_as_empty() {
+            let store = FileJobStore::new(temp_store_path("missing"));
+            assert_eq!(store.load("report"), Ok(None));
+            assert_eq!(store.jobs(), Ok(vec![]));
+        }
+
+        #[test]
+        fn rejects_names_that_break_the_format() {
+            let store = FileJobStore::new(temp_store_path("names"));
+            assert!(store.save("bad\tname", &JobState::default()).is_err());
+            assert!(store.save("", &JobState::default()).is_err());
+        }
+
+        #[test]
+        fn reads_lines_without_first_seen() {
+            let path = temp_store_path("three-columns");
+            fs::write(&path, "report\t2025-03-11T08:00:00\t2025-03-12T08:00:00\n").unwrap();
+            let store = FileJobStore::new(&path);
+            assert_eq!(
+                store.load("report"),
+                Ok(Some(state(at(11, 8, 0), at(12, 8, 0))))
+            );
+            fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn reports_corrupt_lines() {
+            let path = temp_store_path("corrupt");
+            fs::write(&path, "report\tyesterday\t-\n").unwrap();
+            let store = FileJobStore::new(&path);
+            assert_eq!(
+                store.load("report"),
+                Err("Invalid timestamp on job store line 1: 'yesterday'".to_string())
+            );
+            fs::remove_file(&path).unwrap();
+        }
+    }
+
+    mod missed_occurrences {
+        use super::*;
+
+        #[test]
+        fn lists_runs_missed_since_last_run() {
+            let store = InMemoryJobStore::new();
+            store
+                .save("report", &state(at(10, 8, 0), at(11, 8, 0)))
+                .unwrap();
+            let schedule = CronExpressionBuilder::new().at_time("08:00").unwrap();
+            assert_eq!(
+                store.missed_occurrences("report", &schedule, &at(13, 9, 0)),
+                Ok(vec![at(11, 8, 0), at(12, 8, 0), at(13, 8, 0)])
+            );
+        }
+
+        #[test]
+        fn lists_runs_missed_since_first_seen() {
+            let store = InMemoryJobStore::new();
+            let first_seen = JobState {
+                first_seen: Some(at(11, 9, 0)),
+                ..JobState::default()
+            };
+            store.save("report", &first_seen).unwrap();
+            let schedule = CronExpressionBuilder::new().at_time("08:00").unwrap();
+            assert_eq!(
+                store.missed_occurrences("report", &schedule, &at(13, 9, 0)),
+                Ok(vec![at(12, 8, 0), at(13, 8, 0)])
+            );
+        }
+
+        #[test]
+        fn unknown_job_missed_nothing() {
+            let store = InMemoryJobStore::new();
+            let schedule = CronExpressionBuilder::new().at_time("08:00").unwrap();
+            assert_eq!(
+                store.missed_occurrences("report", &schedule, &at(13, 9, 0)),
+                Ok(vec![])
+            );
+        }
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use natural_cron::CronSchedule;
+    use natural_cron::interval::IntervalSchedule;
+    use natural_cron::misfire::{MisfireOutcome, MisfirePolicy};
+
+    fn at(hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, 10)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+    }
+
+    fn hourly() -> CronSchedule {
+        CronSchedule::parse("0 * * * *").unwrap()
+    }
+
+    #[test]
+    fn nothing_to_do_when_nothing_is_due() {
+        let outcome = MisfirePolicy::FireAll.resolve(&hourly(), &at(8, 0, 0), &at(8, 30, 0));
+        assert_eq!(
+            outcome,
+            MisfireOutcome {
+                fire: vec![],
+                skipped: 0,
+                next_run: Some(at(9, 0, 0)),
+            }
+        );
+    }
+
+    #[test]
+    fn on_time_occurrence_always_fires() {
+        for policy in [
+            MisfirePolicy::FireOnceNow,
+            MisfirePolicy::FireAll,
+            MisfirePolicy::SkipToNext,
+            MisfirePolicy::FireWithinGrace(Duration::zero()),
+        ] {
+            let outcome = policy.resolve(&hourly(), &at(8, 0, 0), &at(9, 0, 5));
+            assert_eq!(outcome.fire, vec![at(9, 0, 0)], "{:?}", policy);
+        }
+    }
+
+    #[test]
+    fn fire_once_now_coalesces_missed_runs() {
+        let outcome = MisfirePolicy::FireOnceNow.resolve(&hourly(), &at(5, 0, 0), &at(8, 30, 0));
+        assert_eq!(outcome.fire, vec![at(8, 0, 0)]);
+        assert_eq!(outcome.skipped, 2);
+        assert_eq!(outcome.next_run, Some(at(9, 0, 0)));
+    }
+
+    #[test]
+    fn fire_all_replays_every_missed_run() {
+        let outcome = MisfirePolicy::FireAll.resolve(&hourly(), &at(5, 0, 0), &at(8, 30, 0));
+        assert_eq!(outcome.fire, vec![at(6, 0, 0), at(7, 0, 0), at(8, 0, 0)]);
+        assert_eq!(outcome.skipped, 0);
+    }
+
+    #[test]
+    fn skip_to_next_drops_missed_runs() {
+        let outcome = MisfirePolicy::SkipToNext.resolve(&hourly(), &at(5, 0, 0), &at(8, 30, 0));
+        assert!(outcome.fire.is_empty());
+        assert_eq!(outcome.skipped, 3);
+        assert_eq!(outcome.next_run, Some(at(9, 0, 0)));
+    }
+
+    #[test]
+    fn fire_within_grace_fires_recent_miss() {
+        let policy = MisfirePolicy::FireWithinGrace(Duration::minutes(45));
+        let outcome = policy.resolve(&hourly(), &at(5, 0, 0), &at(8, 30, 0));
+        assert_eq!(outcome.fire, vec![at(8, 0, 0)]);
+        assert_eq!(outcome.skipped, 2);
+    }
+
+    #[test]
+    fn fire_within_grace_skips_old_miss() {
+        let policy = MisfirePolicy::FireWithinGrace(Duration::minutes(15));
+        let outcome = policy.resolve(&hourly(), &at(5, 0, 0), &at(8, 30, 0));
+        assert!(outcome.fire.is_empty());
+        assert_eq!(outcome.skipped, 3);
+    }
+
+    #[test]
+    fn seconds_schedules_use_a_one_second_slot() {
+        let schedule = CronSchedule::parse("0 * * * * *").unwrap();
+        let outcome = MisfirePolicy::SkipToNext.resolve(&schedule, &at(8, 0, 30), &at(8, 1, 2));
+        assert!(outcome.fire.is_empty());
+        assert_eq!(outcome.skipped, 1);
+    }
+
+    #[test]
+    fn counts_a_long_downtime_of_a_seconds_schedule() {
+        let every_second = CronSchedule::parse("* * * * * *").unwrap();
+        let outcome =
+            MisfirePolicy::FireOnceNow.resolve(&every_second, &at(0, 0, 0), &at(23, 0, 0));
+        assert_eq!(outcome.fire, vec![at(23, 0, 0)]);
+        assert_eq!(outcome.skipped, 23 * 3600 - 1);
+    }
+
+    #[test]
+    fn resolves_interval_schedules() {
+        let every_40_minutes = IntervalSchedule::new(at(5, 0, 0), Duration::minutes(40)).unwrap();
+        let outcome =
+            MisfirePolicy::FireAll.resolve(&every_40_minutes, &at(6, 0, 0), &at(8, 0, 30));
+        assert_eq!(outcome.fire, vec![at(6, 20, 0), at(7, 0, 0), at(7, 40, 0)]);
+        assert_eq!(outcome.next_run, Some(at(8, 20, 0)));
+    }
+}
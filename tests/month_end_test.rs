@@ -0,0 +1,129 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use natural_cron::calendar::BusinessDaySchedule;
+    use natural_cron::cron_lint::CronLinter;
+    use natural_cron::month_end::MonthEndSchedule;
+    use natural_cron::quartz::QuartzConverter;
+    use natural_cron::{CronExpressionBuilder, CronSchedule};
+
+    use crate::common::{at, date};
+
+    fn billing(days: i32) -> CronExpressionBuilder {
+        CronExpressionBuilder::new()
+            .at_time("06:00")
+            .unwrap()
+            .on_days_before_month_end(days)
+            .unwrap()
+    }
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn requires_a_runtime_filter() {
+            let builder = billing(3);
+            assert!(builder.requires_runtime_filter());
+            assert_eq!(
+                builder.validate(),
+                Err("Cannot express 3 days before the end of the month in a cron expression; it requires a runtime filter such as MonthEndSchedule.".to_string())
+            );
+            assert_eq!(
+                builder.clone().try_compile(),
+                Err("Cannot express 3 days before the end of the month in a cron expression; it requires a runtime filter such as MonthEndSchedule.".to_string())
+            );
+            assert!(CronSchedule::from_builder(&builder).is_err());
+            assert!(CronLinter::lint_builder(&builder).is_err());
+        }
+
+        #[test]
+        fn rejects_days_outside_february() {
+            assert_eq!(
+                CronExpressionBuilder::new().on_days_before_month_end(28),
+                Err(
+                    "Invalid days before month end: 28. Days should be between 0 and 27."
+                        .to_string()
+                )
+            );
+            assert!(
+                CronExpressionBuilder::new()
+                    .on_days_before_month_end(-1)
+                    .is_err()
+            );
+        }
+    }
+
+    mod evaluation {
+        use super::*;
+
+        #[test]
+        fn follows_the_length_of_each_month() {
+            let schedule = MonthEndSchedule::from_builder(&billing(3)).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2025, 1, 1, 0, 0), 4),
+                vec![
+                    at(2025, 1, 28, 6, 0),
+                    at(2025, 2, 25, 6, 0),
+                    at(2025, 3, 28, 6, 0),
+                    at(2025, 4, 27, 6, 0)
+                ]
+            );
+            // 2024 is a leap year
+            assert_eq!(
+                schedule.next_after(&at(2024, 2, 1, 0, 0)),
+                Some(at(2024, 2, 26, 6, 0))
+            );
+        }
+
+        #[test]
+        fn runs_on_the_last_day_for_zero_days() {
+            let schedule = MonthEndSchedule::new(CronSchedule::parse("30 23 * * *").unwrap(), 0);
+            assert!(schedule.matches_date(&date(2025, 2, 28)));
+            assert!(schedule.matches_date(&date(2024, 2, 29)));
+            assert!(!schedule.matches_date(&date(2024, 2, 28)));
+            assert_eq!(
+                schedule.next_after(&at(2025, 4, 30, 23, 30)),
+                Some(at(2025, 5, 31, 23, 30))
+            );
+        }
+
+        #[test]
+        fn combines_with_other_fields() {
+            let builder = billing(27).during_months(vec![2, 3]).unwrap();
+            let schedule = MonthEndSchedule::from_builder(&builder).unwrap();
+            assert_eq!(
+                schedule.upcoming(&at(2025, 1, 1, 0, 0), 3),
+                vec![
+                    at(2025, 2, 1, 6, 0),
+                    at(2025, 3, 4, 6, 0),
+                    at(2026, 2, 1, 6, 0)
+                ]
+            );
+        }
+
+        #[test]
+        fn combines_with_a_business_day() {
+            // 2025-05-31 is a Saturday
+            let builder = billing(0).on_last_business_day().unwrap();
+            let expected = vec![
+                at(2025, 3, 31, 6, 0),
+                at(2025, 4, 30, 6, 0),
+                at(2025, 6, 30, 6, 0),
+            ];
+            let schedule = MonthEndSchedule::from_builder(&builder).unwrap();
+            assert_eq!(schedule.upcoming(&at(2025, 3, 1, 0, 0), 3), expected);
+            let schedule = BusinessDaySchedule::from_builder(&builder).unwrap();
+            assert_eq!(schedule.upcoming(&at(2025, 3, 1, 0, 0), 3), expected);
+            assert_eq!(
+                QuartzConverter::from_builder(&builder),
+                Err("Cannot convert the last day of the month to Quartz: its day of month cannot also hold the last business day of the month.".to_string())
+            );
+        }
+
+        #[test]
+        fn needs_days_before_month_end() {
+            assert!(MonthEndSchedule::from_builder(&CronExpressionBuilder::new()).is_err());
+        }
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod tests {
+    use natural_cron::quartz::QuartzConverter;
+
+    mod to_quartz {
+        use super::*;
+
+        #[test]
+        fn adds_seconds_and_question_mark() {
+            assert_eq!(
+                QuartzConverter::to_quartz("30 8 * * *"),
+                Ok("0 30 8 * * ?".to_string())
+            );
+        }
+
+        #[test]
+        fn shifts_day_of_week_numbers() {
+            assert_eq!(
+                QuartzConverter::to_quartz("0 9 * * 1-5"),
+                Ok("0 0 9 ? * 2-6".to_string())
+            );
+            assert_eq!(
+                QuartzConverter::to_quartz("0 9 * * 0,6"),
+                Ok("0 0 9 ? * 1,7".to_string())
+            );
+        }
+
+        #[test]
+        fn keeps_seconds_of_six_field_expressions() {
+            assert_eq!(
+                QuartzConverter::to_quartz("*/10 * * * * *"),
+                Ok("*/10 * * * * ?".to_string())
+            );
+        }
+
+        #[test]
+        fn rejects_both_day_fields() {
+            assert!(QuartzConverter::to_quartz("0 0 1 * 1").is_err());
+        }
+    }
+
+    mod from_quartz {
+        use super::*;
+
+        #[test]
+        fn drops_seconds_year_and_question_mark() {
+            assert_eq!(
+                QuartzConverter::from_quartz("0 0 12 ? * MON-FRI *"),
+                Ok("0 12 * * 1-5".to_string())
+            );
+            assert_eq!(
+                QuartzConverter::from_quartz("0 15 10 1 * ?"),
+                Ok("15 10 1 * *".to_string())
+            );
+        }
+
+        #[test]
+        fn shifts_day_of_week_numbers() {
+            assert_eq!(
+                QuartzConverter::from_quartz("0 0 9 ? * 1,7"),
+                Ok("0 9 * * 0,6".to_string())
+            );
+        }
+
+        #[test]
+        fn rejects_quartz_only_features() {
+            assert_eq!(
+                QuartzConverter::from_quartz("0 0 0 L * ?"),
+                Err(
+                    "Cannot convert '0 0 0 L * ?' to cron: 'L' has no cron equivalent.".to_string()
+                )
+            );
+            assert!(QuartzConverter::from_quartz("0 0 0 ? * 6#3").is_err());
+            assert!(QuartzConverter::from_quartz("30 0 0 * * ?").is_err());
+            assert!(QuartzConverter::from_quartz("0 0 0 * * ? 2030").is_err());
+        }
+    }
+
+    mod validate {
+        use super::*;
+
+        #[test]
+        fn accepts_quartz_day_features() {
+            for expression in [
+                "0 0 12 L * ?",
+                "0 0 12 L-3 * ?",
+                "0 0 12 LW * ?",
+                "0 0 12 15W * ?",
+                "0 0 12 ? * 6L",
+                "0 0 12 ? * MON#1",
+                "0 0 12 29 2 ? 2028",
+            ] {
+                assert_eq!(
+                    QuartzConverter::validate(expression),
+                    Ok(()),
+                    "{}",
+                    expression
+                );
+            }
+        }
+
+        #[test]
+        fn rejects_dates_that_never_exist() {
+            for expression in [
+                "0 0 12 30 2 ?",
+                "0 0 12 30W 2 ?",
+                "0 0 12 L-29 2 ?",
+                "0 0 12 29 2 ? 2025-2027",
+            ] {
+                assert_eq!(
+                    QuartzConverter::validate(expression),
+                    Err(format!(
+                        "Invalid schedule: '{}'. No date can ever match it.",
+                        expression
+                    ))
+                );
+            }
+        }
+
+        #[test]
+        fn checks_nth_weekday_against_the_calendar() {
+            // February 2026 starts on a Sunday and has only four of each weekday
+            assert!(QuartzConverter::validate("0 0 12 ? 2 1#5 2026").is_err());
+            // February 2032 starts on a Sunday and is a leap month
+            assert_eq!(QuartzConverter::validate("0 0 12 ? 2 1#5 2032"), Ok(()));
+            assert_eq!(QuartzConverter::validate("0 0 12 ? 2 1#5"), Ok(()));
+        }
+
+        #[test]
+        fn requires_exactly_one_question_mark() {
+            assert!(QuartzConverter::validate("0 0 12 * * *").is_err());
+            assert!(QuartzConverter::validate("0 0 12 ? * ?").is_err());
+            assert!(QuartzConverter::validate("0 0 12 ? * MON#6").is_err());
+        }
+    }
+
+    mod from_builder {
+        use natural_cron::CronExpressionBuilder;
+
+        use super::*;
+
+        fn at_six_pm() -> CronExpressionBuilder {
+            CronExpressionBuilder::new().at_time("18:00").unwrap()
+        }
+
+        #[test]
+        fn uses_lw_for_the_last_business_day() {
+            let builder = at_six_pm().on_last_business_day().unwrap();
+            assert_eq!(
+                QuartzConverter::from_builder(&builder),
+                Ok("0 0 18 LW * ?".to_string())
+            );
+            let builder = at_six_pm().on_business_day(1).unwrap();
+            assert_eq!(
+                QuartzConverter::from_builder(&builder),
+                Ok("0 0 18 1W * ?".to_string())
+            );
+        }
+
+        #[test]
+        fn uses_l_for_days_before_month_end() {
+            let builder = at_six_pm().on_days_before_month_end(3).unwrap();
+            assert_eq!(
+                QuartzConverter::from_builder(&builder),
+                Ok("0 0 18 L-3 * ?".to_string())
+            );
+            let builder = at_six_pm().on_days_before_month_end(0).unwrap();
+            assert_eq!(
+                QuartzConverter::from_builder(&builder),
+                Ok("0 0 18 L * ?".to_string())
+            );
+            let builder = at_six_pm()
+                .on_days_before_month_end(3)
+                .unwrap()
+                .on_week_days(vec![1])
+                .unwrap();
+            assert_eq!(
+                QuartzConverter::from_builder(&builder),
+                Err("Cannot convert 3 days before the end of the month to Quartz: the day fields of '0 18 * * 1' are already set.".to_string())
+            );
+        }
+
+        #[test]
+        fn converts_plain_schedules() {
+            let builder = at_six_pm().on_week_days(vec![1, 2, 3, 4, 5]).unwrap();
+            assert_eq!(
+                QuartzConverter::from_builder(&builder),
+                Ok("0 0 18 ? * 2-6".to_string())
+            );
+        }
+
+        #[test]
+        fn rejects_business_days_quartz_cannot_express() {
+            let builder = at_six_pm().on_business_day(3).unwrap();
+            assert_eq!(
+                QuartzConverter::from_builder(&builder),
+                Err("Cannot convert the 3rd business day of the month to Quartz: Quartz can only express the first and last.".to_string())
+            );
+            let holiday = chrono::NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+            let builder = at_six_pm()
+                .on_last_business_day()
+                .unwrap()
+                .with_holidays(vec![holiday])
+                .unwrap();
+            assert!(QuartzConverter::from_builder(&builder).is_err());
+        }
+    }
+}
@@ -0,0 +1,216 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use natural_cron::CronExpressionBuilder;
+    use natural_cron::rrule::{Frequency, RRuleConverter, RRuleInterval};
+
+    use crate::common::at;
+
+    fn expressions(text: &str) -> Result<Vec<String>, String> {
+        RRuleConverter::import(text).map(|imported| imported.expressions)
+    }
+
+    mod export {
+        use super::*;
+
+        #[test]
+        fn picks_a_natural_frequency() {
+            let cases = [
+                ("* * * * *", "FREQ=MINUTELY"),
+                ("*/15 * * * *", "FREQ=HOURLY;BYMINUTE=0,15,30,45"),
+                ("* 9 * * *", "FREQ=MINUTELY;BYHOUR=9"),
+                ("30 8 * * *", "FREQ=DAILY;BYHOUR=8;BYMINUTE=30"),
+                (
+                    "0 9 * * 1-5",
+                    "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=0",
+                ),
+                (
+                    "0 0 1,15 * *",
+                    "FREQ=MONTHLY;BYMONTHDAY=1,15;BYHOUR=0;BYMINUTE=0",
+                ),
+                (
+                    "0 12 25 12 *",
+                    "FREQ=YEARLY;BYMONTH=12;BYMONTHDAY=25;BYHOUR=12;BYMINUTE=0",
+                ),
+                (
+                    "0 6 * 6-8 6",
+                    "FREQ=DAILY;BYMONTH=6,7,8;BYDAY=SA;BYHOUR=6;BYMINUTE=0",
+                ),
+                ("* * * * * *", "FREQ=SECONDLY"),
+                (
+                    "15 30 8 * * *",
+                    "FREQ=DAILY;BYHOUR=8;BYMINUTE=30;BYSECOND=15",
+                ),
+            ];
+            for (expression, rrule) in cases {
+                assert_eq!(
+                    RRuleConverter::to_rrule(expression),
+                    Ok(rrule.to_string()),
+                    "{}",
+                    expression
+                );
+            }
+        }
+
+        #[test]
+        fn rejects_both_day_fields() {
+            assert_eq!(
+                RRuleConverter::to_rrule("0 9 1 * 1"),
+                Err("Cannot convert '0 9 1 * 1' to an RRULE: cron runs on days matching either day field, while an RRULE needs both to match.".to_string())
+            );
+        }
+
+        #[test]
+        fn converts_builders() {
+            let builder = CronExpressionBuilder::new()
+                .at_time("17:45")
+                .unwrap()
+                .during_months(vec![1, 7])
+                .unwrap();
+            assert_eq!(
+                RRuleConverter::from_builder(&builder),
+                Ok("FREQ=DAILY;BYMONTH=1,7;BYHOUR=17;BYMINUTE=45".to_string())
+            );
+            let business_day = CronExpressionBuilder::new().on_business_day(1).unwrap();
+            assert!(RRuleConverter::from_builder(&business_day).is_err());
+        }
+
+        #[test]
+        fn round_trips_through_import() {
+            for expression in [
+                "0,15,30,45 * * * *",
+                "30 8 * * *",
+                "0 9 * * 1-5",
+                "0 0 1,15 * *",
+                "0 12 25 12 *",
+                "0 6 * 6-8 6",
+                "15 30 8 * * *",
+            ] {
+                let rrule = RRuleConverter::to_rrule(expression).unwrap();
+                assert_eq!(
+                    expressions(&rrule),
+                    Ok(vec![expression.to_string()]),
+                    "{}",
+                    rrule
+                );
+            }
+        }
+    }
+
+    mod import {
+        use super::*;
+
+        #[test]
+        fn takes_missing_parts_from_the_start() {
+            assert_eq!(
+                expressions("DTSTART:20260105T093000\nRRULE:FREQ=DAILY"),
+                Ok(vec!["30 9 * * *".to_string()])
+            );
+            // 2026-01-05 is a Monday
+            assert_eq!(
+                expressions("DTSTART;TZID=Asia/Kolkata:20260105T093000\nRRULE:FREQ=WEEKLY"),
+                Ok(vec!["30 9 * * 1".to_string()])
+            );
+            assert_eq!(
+                expressions("DTSTART:20260105T093000\nFREQ=MONTHLY;BYMONTH=3,9"),
+                Ok(vec!["30 9 5 3,9 *".to_string()])
+            );
+            assert_eq!(
+                expressions("DTSTART:20260105T093000\nRRULE:FREQ=YEARLY"),
+                Ok(vec!["30 9 5 1 *".to_string()])
+            );
+            assert_eq!(
+                expressions("DTSTART:20260105T093015\nRRULE:FREQ=HOURLY"),
+                Ok(vec!["15 30 * * * *".to_string()])
+            );
+        }
+
+        #[test]
+        fn needs_a_start_for_missing_parts() {
+            assert_eq!(
+                expressions("FREQ=DAILY;BYHOUR=9"),
+                Err("Invalid RRULE: 'FREQ=DAILY;BYHOUR=9' has no BYMINUTE and no DTSTART to take it from.".to_string())
+            );
+            assert_eq!(
+                expressions("FREQ=DAILY;BYHOUR=9;BYMINUTE=0"),
+                Ok(vec!["0 9 * * *".to_string()])
+            );
+        }
+
+        #[test]
+        fn reads_several_rules() {
+            assert_eq!(
+                expressions(
+                    "DTSTART:20260105T090000\nRRULE:FREQ=WEEKLY;BYDAY=MO,WE\nRRULE:FREQ=MONTHLY;BYMONTHDAY=1"
+                ),
+                Ok(vec!["0 9 * * 1,3".to_string(), "0 9 1 * *".to_string()])
+            );
+        }
+
+        #[test]
+        fn returns_what_cron_cannot_express() {
+            let imported = RRuleConverter::import(
+                "DTSTART:20260105T100000\nRRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO;UNTIL=20261231",
+            )
+            .unwrap();
+            assert_eq!(imported.expressions, vec!["0 10 * * 1".to_string()]);
+            assert_eq!(imported.start, Some(at(2026, 1, 5, 10, 0)));
+            assert_eq!(
+                imported.end,
+                NaiveDate::from_ymd_opt(2026, 12, 31)
+                    .unwrap()
+                    .and_hms_opt(23, 59, 59)
+            );
+            assert_eq!(imported.count, None);
+            assert_eq!(
+                imported.interval,
+                Some(RRuleInterval {
+                    every: 2,
+                    frequency: Frequency::Weekly,
+                    anchor: at(2026, 1, 5, 10, 0),
+                })
+            );
+            assert!(!imported.is_plain_cron());
+
+            let counted = RRuleConverter::import("FREQ=DAILY;BYHOUR=8;BYMINUTE=0;COUNT=5").unwrap();
+            assert_eq!(counted.count, Some(5));
+            assert!(
+                RRuleConverter::import("FREQ=DAILY;BYHOUR=8;BYMINUTE=0")
+                    .unwrap()
+                    .is_plain_cron()
+            );
+        }
+
+        #[test]
+        fn rejects_what_cron_cannot_select() {
+            assert_eq!(
+                expressions("FREQ=MONTHLY;BYDAY=1MO;BYHOUR=9;BYMINUTE=0"),
+                Err("Cannot convert 'FREQ=MONTHLY;BYDAY=1MO;BYHOUR=9;BYMINUTE=0' to cron: cron cannot pick the 1MO of a month or year.".to_string())
+            );
+            assert!(expressions("FREQ=MONTHLY;BYMONTHDAY=-1;BYHOUR=9;BYMINUTE=0").is_err());
+            assert!(expressions("FREQ=YEARLY;BYWEEKNO=20;BYHOUR=9;BYMINUTE=0").is_err());
+            assert!(expressions("FREQ=DAILY;BYDAY=MO;BYMONTHDAY=1;BYHOUR=9;BYMINUTE=0").is_err());
+            assert!(
+                expressions("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO;BYHOUR=9;BYMINUTE=0").is_err(),
+                "an interval needs a DTSTART anchor"
+            );
+        }
+
+        #[test]
+        fn rejects_malformed_rules() {
+            assert_eq!(
+                expressions("BYHOUR=9"),
+                Err("Invalid RRULE: 'BYHOUR=9' has no FREQ.".to_string())
+            );
+            assert_eq!(
+                expressions("FREQ=DAILY;BYHOUR=24"),
+                Err("Invalid RRULE BYHOUR: 24. Values should be between 0 and 23.".to_string())
+            );
+            assert!(expressions("FREQ=FORTNIGHTLY").is_err());
+            assert!(expressions("FREQ=DAILY;COUNT=2;UNTIL=20260101").is_err());
+            assert!(expressions("").is_err());
+        }
+    }
+}
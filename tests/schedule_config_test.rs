@@ -0,0 +1,179 @@
+#![cfg(feature = "serde")]
+
+#[cfg(test)]
+mod tests {
+    use natural_cron::schedule_config::{load_schedule, load_schedules};
+    use serde_json::Value;
+
+    fn load(json: &str) -> Result<String, String> {
+        let value: Value = serde_json::from_str(json).unwrap();
+        load_schedule(value).map(|d| d.builder.compile())
+    }
+
+    mod load_schedule {
+        use super::*;
+
+        #[test]
+        fn maps_keys_onto_builder_calls() {
+            assert_eq!(
+                load(r#"{ "at": "08:00", "on_weekdays": ["mon", "tue", "wed", "thu", "fri"] }"#),
+                Ok("0 8 * * 1-5".to_string())
+            );
+            assert_eq!(
+                load(
+                    r#"{ "at": "00:00", "on_days_of_month": [1], "during_months": ["jan", "jul"] }"#
+                ),
+                Ok("0 0 1 1,7 *".to_string())
+            );
+            assert_eq!(load(r#"{ "every": "hour" }"#), Ok("0 * * * *".to_string()));
+        }
+
+        #[test]
+        fn reads_intervals_and_windows() {
+            assert_eq!(
+                load(r#"{ "every": "15 minutes", "between": "09:00-17:00" }"#),
+                Ok("*/15 9-16 * * *".to_string())
+            );
+            assert_eq!(
+                load(r#"{ "every": "2 hours" }"#),
+                Ok("0 */2 * * *".to_string())
+            );
+            assert_eq!(
+                load(r#"{ "every": "hour", "between": "8pm-11pm" }"#),
+                Ok("0 20-22 * * *".to_string())
+            );
+        }
+
+        #[test]
+        fn removes_excluded_values() {
+            assert_eq!(
+                load(
+                    r#"{ "at": "06:00", "except": { "weekdays": ["sat", "sun"], "months": ["aug"] } }"#
+                ),
+                Ok("0 6 * 1,2,3,4,5,6,7,9,10,11,12 1-5".to_string())
+            );
+            assert_eq!(
+                load(r#"{ "every": "hour", "except": { "hours": [12] } }"#),
+                Ok(
+                    "0 0,1,2,3,4,5,6,7,8,9,10,11,13,14,15,16,17,18,19,20,21,22,23 * * *"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn keeps_the_time_zone() {
+            let value: Value =
+                serde_json::from_str(r#"{ "at": "08:00", "timezone": "Europe/Berlin" }"#).unwrap();
+            let definition = load_schedule(value).unwrap();
+            assert_eq!(definition.timezone.as_deref(), Some("Europe/Berlin"));
+        }
+    }
+
+    mod errors {
+        use super::*;
+
+        #[test]
+        fn name_the_offending_key() {
+            assert_eq!(
+                load(r#"{ "at": "25:00" }"#),
+                Err("at: Invalid time format for 'at': 25:00".to_string())
+            );
+            assert_eq!(
+                load(r#"{ "on_weekdays": ["mon", "funday"] }"#),
+                Err("on_weekdays[1]: Invalid weekday name: funday".to_string())
+            );
+            assert_eq!(
+                load(r#"{ "every": "3 fortnights" }"#),
+                Err("every: Invalid time unit for 'every': 3 fortnights".to_string())
+            );
+            assert_eq!(
+                load(r#"{ "between": "09:30-17:00" }"#),
+                Err("between: Only whole hours are supported, got 09:30".to_string())
+            );
+        }
+
+        #[test]
+        fn name_unknown_and_mistyped_keys() {
+            let error = load(r#"{ "at": "08:00", "except": { "weekday": [0] } }"#).unwrap_err();
+            assert!(
+                error.starts_with("except.weekday: unknown field `weekday`"),
+                "{}",
+                error
+            );
+
+            let error = load(r#"{ "on_days_of_month": ["first"] }"#).unwrap_err();
+            assert!(error.starts_with("on_days_of_month[0]: "), "{}", error);
+        }
+
+        #[test]
+        fn include_the_schedule_name() {
+            let value: Value = serde_json::from_str(
+                r#"{ "report": { "at": "08:00" }, "cleanup": { "on_days_of_month": [32] } }"#,
+            )
+            .unwrap();
+            assert_eq!(
+                load_schedules(value).err(),
+                Some(
+                    "cleanup.on_days_of_month: Invalid day of month: 32. Day should be between 1 and 31."
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn reject_conflicting_keys() {
+            assert_eq!(
+                load(r#"{ "at": "08:00", "between": "09:00-17:00" }"#),
+                Err("at: Cannot be combined with 'between'.".to_string())
+            );
+            assert_eq!(
+                load(r#"{ "at": "08:00", "except": { "months": [1,2,3,4,5,6,7,8,9,10,11,12] } }"#),
+                Err("except.months: Excludes every value, so the schedule never runs.".to_string())
+            );
+        }
+
+        #[test]
+        fn reject_narrowing_one_day_field_while_the_other_is_set() {
+            let message = "Cannot narrow one day field while the other is set; cron would run on days matching either.";
+            assert_eq!(
+                load(
+                    r#"{ "at": "08:00", "on_weekdays": ["mon"], "except": { "days_of_month": [1] } }"#
+                ),
+                Err(format!("except.days_of_month: {}", message))
+            );
+            assert_eq!(
+                load(
+                    r#"{ "at": "08:00", "on_days_of_month": [1, 15], "except": { "weekdays": ["sun"] } }"#
+                ),
+                Err(format!("except.weekdays: {}", message))
+            );
+            assert_eq!(
+                load(
+                    r#"{ "at": "08:00", "except": { "weekdays": ["sun"], "days_of_month": [1] } }"#
+                ),
+                Err(format!("except.days_of_month: {}", message))
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "tz")]
+        fn reject_unknown_time_zones() {
+            assert_eq!(
+                load(r#"{ "at": "08:00", "timezone": "Mars/Olympus" }"#),
+                Err("timezone: Unknown time zone: Mars/Olympus".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn loads_named_schedules() {
+        let value: Value = serde_json::from_str(
+            r#"{ "report": { "at": "08:00" }, "poll": { "every": "5 minutes" } }"#,
+        )
+        .unwrap();
+        let schedules = load_schedules(value).unwrap();
+        assert_eq!(schedules["report"].builder.clone().compile(), "0 8 * * *");
+        assert_eq!(schedules["poll"].builder.clone().compile(), "*/5 * * * *");
+    }
+}
@@ -0,0 +1,233 @@
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use natural_cron::CronSchedule;
+    use natural_cron::cron_lint::{CronLinter, LintKind};
+    use natural_cron::schedules::{self, CronDialect, DialectSchedule, standard, with_seconds};
+
+    fn all() -> impl Iterator<Item = &'static (&'static str, DialectSchedule)> {
+        standard::ALL.iter().chain(with_seconds::ALL)
+    }
+
+    mod dialects {
+        use super::*;
+
+        #[test]
+        fn tags_every_constant_with_its_field_count() {
+            for (name, constant) in all() {
+                assert_eq!(
+                    constant.expression().split_whitespace().count(),
+                    constant.dialect().field_count(),
+                    "{}",
+                    name
+                );
+            }
+            assert!(
+                standard::ALL
+                    .iter()
+                    .all(|(_, c)| c.dialect() == CronDialect::Standard)
+            );
+            assert!(
+                with_seconds::ALL
+                    .iter()
+                    .all(|(_, c)| c.dialect() == CronDialect::WithSeconds)
+            );
+        }
+
+        #[test]
+        fn top_level_constants_are_five_field_unless_sub_minute() {
+            assert_eq!(schedules::EVERY_MINUTE.dialect(), CronDialect::Standard);
+            assert_eq!(schedules::EVERY_5_MINUTES.dialect(), CronDialect::Standard);
+            assert_eq!(
+                schedules::MONDAY_TO_FRIDAY_AT_1AM.expression(),
+                "0 1 * * 1-5"
+            );
+            assert_eq!(schedules::EVERY_SECOND.dialect(), CronDialect::WithSeconds);
+        }
+
+        #[test]
+        fn six_field_variants_match_five_field_ones_at_second_zero() {
+            for (name, five) in standard::ALL {
+                let (_, six) = with_seconds::ALL
+                    .iter()
+                    .find(|(other, _)| other == name)
+                    .unwrap_or_else(|| panic!("{} has no six-field variant", name));
+                assert_eq!(six.expression(), format!("0 {}", five.expression()));
+                assert_eq!(six.schedule().seconds(), vec![0]);
+                assert_eq!(six.schedule().minutes(), five.schedule().minutes());
+                assert_eq!(six.schedule().hours(), five.schedule().hours());
+                assert_eq!(
+                    six.schedule().days_of_month(),
+                    five.schedule().days_of_month()
+                );
+                assert_eq!(six.schedule().months(), five.schedule().months());
+                assert_eq!(
+                    six.schedule().days_of_week(),
+                    five.schedule().days_of_week()
+                );
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "does not match its dialect")]
+        fn rejects_expression_of_the_other_dialect() {
+            DialectSchedule::new(CronDialect::Standard, "0 0 * * * *");
+        }
+    }
+
+    mod round_trip {
+        use super::*;
+
+        #[test]
+        fn expressions_parse_to_the_constants() {
+            for (name, constant) in all() {
+                assert_eq!(
+                    CronSchedule::parse(constant.expression()).as_ref(),
+                    Ok(constant.schedule()),
+                    "{}",
+                    name
+                );
+                assert_eq!(
+                    CronSchedule::parse(&constant.schedule().to_string()).as_ref(),
+                    Ok(constant.schedule()),
+                    "{}",
+                    name
+                );
+                assert_eq!(constant.to_string(), constant.expression());
+            }
+        }
+    }
+
+    mod names {
+        use super::*;
+
+        /// Parses times written like `1AM`, `09_30AM`, `NOON` or `MIDNIGHT`
+        fn time(text: &str) -> (u32, u32) {
+            match text {
+                "NOON" => return (12, 0),
+                "MIDNIGHT" => return (0, 0),
+                _ => {}
+            }
+            let (clock, pm) = match text.strip_suffix("PM") {
+                Some(clock) => (clock, true),
+                None => (text.strip_suffix("AM").unwrap(), false),
+            };
+            let (hour, minute) = clock.split_once('_').unwrap_or((clock, "0"));
+            let hour: u32 = hour.parse().unwrap();
+            let hour = match (hour, pm) {
+                (12, false) => 0,
+                (12, true) => 12,
+                (hour, true) => hour + 12,
+                (hour, false) => hour,
+            };
+            (hour, minute.parse().unwrap())
+        }
+
+        /// The expression a constant's name describes
+        fn described_by(name: &str) -> String {
+            if let Some(rest) = name.strip_prefix("EVERY_DAY_AT_") {
+                let (hour, minute) = time(rest);
+                return format!("{} {} * * *", minute, hour);
+            }
+            if let Some(rest) = name.strip_prefix("MONDAY_TO_FRIDAY_AT_") {
+                let (hour, minute) = time(rest);
+                return format!("{} {} * * 1-5", minute, hour);
+            }
+            if let Some(rest) = name.strip_prefix("EVERY_30_MINUTES_FROM_") {
+                let (from, to) = rest.split_once("_TO_").unwrap();
+                assert_eq!(time(to).1, 30, "{}", name);
+                return format!("*/30 {}-{} * * *", time(from).0, time(to).0);
+            }
+            let words: Vec<&str> = name.split('_').collect();
+            match words[..] {
+                ["EVERY", "SECOND"] => "* * * * * *".to_string(),
+                ["EVERY", n, "SECONDS"] => format!("*/{} * * * * *", n),
+                ["EVERY", "MINUTE"] => "* * * * *".to_string(),
+                ["EVERY", n, "MINUTES"] => format!("*/{} * * * *", n),
+                ["EVERY", "HOUR"] => "0 * * * *".to_string(),
+                ["EVERY", n, "HOURS"] => format!("0 */{} * * *", n),
+                ["EVERY", "2ND", "HOUR"] => "0 */2 * * *".to_string(),
+                ["EVERY", "2ND", "HOUR", "FROM", "1AM", "THROUGH", "11PM"] => {
+                    "0 1,3,5,7,9,11,13,15,17,19,21,23 * * *".to_string()
+                }
+                ["EVERY", "WEEK"] => "0 0 * * SUN".to_string(),
+                ["EVERY", "WEEKDAY"] => "0 0 * * MON-FRI".to_string(),
+                ["EVERY", "WEEKEND"] => "0 0 * * SAT,SUN".to_string(),
+                ["EVERY", "1ST", "DAY", "OF", "MONTH", "AT", at] => {
+                    let (hour, minute) = time(at);
+                    format!("{} {} 1 * *", minute, hour)
+                }
+                ["EVERY", "2ND", "MONTH"] => "0 0 1 1,3,5,7,9,11 *".to_string(),
+                ["EVERY", "QUARTER"] => "0 0 1 1,4,7,10 *".to_string(),
+                ["EVERY", "6", "MONTHS"] => "0 0 1 1,7 *".to_string(),
+                ["EVERY", "YEAR"] => "0 0 1 JAN *".to_string(),
+                _ => panic!("No expectation for {}", name),
+            }
+        }
+
+        #[test]
+        fn constants_match_their_names() {
+            for (name, constant) in all() {
+                let mut expected = described_by(name);
+                if constant.dialect() == CronDialect::WithSeconds
+                    && expected.split_whitespace().count() == 5
+                {
+                    expected = format!("0 {}", expected);
+                }
+                assert_eq!(
+                    CronSchedule::parse(&expected).as_ref(),
+                    Ok(constant.schedule()),
+                    "{}",
+                    name
+                );
+            }
+        }
+
+        #[test]
+        fn windowed_constants_run_from_start_to_end_of_their_window() {
+            let day = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+            for (name, constant) in all() {
+                let Some(rest) = name.strip_prefix("EVERY_30_MINUTES_FROM_") else {
+                    continue;
+                };
+                let (from, to) = rest.split_once("_TO_").unwrap();
+                let runs = constant.schedule().occurrences_between(
+                    &day.and_hms_opt(0, 0, 0).unwrap(),
+                    &day.and_hms_opt(23, 59, 59).unwrap(),
+                );
+                let (from, to) = (time(from), time(to));
+                assert_eq!(
+                    runs.first(),
+                    Some(&day.and_hms_opt(from.0, from.1, 0).unwrap()),
+                    "{}",
+                    name
+                );
+                assert_eq!(
+                    runs.last(),
+                    Some(&day.and_hms_opt(to.0, to.1, 0).unwrap()),
+                    "{}",
+                    name
+                );
+            }
+        }
+
+        #[test]
+        fn lints_hour_steps_that_restart_at_midnight() {
+            for constant in [
+                schedules::EVERY_5_HOURS,
+                schedules::EVERY_7_HOURS,
+                schedules::EVERY_9_HOURS,
+                schedules::EVERY_10_HOURS,
+                schedules::EVERY_11_HOURS,
+            ] {
+                let warnings = CronLinter::lint(constant.expression()).unwrap();
+                assert_eq!(warnings.len(), 1, "{}", constant.expression());
+                assert_eq!(warnings[0].kind, LintKind::UnevenStep);
+            }
+            assert_eq!(
+                CronLinter::lint(schedules::EVERY_6_HOURS.expression()),
+                Ok(vec![])
+            );
+        }
+    }
+}
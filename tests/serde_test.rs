@@ -0,0 +1,321 @@
+#![cfg(feature = "serde")]
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use natural_cron::blackout::Blackout;
+    use natural_cron::calendar::Calendar;
+    #[allow(deprecated)]
+    use natural_cron::interfaces::{CronTimeUnit, ScheduleValue};
+    use natural_cron::{CronExpressionBuilder, CronSchedule};
+    use serde::{Deserialize, Serialize};
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn serializes_as_cron_string() {
+            let builder = CronExpressionBuilder::new().at_time("08:00").unwrap();
+            assert_eq!(serde_json::to_string(&builder).unwrap(), "\"0 8 * * *\"");
+        }
+
+        #[test]
+        fn serializes_runtime_filters_as_structured_object() {
+            let builder = CronExpressionBuilder::new()
+                .every_x(5, CronTimeUnit::Minute)
+                .and_then(|s| s.except(Blackout::schedule("* 2-3 * * 0")?))
+                .unwrap();
+            assert_eq!(
+                serde_json::to_string(&builder).unwrap(),
+                r#"{"minutes":"*/5","except":[{"schedule":"* 2,3 * * 0"}]}"#
+            );
+        }
+
+        #[test]
+        fn round_trips_steps_in_structured_object() {
+            let builders = [
+                CronExpressionBuilder::new()
+                    .every_x(5, CronTimeUnit::Minute)
+                    .and_then(|s| s.at_hours(vec![9, 10, 11]))
+                    .and_then(|s| s.on_last_business_day()),
+                CronExpressionBuilder::new()
+                    .every_x(2, CronTimeUnit::Hour)
+                    .and_then(|s| s.on_odd_weeks()),
+                CronExpressionBuilder::new()
+                    .every_x(3, CronTimeUnit::Month)
+                    .and_then(|s| s.on_days_before_month_end(0)),
+                CronExpressionBuilder::from_expression("0 */6 */2 * *")
+                    .and_then(|s| s.except(Blackout::schedule("* 2-3 * * 0")?)),
+            ];
+            for builder in builders {
+                let builder = builder.unwrap();
+                let json = serde_json::to_string(&builder).unwrap();
+                let restored: CronExpressionBuilder = serde_json::from_str(&json).unwrap();
+                assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+                assert_eq!(restored.compile(), builder.compile(), "{}", json);
+            }
+
+            let error = serde_json::from_str::<CronExpressionBuilder>(r#"{ "hours": "9-17" }"#)
+                .unwrap_err()
+                .to_string();
+            assert!(
+                error.starts_with("Invalid step: '9-17'. Expected '*/n'."),
+                "{}",
+                error
+            );
+        }
+
+        #[test]
+        fn round_trips_structured_object() {
+            let builder = CronExpressionBuilder::new()
+                .at_times(vec!["08:00", "17:30"])
+                .and_then(|s| s.during_months(vec![1, 7]))
+                .and_then(|s| {
+                    s.except(Blackout::date_range(
+                        NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(),
+                        NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(),
+                    )?)
+                })
+                .and_then(|s| s.except(Blackout::time_window("12:00", "13:00")?))
+                .and_then(|s| s.on_last_business_day())
+                .and_then(|s| s.with_calendar(Calendar::parse("weekend fri sat\nfixed jan 1")?))
+                .unwrap();
+            let json = serde_json::to_string(&builder).unwrap();
+            assert_eq!(
+                json,
+                concat!(
+                    r#"{"at":["08:00","17:30"],"months":["jan","jul"],"#,
+                    r#""except":[{"date_range":{"start":"2025-12-24","end":"2025-12-26"}},"#,
+                    r#"{"time_window":{"start":"12:00","end":"13:00"}}],"#,
+                    r#""business_day":"last","calendar":"weekend fri sat\nfixed jan 1"}"#
+                )
+            );
+            let restored: CronExpressionBuilder = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, builder);
+        }
+
+        #[test]
+        fn round_trips_runtime_days() {
+            let builders = [
+                CronExpressionBuilder::new()
+                    .at_time("09:00")
+                    .and_then(|s| s.on_business_day(3)),
+                CronExpressionBuilder::new()
+                    .at_hours(vec![6, 18])
+                    .and_then(|s| s.on_days_before_month_end(2)),
+                CronExpressionBuilder::new()
+                    .at_time("10:00")
+                    .and_then(|s| s.on_week_days(vec![1]))
+                    .and_then(|s| s.every_other_week(NaiveDate::from_ymd_opt(2025, 1, 6).unwrap())),
+                CronExpressionBuilder::new()
+                    .every("minute")
+                    .and_then(|s| s.at_hours(vec![9]))
+                    .and_then(|s| s.at_seconds(vec![30]))
+                    .and_then(|s| s.on_odd_weeks()),
+            ];
+            for builder in builders {
+                let builder = builder.unwrap();
+                let json = serde_json::to_string(&builder).unwrap();
+                let restored: CronExpressionBuilder = serde_json::from_str(&json).unwrap();
+                assert_eq!(restored, builder, "{}", json);
+            }
+        }
+
+        #[test]
+        fn serializes_structured_object_with_helper() {
+            #[derive(Serialize, Deserialize)]
+            struct Job {
+                #[serde(with = "natural_cron::serde_support::structured")]
+                schedule: CronExpressionBuilder,
+            }
+
+            let schedule = CronExpressionBuilder::new()
+                .at_time("08:00")
+                .and_then(|s| s.on_week_days(vec![1, 5]))
+                .unwrap();
+            let json = serde_json::to_string(&Job { schedule }).unwrap();
+            assert_eq!(
+                json,
+                r#"{"schedule":{"at":"08:00","weekdays":["mon","fri"]}}"#
+            );
+            let job: Job = serde_json::from_str(&json).unwrap();
+            assert_eq!(job.schedule.compile(), "0 8 * * 1,5");
+
+            let job: Job = serde_json::from_str(r#"{"schedule":"0 8 * * 1,5"}"#).unwrap();
+            assert_eq!(job.schedule.compile(), "0 8 * * 1,5");
+        }
+
+        #[test]
+        fn deserializes_from_cron_string() {
+            let builder: CronExpressionBuilder =
+                serde_json::from_str("\"*/15 9-17 * * MON-FRI\"").unwrap();
+            assert_eq!(builder.compile(), "*/15 9-17 * * MON-FRI");
+        }
+
+        #[test]
+        fn deserializes_from_structured_object() {
+            let builder: CronExpressionBuilder =
+                serde_json::from_str(r#"{ "at": "08:00", "weekdays": ["mon", "Friday"] }"#)
+                    .unwrap();
+            assert_eq!(builder.compile(), "0 8 * * 1,5");
+
+            let builder: CronExpressionBuilder = serde_json::from_str(
+                r#"{ "every_x": { "interval": 15, "unit": "minute" }, "hours": [9, 10, 11] }"#,
+            )
+            .unwrap();
+            assert_eq!(builder.compile(), "*/15 9-11 * * *");
+
+            let builder: CronExpressionBuilder = serde_json::from_str(
+                r#"{ "at": "00:00", "days_of_month": [1], "months": ["jan", 7] }"#,
+            )
+            .unwrap();
+            assert_eq!(builder.compile(), "0 0 1 1,7 *");
+        }
+
+        #[test]
+        fn round_trips() {
+            let builder = CronExpressionBuilder::new()
+                .at_time("17:30")
+                .and_then(|s| s.on_days_of_month(vec![1, 15]))
+                .unwrap();
+            let json = serde_json::to_string(&builder).unwrap();
+            let restored: CronExpressionBuilder = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.compile(), "30 17 1,15 * *");
+        }
+
+        #[test]
+        fn round_trips_times_needing_several_lines() {
+            let builder = CronExpressionBuilder::new()
+                .at_times(vec!["08:00", "17:30"])
+                .and_then(|s| s.on_week_days(vec![1, 2, 3, 4, 5]))
+                .unwrap();
+            let json = serde_json::to_string(&builder).unwrap();
+            assert_eq!(json, r#"["0 8 * * 1-5","30 17 * * 1-5"]"#);
+            let restored: CronExpressionBuilder = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                restored.compile_lines(),
+                Ok(vec!["0 8 * * 1-5".to_string(), "30 17 * * 1-5".to_string()])
+            );
+
+            let error =
+                serde_json::from_str::<CronExpressionBuilder>(r#"["0 8 * * 1", "30 17 * * 2"]"#)
+                    .unwrap_err()
+                    .to_string();
+            assert!(
+                error.starts_with(
+                    "Invalid cron lines: they may only differ in their minutes and hours."
+                ),
+                "{}",
+                error
+            );
+        }
+
+        #[test]
+        fn round_trips_a_time_with_seconds() {
+            let builder = CronExpressionBuilder::new()
+                .with_seconds()
+                .at_time("08:00:15")
+                .unwrap();
+            let json = serde_json::to_string(&builder).unwrap();
+            assert_eq!(json, "\"15 0 8 * * *\"");
+            let restored: CronExpressionBuilder = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.compile(), "15 0 8 * * *");
+        }
+
+        #[test]
+        fn round_trips_times_with_seconds_needing_several_lines() {
+            let builder = CronExpressionBuilder::new()
+                .with_seconds()
+                .at_times(vec!["08:00:30", "17:30:30"])
+                .unwrap();
+            let json = serde_json::to_string(&builder).unwrap();
+            assert_eq!(json, r#"["30 0 8 * * *","30 30 17 * * *"]"#);
+            let restored: CronExpressionBuilder = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                restored.compile_lines(),
+                Ok(vec![
+                    "30 0 8 * * *".to_string(),
+                    "30 30 17 * * *".to_string()
+                ])
+            );
+        }
+
+        #[test]
+        fn validates_on_deserialize() {
+            let error = serde_json::from_str::<CronExpressionBuilder>("\"0 24 * * *\"")
+                .unwrap_err()
+                .to_string();
+            assert!(error.starts_with("Invalid hour: '24'"), "{}", error);
+
+            let error = serde_json::from_str::<CronExpressionBuilder>(r#"{ "at": "25:00" }"#)
+                .unwrap_err()
+                .to_string();
+            assert!(
+                error.starts_with("Invalid time format for 'at': 25:00"),
+                "{}",
+                error
+            );
+
+            let error =
+                serde_json::from_str::<CronExpressionBuilder>(r#"{ "weekdays": ["funday"] }"#)
+                    .unwrap_err()
+                    .to_string();
+            assert!(
+                error.starts_with("Invalid weekday name: funday"),
+                "{}",
+                error
+            );
+
+            assert!(serde_json::from_str::<CronExpressionBuilder>(r#"{ "when": "now" }"#).is_err());
+        }
+    }
+
+    mod schedule {
+        use super::*;
+
+        #[test]
+        fn round_trips_through_cron_string() {
+            let schedule = CronSchedule::parse("0 9 * * MON-FRI").unwrap();
+            let json = serde_json::to_string(&schedule).unwrap();
+            assert_eq!(json, "\"0 9 * * 1-5\"");
+            assert_eq!(
+                serde_json::from_str::<CronSchedule>(&json).unwrap(),
+                schedule
+            );
+        }
+
+        #[test]
+        fn validates_on_deserialize() {
+            assert!(serde_json::from_str::<CronSchedule>("\"61 * * * *\"").is_err());
+        }
+    }
+
+    mod interfaces {
+        use super::*;
+
+        #[test]
+        fn uses_snake_case_time_units() {
+            assert_eq!(
+                serde_json::to_string(&CronTimeUnit::DayOfMonth).unwrap(),
+                "\"day_of_month\""
+            );
+            assert_eq!(
+                serde_json::from_str::<CronTimeUnit>("\"day_of_week\"").unwrap(),
+                CronTimeUnit::DayOfWeek
+            );
+        }
+
+        #[test]
+        #[allow(deprecated)]
+        fn keeps_schedule_values_plain() {
+            assert_eq!(
+                serde_json::to_string(&ScheduleValue::Number(5)).unwrap(),
+                "5"
+            );
+            assert_eq!(
+                serde_json::from_str::<ScheduleValue>("\"*/5\"").unwrap(),
+                ScheduleValue::String("*/5".to_string())
+            );
+        }
+    }
+}
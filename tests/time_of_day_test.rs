@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+    use natural_cron::{CronExpressionBuilder, TimeOfDay};
+
+    fn time(hour: u32, minute: u32, second: u32) -> TimeOfDay {
+        TimeOfDay::new(hour, minute, second).unwrap()
+    }
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn reads_the_24_hour_clock() {
+            assert_eq!("08:30".parse(), Ok(time(8, 30, 0)));
+            assert_eq!("7:5".parse(), Ok(time(7, 5, 0)));
+            assert_eq!("23:59:59".parse(), Ok(time(23, 59, 59)));
+        }
+
+        #[test]
+        fn reads_the_12_hour_clock() {
+            assert_eq!("8:30pm".parse(), Ok(time(20, 30, 0)));
+            assert_eq!("8 AM".parse(), Ok(time(8, 0, 0)));
+            assert_eq!("12am".parse(), Ok(time(0, 0, 0)));
+            assert_eq!("12:15 pm".parse(), Ok(time(12, 15, 0)));
+            assert_eq!("11:59:30pm".parse(), Ok(time(23, 59, 30)));
+        }
+
+        #[test]
+        fn reads_named_times() {
+            assert_eq!("noon".parse(), Ok(TimeOfDay::NOON));
+            assert_eq!(" Midnight ".parse(), Ok(TimeOfDay::MIDNIGHT));
+        }
+
+        #[test]
+        fn rejects_malformed_times() {
+            for text in [
+                "",
+                "8",
+                "24:00",
+                "23:60",
+                "8:30:60",
+                "13pm",
+                "0am",
+                "8:",
+                ":30",
+                "8:300",
+                "+8:30",
+                "8:30:15:00",
+                "8.30",
+                "noonish",
+            ] {
+                assert_eq!(
+                    text.parse::<TimeOfDay>(),
+                    Err(format!("Invalid time format for 'at': {}", text)),
+                    "{}",
+                    text
+                );
+            }
+        }
+    }
+
+    mod conversions {
+        use super::*;
+
+        #[test]
+        fn round_trips_through_naive_time() {
+            let naive = NaiveTime::from_hms_opt(18, 45, 10).unwrap();
+            assert_eq!(TimeOfDay::from(naive), time(18, 45, 10));
+            assert_eq!(NaiveTime::from(time(18, 45, 10)), naive);
+        }
+
+        #[test]
+        fn displays_seconds_only_when_set() {
+            assert_eq!(time(8, 5, 0).to_string(), "08:05");
+            assert_eq!(time(8, 5, 9).to_string(), "08:05:09");
+        }
+
+        #[test]
+        fn rejects_out_of_range_parts() {
+            assert_eq!(
+                TimeOfDay::new(8, 0, 60),
+                Err("Invalid second: 60. Second should be between 0 and 59.".to_string())
+            );
+            assert!(TimeOfDay::new(24, 0, 0).is_err());
+        }
+    }
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn accepts_typed_and_written_times() {
+            let compile =
+                |builder: Result<CronExpressionBuilder, String>| builder.unwrap().compile();
+            assert_eq!(
+                compile(CronExpressionBuilder::new().at_time(time(14, 5, 0))),
+                "5 14 * * *"
+            );
+            assert_eq!(
+                compile(CronExpressionBuilder::new().at_time("noon")),
+                "0 12 * * *"
+            );
+            assert_eq!(
+                compile(CronExpressionBuilder::new().at_time(String::from("9:15pm"))),
+                "15 21 * * *"
+            );
+        }
+
+        #[test]
+        fn writes_seconds_once_enabled() {
+            let builder = CronExpressionBuilder::new().with_seconds();
+            assert_eq!(builder.clone().compile(), "0 * * * * *");
+            assert_eq!(
+                builder.clone().at_time("08:30:15").unwrap().compile(),
+                "15 30 8 * * *"
+            );
+            assert_eq!(
+                builder
+                    .at_times(vec!["08:00:30", "17:30:30"])
+                    .unwrap()
+                    .compile_lines(),
+                Ok(vec![
+                    "30 0 8 * * *".to_string(),
+                    "30 30 17 * * *".to_string()
+                ])
+            );
+        }
+
+        #[test]
+        fn rejects_differing_seconds_on_one_line() {
+            assert_eq!(
+                CronExpressionBuilder::new()
+                    .with_seconds()
+                    .at_times(vec!["08:00:00", "08:00:30"]),
+                Err(
+                    "Invalid times: a single seconds field cannot hold different seconds."
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn matches_blackouts_on_seconds() {
+            use natural_cron::blackout::Blackout;
+
+            let builder = CronExpressionBuilder::new()
+                .with_seconds()
+                .at_time("09:00:30")
+                .unwrap();
+            let other_second = Blackout::schedule("0 * * * * *").unwrap();
+            assert_eq!(
+                builder.clone().except(other_second).unwrap().try_compile(),
+                Ok("30 0 9 * * *".to_string())
+            );
+            let same_second = Blackout::schedule("30 * * * * *").unwrap();
+            assert!(builder.except(same_second).unwrap().try_compile().is_err());
+        }
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use chrono_tz::America::{Bogota, New_York};
+    use chrono_tz::Asia::{Kathmandu, Kolkata, Tokyo};
+    use chrono_tz::Europe::London;
+    use natural_cron::CronExpressionBuilder;
+    use natural_cron::utc::UtcTranslator;
+
+    fn lines(values: &[&str]) -> Result<Vec<String>, String> {
+        Ok(values.iter().map(|line| line.to_string()).collect())
+    }
+
+    mod fixed_offsets {
+        use super::*;
+
+        #[test]
+        fn shifts_half_hour_offsets() {
+            assert_eq!(
+                UtcTranslator::translate("0 9 * * 1-5", &Kolkata, 2026),
+                lines(&["30 3 * * 1-5"])
+            );
+            assert_eq!(
+                UtcTranslator::translate("*/15 9-17 * * *", &Kolkata, 2026),
+                lines(&["30,45 3-11 * * *", "0,15 4-12 * * *"])
+            );
+            assert_eq!(
+                UtcTranslator::translate("0 0 * * *", &Kathmandu, 2026),
+                lines(&["15 18 * * *"])
+            );
+        }
+
+        #[test]
+        fn moves_days_across_midnight() {
+            // 01:00 in India is 19:30 the evening before in UTC
+            assert_eq!(
+                UtcTranslator::translate("0 1 * * 1-5", &Kolkata, 2026),
+                lines(&["30 19 * * 0-4"])
+            );
+            assert_eq!(
+                UtcTranslator::translate("0 21 * * 5", &Bogota, 2026),
+                lines(&["0 2 * * 6"])
+            );
+            assert_eq!(
+                UtcTranslator::translate("0 2 15 * *", &Tokyo, 2026),
+                lines(&["0 17 14 * *"])
+            );
+            // Runs on both sides of midnight in UTC need a line each
+            assert_eq!(
+                UtcTranslator::translate("0 6,12 * * 1", &Tokyo, 2026),
+                lines(&["0 21 * * 0", "0 3 * * 1"])
+            );
+        }
+
+        #[test]
+        fn keeps_whole_days_on_one_line() {
+            assert_eq!(
+                UtcTranslator::translate("* * * * *", &Kolkata, 2026),
+                lines(&["* * * * *"])
+            );
+            assert_eq!(
+                UtcTranslator::translate("30 0 9 * * *", &Kolkata, 2026),
+                lines(&["30 30 3 * * *"])
+            );
+        }
+
+        #[test]
+        fn rejects_days_moving_into_another_month() {
+            assert_eq!(
+                UtcTranslator::translate("0 2 1 * *", &Tokyo, 2026),
+                Err("Cannot translate '0 2 1 * *' to UTC: runs on day 1 of the month would move into another month.".to_string())
+            );
+            assert!(UtcTranslator::translate("0 21 28 * *", &Bogota, 2026).is_err());
+            assert!(UtcTranslator::translate("0 2 31 * *", &Tokyo, 2026).is_err());
+            assert_eq!(
+                UtcTranslator::translate("0 1 * 1 *", &Kolkata, 2026),
+                Err("Cannot translate '0 1 * 1 *' to UTC: runs moved across midnight would leave or enter the months it is limited to.".to_string())
+            );
+        }
+    }
+
+    mod daylight_saving {
+        use super::*;
+
+        #[test]
+        fn rejects_runs_at_different_offsets() {
+            assert_eq!(
+                UtcTranslator::translate("0 9 * * *", &London, 2026),
+                Err("Cannot translate '0 9 * * *' to UTC: on 2026-03-29 the zone is at +01:00 instead of +00:00, so no static UTC expression covers 2026.".to_string())
+            );
+        }
+
+        #[test]
+        fn accepts_runs_within_one_offset() {
+            assert_eq!(
+                UtcTranslator::translate("0 9 * 1 *", &New_York, 2026),
+                lines(&["0 14 * 1 *"])
+            );
+        }
+    }
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn translates_every_line() {
+            let builder = CronExpressionBuilder::new()
+                .at_times(vec!["08:00", "17:30"])
+                .unwrap()
+                .on_week_days(vec![1, 2, 3, 4, 5])
+                .unwrap();
+            assert_eq!(
+                UtcTranslator::translate_builder(&builder, &Kolkata, 2026),
+                lines(&["30 2 * * 1-5", "0 12 * * 1-5"])
+            );
+        }
+
+        #[test]
+        fn needs_a_cron_expression() {
+            let builder = CronExpressionBuilder::new().on_business_day(1).unwrap();
+            assert!(UtcTranslator::translate_builder(&builder, &Kolkata, 2026).is_err());
+        }
+    }
+}